@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use glyphs::{parse, strip_ansi, visible_len};
+
+/// A few megabytes of log output mixing plain lines, SGR colors, and links.
+fn sample_log() -> String {
+    let mut log = String::new();
+    for i in 0..40_000 {
+        match i % 4 {
+            0 => log.push_str("2024-01-01T00:00:00Z INFO  request handled in 12ms\n"),
+            1 => log.push_str("\x1b[1;32mOK\x1b[0m  build finished \x1b[2m(0.42s)\x1b[0m\n"),
+            2 => log.push_str("\x1b[38;2;249;115;22mwarning\x1b[0m: unused variable `x`\n"),
+            _ => log.push_str("see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\\r\n"),
+        }
+    }
+    log
+}
+
+fn bench_parser(c: &mut Criterion) {
+    let log = sample_log();
+    let mut group = c.benchmark_group("parser");
+    group.throughput(Throughput::Bytes(log.len() as u64));
+    group.bench_function("parse", |b| b.iter(|| parse(black_box(&log))));
+    group.bench_function("strip_ansi", |b| b.iter(|| strip_ansi(black_box(&log))));
+    group.bench_function("visible_len", |b| b.iter(|| visible_len(black_box(&log))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parser);
+criterion_main!(benches);
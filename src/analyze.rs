@@ -0,0 +1,238 @@
+//! Statistics over captured terminal output.
+
+use crate::escape::{Escape, EscapeKind};
+use crate::parser::{parse, ParsedSequence, StreamParser};
+use crate::tracker::StyleTracker;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Counts gathered from a stream of terminal output by [`analyze`] or an
+/// [`Analyzer`].
+///
+/// Meant for catching rendering regressions in CI, such as a TUI that
+/// starts clearing the whole screen on every frame.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{analyze, EscapeKind};
+///
+/// let report = analyze("\x1b[2J\x1b[H\x1b[0m\x1b[1mhi\x1b[0m\x1b[0m");
+/// assert_eq!(report.full_clears, 1);
+/// assert_eq!(report.cursor_moves, 1);
+/// assert_eq!(report.redundant_resets, 2);
+/// assert_eq!(report.count(&EscapeKind::Sgr), 4);
+/// println!("{report}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// Escape sequences seen per kind, in order of first appearance.
+    pub kinds: Vec<(EscapeKind, usize)>,
+    /// Clears of the whole screen (`CSI 2 J`, `CSI 3 J`, or `ESC c`).
+    pub full_clears: usize,
+    /// Cursor movement, save, and restore sequences.
+    pub cursor_moves: usize,
+    /// SGR resets sent while the style was already the default.
+    pub redundant_resets: usize,
+    /// Truncated or invalid sequences.
+    pub malformed: usize,
+    /// Bytes of escape sequences, including malformed ones.
+    pub escape_bytes: usize,
+    /// Bytes of text and control characters.
+    pub text_bytes: usize,
+}
+
+impl Analysis {
+    /// Number of escape sequences of `kind`.
+    #[must_use]
+    pub fn count(&self, kind: &EscapeKind) -> usize {
+        self.kinds
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map_or(0, |(_, n)| *n)
+    }
+
+    /// Total number of escape sequences.
+    #[must_use]
+    pub fn escapes(&self) -> usize {
+        self.kinds.iter().map(|(_, n)| n).sum()
+    }
+
+    /// Share of the output taken up by escape sequences, from `0.0` to
+    /// `1.0`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn overhead(&self) -> f64 {
+        let total = self.escape_bytes + self.text_bytes;
+        if total == 0 {
+            return 0.0;
+        }
+        self.escape_bytes as f64 / total as f64
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "escapes: {}", self.escapes())?;
+        for (i, (kind, n)) in self.kinds.iter().enumerate() {
+            let sep = if i == 0 { " (" } else { ", " };
+            write!(f, "{sep}{kind}: {n}")?;
+        }
+        if !self.kinds.is_empty() {
+            write!(f, ")")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "full clears: {}", self.full_clears)?;
+        writeln!(f, "cursor moves: {}", self.cursor_moves)?;
+        writeln!(f, "redundant resets: {}", self.redundant_resets)?;
+        writeln!(f, "malformed: {}", self.malformed)?;
+        write!(
+            f,
+            "bytes: {} escape, {} text ({:.1}% overhead)",
+            self.escape_bytes,
+            self.text_bytes,
+            self.overhead() * 100.0
+        )
+    }
+}
+
+/// Gathers an [`Analysis`] from output arriving in chunks.
+///
+/// Sequences split across chunks are counted once.
+///
+/// ```rust
+/// use glyphs::Analyzer;
+///
+/// let mut analyzer = Analyzer::new();
+/// analyzer.feed("\x1b[");
+/// analyzer.feed("2Jframe");
+/// assert_eq!(analyzer.finish().full_clears, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Analyzer {
+    report: Analysis,
+    tracker: StyleTracker,
+    parser: StreamParser,
+}
+
+impl Analyzer {
+    /// Create an analyzer with empty counts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a chunk of output and count what it contains.
+    pub fn feed(&mut self, input: &str) {
+        for segment in self.parser.push(input) {
+            self.record(&segment);
+        }
+    }
+
+    /// Count one parsed segment.
+    pub fn record(&mut self, segment: &ParsedSequence) {
+        let report = &mut self.report;
+        match segment {
+            ParsedSequence::Text(text) => report.text_bytes += text.len(),
+            ParsedSequence::Control(c) => report.text_bytes += c.as_char().len_utf8(),
+            ParsedSequence::Malformed { raw, .. } => {
+                report.malformed += 1;
+                report.escape_bytes += raw.len();
+            }
+            ParsedSequence::Escape(escape) => {
+                report.escape_bytes += escape.raw.len();
+                match report.kinds.iter_mut().find(|(k, _)| *k == escape.kind) {
+                    Some((_, n)) => *n += 1,
+                    None => report.kinds.push((escape.kind.clone(), 1)),
+                }
+                if escape.kind == EscapeKind::Cursor {
+                    report.cursor_moves += 1;
+                }
+                if is_full_clear(escape) {
+                    report.full_clears += 1;
+                }
+                if is_reset(escape) && self.tracker.style().is_plain() {
+                    report.redundant_resets += 1;
+                }
+                self.tracker.apply(escape);
+            }
+        }
+    }
+
+    /// The counts so far.
+    #[must_use]
+    pub const fn report(&self) -> &Analysis {
+        &self.report
+    }
+
+    /// Count any buffered incomplete sequence and return the final counts.
+    #[must_use]
+    pub fn finish(mut self) -> Analysis {
+        for segment in self.parser.finish() {
+            self.record(&segment);
+        }
+        self.report
+    }
+}
+
+/// Count the escape sequences, clears, cursor moves, redundant resets, and
+/// byte overhead in `input`.
+#[must_use]
+pub fn analyze(input: &str) -> Analysis {
+    let mut analyzer = Analyzer::new();
+    for segment in parse(input) {
+        analyzer.record(&segment);
+    }
+    analyzer.report
+}
+
+fn is_full_clear(escape: &Escape) -> bool {
+    escape.raw == "\x1bc"
+        || (escape.kind == EscapeKind::Erase
+            && escape.raw.ends_with('J')
+            && matches!(escape.params.first(), Some(2 | 3)))
+}
+
+fn is_reset(escape: &Escape) -> bool {
+    escape.kind == EscapeKind::Sgr
+        && escape.unparsed.is_empty()
+        && escape.params.iter().all(|&p| p == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_analyze_counts() {
+        let report = analyze("\x1b[2J\x1b[3J\x1b[J\x1bc\x1b[5;1Hab\n\x1b[31mc\x1b[m\x1b[0m\x1b[");
+        assert_eq!(report.full_clears, 3);
+        assert_eq!(report.count(&EscapeKind::Erase), 3);
+        assert_eq!(report.count(&EscapeKind::Cursor), 1);
+        assert_eq!(report.count(&EscapeKind::Sgr), 3);
+        assert_eq!(report.cursor_moves, 1);
+        assert_eq!(report.redundant_resets, 1);
+        assert_eq!(report.malformed, 1);
+        assert_eq!(report.text_bytes, 4);
+        assert_eq!(report.kinds[0].0, EscapeKind::Erase);
+    }
+
+    #[test]
+    fn test_analyzer_chunks_and_display() {
+        let mut analyzer = Analyzer::new();
+        analyzer.feed("\x1b[0");
+        analyzer.feed("mabc");
+        let report = analyzer.finish();
+        assert_eq!(report.escapes(), 1);
+        assert_eq!(report.redundant_resets, 1);
+        assert_eq!(report.escape_bytes, 4);
+        assert_eq!(report.text_bytes, 3);
+        assert!(report.to_string().contains("SGR (style): 1"));
+        assert!(report.to_string().ends_with("(57.1% overhead)"));
+
+        let empty = analyze("");
+        assert!(empty.overhead() <= 0.0);
+        assert!(empty.to_string().starts_with("escapes: 0\n"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Classic ANSI art (`.ans`) loading (requires the `art` feature).
+//!
+//! ANSI art files are CP437 text laid out with SGR colors and cursor
+//! movement for an 80-column DOS screen. [`Art`] translates the glyphs to
+//! Unicode, plays the file through the [emulator](crate::emulate), and
+//! keeps the result as styled lines that print on a modern terminal.
+//!
+//! ```rust
+//! use glyphs::art::Art;
+//!
+//! let art = Art::from_bytes(b"\x1b[1;31m\xdb\xdb\x1b[0m\xb0\xb1\xb2\r\n");
+//! assert_eq!(art.plain_lines, vec!["██░▒▓"]);
+//! print!("{art}");
+//! ```
+
+use crate::emulate::TerminalState;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Width of a DOS text screen, used when the file doesn't say otherwise.
+const DEFAULT_WIDTH: u16 = 80;
+
+/// Size of a SAUCE metadata record.
+const SAUCE_LEN: usize = 128;
+
+/// DOS end-of-file marker, which separates the art from its metadata.
+const EOF: u8 = 0x1a;
+
+/// Unicode glyphs for the CP437 bytes 0x80–0xFF.
+const HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Unicode glyphs DOS showed for the CP437 bytes 0x00–0x1F.
+const LOW: [char; 32] = [
+    ' ', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', //
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// Decode CP437 bytes as used by ANSI art.
+///
+/// Printable bytes map to their CP437 glyphs (`0xDB` is `█`, `0x7F` is
+/// `⌂`). Backspace, tab, line feed, carriage return, and escape stay
+/// control characters, as they were for `ANSI.SYS`; the other control
+/// bytes map to the symbols DOS displayed for them (`0x03` is `♥`), and
+/// NUL to a space.
+///
+/// ```rust
+/// use glyphs::art::decode_cp437;
+///
+/// assert_eq!(decode_cp437(b"\x1b[31m\x03 \xc9\xcd\xbb\r\n"), "\x1b[31m♥ ╔═╗\r\n");
+/// ```
+#[must_use]
+pub fn decode_cp437(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x08 | b'\t' | b'\n' | b'\r' | 0x1b | 0x20..=0x7e => char::from(b),
+            0x00..=0x1f => LOW[usize::from(b)],
+            0x7f => '⌂',
+            0x80..=0xff => HIGH[usize::from(b - 0x80)],
+        })
+        .collect()
+}
+
+/// A loaded ANSI art file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Art {
+    /// The rendered rows, each with its own SGR sequences and a closing
+    /// reset, and with trailing blank rows removed.
+    pub lines: Vec<String>,
+    /// The same rows without styling.
+    pub plain_lines: Vec<String>,
+    /// The column count the art was drawn for.
+    pub width: u16,
+    /// The title from the SAUCE record, if the file has one.
+    pub title: Option<String>,
+    /// The author from the SAUCE record, if the file has one.
+    pub author: Option<String>,
+}
+
+impl Art {
+    /// Load ANSI art from the bytes of a `.ans` file.
+    ///
+    /// The width comes from the file's SAUCE record when present and is
+    /// 80 columns otherwise. Anything after the DOS end-of-file marker
+    /// (`0x1A`) is ignored.
+    #[must_use]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let sauce = Sauce::find(data);
+        let width = sauce
+            .as_ref()
+            .and_then(|s| s.width)
+            .unwrap_or(DEFAULT_WIDTH);
+        Self::render(data, width, sauce)
+    }
+
+    /// Load ANSI art drawn for a screen `width` columns wide, ignoring the
+    /// width in the SAUCE record.
+    #[must_use]
+    pub fn with_width(data: &[u8], width: u16) -> Self {
+        Self::render(data, width, Sauce::find(data))
+    }
+
+    /// Read and load a `.ans` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        std::fs::read(path).map(|data| Self::from_bytes(&data))
+    }
+
+    fn render(data: &[u8], width: u16, sauce: Option<Sauce>) -> Self {
+        let end = data.iter().position(|&b| b == EOF).unwrap_or(data.len());
+        let mut screen = TerminalState::growing(width);
+        screen.feed(&decode_cp437(&data[..end]));
+
+        let mut lines = screen.lines();
+        let mut plain_lines = screen.plain_lines();
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+            plain_lines.pop();
+        }
+        let (title, author) = sauce.map_or((None, None), |s| (s.title, s.author));
+        Self {
+            lines,
+            plain_lines,
+            width,
+            title,
+            author,
+        }
+    }
+}
+
+impl fmt::Display for Art {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The parts of a SAUCE record that matter for display.
+struct Sauce {
+    title: Option<String>,
+    author: Option<String>,
+    width: Option<u16>,
+}
+
+impl Sauce {
+    /// Read the SAUCE record at the end of `data`, if there is one.
+    fn find(data: &[u8]) -> Option<Self> {
+        let record = data.get(data.len().checked_sub(SAUCE_LEN)?..)?;
+        if !record.starts_with(b"SAUCE") {
+            return None;
+        }
+        let text = |range: core::ops::Range<usize>| {
+            let value = decode_cp437(&record[range]);
+            let value = value.trim_end_matches([' ', '\0']);
+            (!value.is_empty()).then(|| String::from(value))
+        };
+        // Character data (1) in ASCII, ANSI, or ANSImation (0-2) form
+        // stores its width in TInfo1.
+        let is_text = record[94] == 1 && record[95] <= 2;
+        let width = u16::from_le_bytes([record[96], record[97]]);
+        Some(Self {
+            title: text(7..42),
+            author: text(42..62),
+            width: (is_text && width > 0).then_some(width),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sauce(title: &str, width: u16) -> Vec<u8> {
+        let mut record = b"SAUCE00".to_vec();
+        record.extend(format!("{title:<35}").bytes());
+        record.extend(format!("{:<20}", "artist").bytes());
+        record.resize(94, b' ');
+        record.extend([1, 1]);
+        record.extend(width.to_le_bytes());
+        record.resize(SAUCE_LEN, 0);
+        record
+    }
+
+    #[test]
+    fn test_decode_cp437() {
+        assert_eq!(decode_cp437(b"a\x01\x7f\x80\xff\n"), "a☺⌂Ç\u{a0}\n");
+    }
+
+    #[test]
+    fn test_art_positions_and_wraps() {
+        let data = b"\x1b[3Cab\x1b[1;1Hx\r\n\r\n\xdc\xdc\xdc\xdc\xdc\x1b[0m\r\n\r\n";
+        let art = Art::with_width(data, 4);
+        assert_eq!(art.plain_lines, vec!["x  a", "b", "▄▄▄▄", "▄"]);
+        assert_eq!(art.lines.len(), 4);
+        assert_eq!(art.width, 4);
+        assert_eq!(art.title, None);
+    }
+
+    #[test]
+    fn test_art_sauce() {
+        let mut data = b"\x1b[44m \x1b[0m\r\n".to_vec();
+        data.push(EOF);
+        data.extend(sauce("Title", 40));
+        let art = Art::from_bytes(&data);
+        assert_eq!(art.width, 40);
+        assert_eq!(art.title.as_deref(), Some("Title"));
+        assert_eq!(art.author.as_deref(), Some("artist"));
+        assert_eq!(art.lines, vec!["\x1b[0;44m \x1b[0m"]);
+        assert_eq!(art.to_string(), "\x1b[0;44m \x1b[0m\n");
+    }
+}
@@ -0,0 +1,169 @@
+//! Badges and pills such as ` PASS ` or `build | passing`.
+
+use crate::color::Color;
+use crate::style::Style;
+use alloc::string::String;
+use core::fmt;
+
+/// Powerline rounded left cap used by [`Badge::rounded`].
+pub const ROUND_LEFT: char = '\u{e0b6}';
+
+/// Powerline rounded right cap used by [`Badge::rounded`].
+pub const ROUND_RIGHT: char = '\u{e0b4}';
+
+/// Create a badge showing ` label ` on `bg`.
+///
+/// The foreground is black or bright white, whichever contrasts more with
+/// `bg`.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{badge, strip_ansi, Color};
+///
+/// let pass = badge("PASS", Color::Green).to_string();
+/// assert_eq!(strip_ansi(&pass), " PASS ");
+/// ```
+#[must_use]
+pub fn badge(label: &str, bg: Color) -> Badge {
+    Badge::new(label, bg)
+}
+
+/// Create a two-part badge like the ones on shields.io.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{key_value_badge, strip_ansi, Color};
+///
+/// let status = key_value_badge("build", "passing", Color::BrightBlack, Color::Green);
+/// assert_eq!(strip_ansi(&status.to_string()), " build  passing ");
+/// ```
+#[must_use]
+pub fn key_value_badge(key: &str, value: &str, key_bg: Color, value_bg: Color) -> Badge {
+    Badge::new(key, key_bg).value(value, value_bg)
+}
+
+/// A label on a solid background, optionally followed by a value part and
+/// wrapped in rounded caps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Badge {
+    label: String,
+    bg: Color,
+    fg: Option<Color>,
+    value: Option<(String, Color)>,
+    rounded: bool,
+}
+
+impl Badge {
+    /// Create a badge showing ` label ` on `bg`.
+    #[must_use]
+    pub fn new(label: &str, bg: Color) -> Self {
+        Self {
+            label: String::from(label),
+            bg,
+            fg: None,
+            value: None,
+            rounded: false,
+        }
+    }
+
+    /// Use `fg` for the text instead of picking a contrasting color.
+    #[must_use]
+    pub const fn fg(mut self, fg: Color) -> Self {
+        self.fg = Some(fg);
+        self
+    }
+
+    /// Add a second part showing ` value ` on `bg`.
+    #[must_use]
+    pub fn value(mut self, value: &str, bg: Color) -> Self {
+        self.value = Some((String::from(value), bg));
+        self
+    }
+
+    /// Add powerline rounded caps ([`ROUND_LEFT`], [`ROUND_RIGHT`]).
+    ///
+    /// The caps need a Nerd Font or another powerline-patched font.
+    #[must_use]
+    pub const fn rounded(mut self) -> Self {
+        self.rounded = true;
+        self
+    }
+
+    /// Render to a string with ANSI codes.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if self.rounded {
+            out.push_str(&cap(ROUND_LEFT, self.bg));
+        }
+        out.push_str(&self.part(&self.label, self.bg));
+        let last_bg = match &self.value {
+            Some((value, bg)) => {
+                out.push_str(&self.part(value, *bg));
+                *bg
+            }
+            None => self.bg,
+        };
+        if self.rounded {
+            out.push_str(&cap(ROUND_RIGHT, last_bg));
+        }
+        out
+    }
+
+    fn part(&self, text: &str, bg: Color) -> String {
+        let fg = self.fg.unwrap_or_else(|| contrasting(bg));
+        let mut padded = String::with_capacity(text.len() + 2);
+        padded.push(' ');
+        padded.push_str(text);
+        padded.push(' ');
+        Style::new().fg(fg).bg(bg).apply(&padded)
+    }
+}
+
+impl fmt::Display for Badge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+fn cap(ch: char, color: Color) -> String {
+    let mut buf = [0; 4];
+    Style::new().fg(color).apply(ch.encode_utf8(&mut buf))
+}
+
+/// Black or bright white, whichever contrasts more with `bg`.
+fn contrasting(bg: Color) -> Color {
+    let black = bg.contrast_ratio(&Color::Black).unwrap_or(0.0);
+    let white = bg.contrast_ratio(&Color::BrightWhite).unwrap_or(0.0);
+    if black > white {
+        Color::Black
+    } else {
+        Color::BrightWhite
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{set_color_choice, ColorChoice};
+
+    #[test]
+    fn test_contrasting_foreground() {
+        assert_eq!(contrasting(Color::rgb(255, 220, 0)), Color::Black);
+        assert_eq!(contrasting(Color::rgb(20, 20, 80)), Color::BrightWhite);
+        assert_eq!(contrasting(Color::Default), Color::BrightWhite);
+    }
+
+    #[test]
+    fn test_rounded_key_value() {
+        set_color_choice(ColorChoice::Always);
+        let badge = key_value_badge("ci", "ok", Color::Black, Color::rgb(255, 255, 255)).rounded();
+        assert_eq!(
+            badge.render(),
+            "\x1b[30m\u{e0b6}\x1b[0m\x1b[97;40m ci \x1b[0m\
+             \x1b[30;48;2;255;255;255m ok \x1b[0m\x1b[38;2;255;255;255m\u{e0b4}\x1b[0m"
+        );
+    }
+}
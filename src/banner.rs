@@ -0,0 +1,432 @@
+//! Large banner text rendered from `FIGlet` fonts.
+//!
+//! [`Font`] reads the `FIGlet` `.flf` format; [`Font::block`] is a small
+//! built-in font drawn with half blocks. [`Banner`] renders text in a font
+//! and colors it with a single style or a left-to-right gradient.
+//!
+//! ```rust
+//! use glyphs::banner::Banner;
+//! use glyphs::{strip_ansi, Color};
+//!
+//! let splash = Banner::new("Hi!").gradient(Color::rgb(249, 115, 22), Color::rgb(234, 179, 8));
+//! assert_eq!(
+//!     strip_ansi(&splash.to_string()),
+//!     "█ █ ▀█▀ █\n█▀█  █  ▀\n▀ ▀ ▀▀▀ ▀"
+//! );
+//! ```
+
+use crate::color::Color;
+use crate::error::Error;
+use crate::style::Style;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Source of the built-in [`Font::block`].
+const BLOCK_FONT: &str = include_str!("fonts/block.flf");
+
+/// German characters that follow printable ASCII in a font file.
+const DEUTSCH: [char; 7] = ['Ä', 'Ö', 'Ü', 'ä', 'ö', 'ü', 'ß'];
+
+/// How the characters of a banner are placed next to each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Each character at its full width, as drawn in the font.
+    #[default]
+    Full,
+    /// Characters moved together until they touch (`FIGlet` "kerning").
+    ///
+    /// `FIGlet`'s smushing rules are not implemented; fonts designed for
+    /// smushing render as if fitted.
+    Fitting,
+}
+
+/// A `FIGlet` font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Font {
+    height: usize,
+    hardblank: char,
+    glyphs: BTreeMap<char, Vec<String>>,
+}
+
+impl Font {
+    /// The built-in font: a 3x5 pixel face drawn with half blocks, three
+    /// rows tall. Lowercase letters look like capitals.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // The font is checked by the tests.
+    pub fn block() -> Self {
+        Self::parse(BLOCK_FONT).expect("the built-in font is valid")
+    }
+
+    /// Parse the contents of a `FIGlet` (`.flf`) font file.
+    ///
+    /// Reads the header, printable ASCII, the optional German characters,
+    /// and any code-tagged characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidFont`] if the header is missing or invalid,
+    /// or if a character is cut short.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut lines = source.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let header = lines.next().map_or("", |(_, line)| line);
+        let rest = header
+            .strip_prefix("flf2a")
+            .ok_or(invalid(1, "missing flf2a signature"))?;
+        let hardblank = rest
+            .chars()
+            .next()
+            .ok_or(invalid(1, "missing hard blank"))?;
+        let fields: Vec<&str> = rest[hardblank.len_utf8()..].split_whitespace().collect();
+        let field = |i: usize| -> Result<usize, Error> {
+            let value = fields.get(i).ok_or(invalid(1, "header is too short"))?;
+            value
+                .parse()
+                .map_err(|_| invalid(1, "invalid number in header"))
+        };
+        let height = field(0)?;
+        let comment_lines = field(4)?;
+        if height == 0 {
+            return Err(invalid(1, "height is zero"));
+        }
+        if height > source.lines().count() - 1 {
+            return Err(invalid(1, "height is larger than the font"));
+        }
+        lines.by_ref().take(comment_lines).for_each(drop);
+
+        let mut font = Self {
+            height,
+            hardblank,
+            glyphs: BTreeMap::new(),
+        };
+        for c in (' '..='~').chain(DEUTSCH) {
+            match font.read_glyph(&mut lines)? {
+                Some(glyph) => font.glyphs.insert(c, glyph),
+                // Older fonts stop after ASCII.
+                None if !c.is_ascii() => break,
+                None => return Err(invalid(source.lines().count(), "missing characters")),
+            };
+        }
+        while let Some((number, tag)) = lines.next() {
+            if tag.trim().is_empty() {
+                continue;
+            }
+            let code = parse_code(tag).ok_or(invalid(number, "invalid character code"))?;
+            let glyph = font
+                .read_glyph(&mut lines)?
+                .ok_or(invalid(number, "missing character after code"))?;
+            if let Some(c) = u32::try_from(code).ok().and_then(char::from_u32) {
+                font.glyphs.insert(c, glyph);
+            }
+        }
+        Ok(font)
+    }
+
+    /// Read one character's rows, or `None` at the end of the file.
+    fn read_glyph<'a>(
+        &self,
+        lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<Option<Vec<String>>, Error> {
+        let mut rows = Vec::new();
+        let mut last = 0;
+        for i in 0..self.height {
+            match lines.next() {
+                Some((number, line)) => {
+                    last = number;
+                    rows.push(strip_endmark(line).to_string());
+                }
+                None if i == 0 => return Ok(None),
+                None => return Err(invalid(last + 1, "character is cut short")),
+            }
+        }
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        for row in &mut rows {
+            let pad = width - row.chars().count();
+            row.extend(core::iter::repeat(' ').take(pad));
+        }
+        Ok(Some(rows))
+    }
+
+    /// Number of rows in each rendered line of text.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the font has a glyph for `c`.
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        self.glyphs.contains_key(&c)
+    }
+
+    /// Render `text` as plain rows, [`Font::height`] rows per line of
+    /// text, with trailing spaces removed.
+    ///
+    /// Characters the font doesn't define are skipped.
+    #[must_use]
+    pub fn render(&self, text: &str, layout: Layout) -> Vec<String> {
+        let mut out = Vec::new();
+        for line in text.lines() {
+            let mut rows = vec![String::new(); self.height];
+            for glyph in line.chars().filter_map(|c| self.glyphs.get(&c)) {
+                let overlap = match layout {
+                    Layout::Full => 0,
+                    Layout::Fitting => fit(&rows, glyph),
+                };
+                for (row, part) in rows.iter_mut().zip(glyph) {
+                    let from_row = overlap.min(trailing_blanks(row));
+                    row.truncate(row.len() - from_row);
+                    row.extend(part.chars().skip(overlap - from_row));
+                }
+            }
+            out.extend(
+                rows.into_iter()
+                    .map(|row| row.replace(self.hardblank, " ").trim_end().to_string()),
+            );
+        }
+        out
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self::block()
+    }
+}
+
+/// Large text in a `FIGlet` font with a style or gradient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Banner {
+    text: String,
+    font: Font,
+    layout: Layout,
+    style: Style,
+    gradient: Option<(Color, Color)>,
+}
+
+impl Banner {
+    /// Create an unstyled banner in the built-in [`Font::block`].
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font: Font::block(),
+            layout: Layout::Full,
+            style: Style::new(),
+            gradient: None,
+        }
+    }
+
+    /// Render in `font`.
+    #[must_use]
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Set how characters are placed next to each other.
+    #[must_use]
+    pub const fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Style the whole banner.
+    ///
+    /// With a [gradient](Banner::gradient), the gradient replaces the
+    /// style's foreground.
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Color the banner with a left-to-right gradient from `from` to `to`.
+    #[must_use]
+    pub const fn gradient(mut self, from: Color, to: Color) -> Self {
+        self.gradient = Some((from, to));
+        self
+    }
+
+    /// The styled rows of the banner.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let rows = self.font.render(&self.text, self.layout);
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        rows.iter()
+            .map(|row| match self.gradient {
+                Some((from, to)) => self.gradient_row(row, width, from, to),
+                None if row.is_empty() => String::new(),
+                None => self.style.apply(row),
+            })
+            .collect()
+    }
+
+    /// Color each column of `row` by its position across the banner.
+    /// Spaces keep the base style so runs of color stay short.
+    fn gradient_row(&self, row: &str, width: usize, from: Color, to: Color) -> String {
+        let mut out = String::new();
+        let mut run = String::new();
+        let mut run_color = None;
+        for (x, c) in row.chars().enumerate() {
+            let color = (c != ' ').then(|| from.blend(to, fraction(x, width)));
+            if color != run_color {
+                self.push_run(&mut out, &run, run_color);
+                run.clear();
+                run_color = color;
+            }
+            run.push(c);
+        }
+        self.push_run(&mut out, &run, run_color);
+        out
+    }
+
+    fn push_run(&self, out: &mut String, run: &str, color: Option<Color>) {
+        if run.is_empty() {
+            return;
+        }
+        let style = match color {
+            Some(color) => self.style.fg(color),
+            None => self.style,
+        };
+        out.push_str(&style.apply(run));
+    }
+}
+
+impl fmt::Display for Banner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lines().join("\n"))
+    }
+}
+
+const fn invalid(line: usize, reason: &'static str) -> Error {
+    Error::InvalidFont { line, reason }
+}
+
+/// Remove the end mark (the last character, repeated on the last row).
+fn strip_endmark(line: &str) -> &str {
+    let line = line.trim_end();
+    line.chars()
+        .last()
+        .map_or(line, |mark| line.trim_end_matches(mark))
+}
+
+/// Parse a code tag: decimal, `0x` hex, or `0` octal, optionally negative.
+fn parse_code(tag: &str) -> Option<i64> {
+    let code = tag.split_whitespace().next()?;
+    let (negative, code) = code.strip_prefix('-').map_or((false, code), |c| (true, c));
+    let value = if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if code.len() > 1 && code.starts_with('0') {
+        i64::from_str_radix(&code[1..], 8).ok()?
+    } else {
+        code.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// How many columns `glyph` can move left before touching `rows`.
+fn fit(rows: &[String], glyph: &[String]) -> usize {
+    rows.iter()
+        .zip(glyph)
+        .map(|(row, part)| trailing_blanks(row) + part.chars().take_while(|&c| c == ' ').count())
+        .min()
+        .unwrap_or(0)
+}
+
+fn trailing_blanks(s: &str) -> usize {
+    s.len() - s.trim_end_matches(' ').len()
+}
+
+/// Position of column `x` across `width` columns, from 0.0 to 1.0.
+#[allow(clippy::cast_precision_loss)]
+fn fraction(x: usize, width: usize) -> f32 {
+    x as f32 / width.saturating_sub(1).max(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::strip_ansi;
+
+    const FONT: &str = "flf2a$ 2 2 4 0 1\ncomment\n$$@\n$$@@\n";
+
+    /// A two-row font where `!` and `i` are narrow and `-` is blank on top.
+    fn font() -> Font {
+        let mut source = String::from(FONT);
+        for c in '!'..='~' {
+            let glyph = match c {
+                '!' | 'i' => "|@\n|@@\n",
+                '-' => "  @\n--@@\n",
+                _ => "## @\n## @@\n",
+            };
+            source.push_str(glyph);
+        }
+        source.push_str(&"## @\n## @@\n".repeat(DEUTSCH.len()));
+        source.push_str("0x263A smiley\n:)@\n  @@\n");
+        Font::parse(&source).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let font = font();
+        assert_eq!(font.height(), 2);
+        assert!(font.contains('☺'));
+        assert!(font.contains('Ä'));
+        assert!(!font.contains('€'));
+        assert_eq!(font.render("a i", Layout::Full), vec!["##   |", "##   |"]);
+        assert_eq!(font.render("☺\n-", Layout::Full), vec![":)", "", "", "--"]);
+    }
+
+    #[test]
+    fn test_fitting() {
+        let font = font();
+        assert_eq!(font.render("aa", Layout::Fitting), vec!["####", "####"]);
+        assert_eq!(
+            font.render("a-a", Layout::Fitting),
+            vec!["##  ##", "##--##"]
+        );
+        assert_eq!(
+            font.render("a a", Layout::Fitting),
+            vec!["##  ##", "##  ##"]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Font::parse("flf2 2 2"),
+            Err(Error::InvalidFont { line: 1, .. })
+        ));
+        assert!(Font::parse("flf2a$ 2 2 4 0 0\n$@\n$@@\n").is_err());
+        assert!(Font::parse("flf2a$ x 2 4 0 0\n").is_err());
+        assert!(matches!(
+            Font::parse("flf2a$ 99999999999 2 4 0 0\n$@\n$@@\n"),
+            Err(Error::InvalidFont { line: 1, .. })
+        ));
+        assert!(Font::parse("flf2a$ 0 0 4 0 0\n").is_err());
+    }
+
+    #[test]
+    fn test_block_font() {
+        let font = Font::block();
+        assert_eq!(font.height(), 3);
+        assert!((' '..='~').all(|c| font.contains(c)));
+        assert_eq!(
+            font.render("a", Layout::Full),
+            font.render("A", Layout::Full)
+        );
+    }
+
+    #[test]
+    fn test_banner_styles() {
+        let plain = Font::block().render("OK", Layout::Full).join("\n");
+        let styled = Banner::new("OK").style(Style::new().fg(Color::Green));
+        assert_eq!(strip_ansi(&styled.to_string()), plain);
+        let gradient = Banner::new("OK").gradient(Color::rgb(255, 0, 0), Color::rgb(0, 0, 255));
+        assert_eq!(strip_ansi(&gradient.to_string()), plain);
+        assert_eq!(gradient.lines().len(), 3);
+    }
+}
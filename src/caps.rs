@@ -0,0 +1,209 @@
+//! Terminal capability detection.
+
+use crate::modifier::{Modifier, ModifierSet};
+
+/// How many colors a terminal can display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No colors or attributes at all.
+    None,
+    /// The 16 basic colors.
+    #[default]
+    Ansi16,
+    /// The 256-color palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Detect the color level from `COLORTERM` and `TERM`.
+    ///
+    /// This does not check whether a stream is a terminal; see
+    /// [`Console`](crate::Console) for that.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    #[cfg(feature = "std")]
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let colorterm = lookup("COLORTERM").unwrap_or_default();
+        let term = lookup("TERM").unwrap_or_default();
+
+        if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+            Self::TrueColor
+        } else if term == "dumb" {
+            Self::None
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+/// What a terminal (or output stream) is able to display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TermCaps {
+    /// Supported color depth.
+    pub color: ColorLevel,
+    /// Modifiers the terminal is known not to display.
+    ///
+    /// Empty unless detected from terminfo; modifiers are assumed to work
+    /// when nothing says otherwise.
+    pub unsupported: ModifierSet,
+    /// Whether OSC 8 hyperlinks are shown as links rather than ignored or
+    /// printed as garbage.
+    ///
+    /// Terminals don't advertise this, so it is only set when the
+    /// environment names a terminal known to support them.
+    pub hyperlinks: bool,
+    /// Whether REP (`CSI n b`), which repeats the last character printed,
+    /// is understood.
+    ///
+    /// Set from the terminfo `rep` capability, and otherwise assumed
+    /// missing.
+    pub repeat: bool,
+    /// Whether the VT420 rectangular area operations (DECFRA, DECERA,
+    /// DECCRA) are understood.
+    ///
+    /// Terminals don't advertise these, so this is never detected; set it
+    /// for a terminal known to support them.
+    pub rectangles: bool,
+}
+
+impl TermCaps {
+    /// Detect capabilities from the environment.
+    ///
+    /// With the `terminfo` feature, the entry for `TERM` is consulted for
+    /// the color count and supported attributes; `COLORTERM=truecolor`
+    /// still raises the color level, as many terminals don't advertise it.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_env() -> Self {
+        let color = ColorLevel::from_env();
+        let hyperlinks = hyperlinks_from_lookup(|name| std::env::var(name).ok());
+        #[cfg(feature = "terminfo")]
+        let terminfo = std::env::var("TERM")
+            .ok()
+            .and_then(|t| Self::from_terminfo(&t));
+        #[cfg(not(feature = "terminfo"))]
+        let terminfo: Option<Self> = None;
+
+        if let Some(caps) = terminfo {
+            let color = if color == ColorLevel::TrueColor {
+                color
+            } else {
+                caps.color
+            };
+            return Self {
+                color,
+                hyperlinks,
+                ..caps
+            };
+        }
+        Self {
+            color,
+            unsupported: ModifierSet::empty(),
+            hyperlinks,
+            repeat: false,
+            rectangles: false,
+        }
+    }
+
+    /// Read the capabilities of `term` from the terminfo database.
+    ///
+    /// Searches `$TERMINFO`, `~/.terminfo`, `$TERMINFO_DIRS`, and the
+    /// system directories. Returns `None` if there is no entry for `term`
+    /// or it can't be read.
+    ///
+    /// Italic, bold, dim, blink, reverse, hidden, and underline are marked
+    /// unsupported when the entry lacks the capability that enables them,
+    /// and strikethrough when it lacks the `smxx` extension. `Tc` or `RGB`
+    /// means truecolor.
+    #[cfg(feature = "terminfo")]
+    #[must_use]
+    pub fn from_terminfo(term: &str) -> Option<Self> {
+        crate::terminfo::Entry::load(term).map(|entry| entry.caps())
+    }
+
+    /// Check whether `modifier` can be displayed.
+    #[must_use]
+    pub const fn supports(&self, modifier: Modifier) -> bool {
+        !self.unsupported.contains(modifier)
+    }
+}
+
+/// Whether the environment names a terminal that shows OSC 8 links.
+///
+/// `FORCE_HYPERLINK` overrides the guess: `0` turns links off and any
+/// other value on.
+#[cfg(feature = "std")]
+fn hyperlinks_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = lookup("FORCE_HYPERLINK").filter(|v| !v.is_empty()) {
+        return force != "0";
+    }
+    let term = lookup("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return false;
+    }
+    let program = lookup("TERM_PROGRAM").unwrap_or_default();
+    let vte = lookup("VTE_VERSION").and_then(|v| v.parse::<u32>().ok());
+
+    lookup("WT_SESSION").is_some()
+        || lookup("KONSOLE_VERSION").is_some()
+        || matches!(
+            program.as_str(),
+            "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+        )
+        || vte.is_some_and(|v| v >= 5000)
+        || ["xterm-kitty", "alacritty", "foot", "wezterm"]
+            .iter()
+            .any(|prefix| term.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::testing::lookup_from;
+
+    #[cfg(feature = "std")]
+    fn level(vars: &[(&str, &str)]) -> ColorLevel {
+        ColorLevel::from_lookup(lookup_from(vars))
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_color_level_detection() {
+        assert_eq!(level(&[]), ColorLevel::Ansi16);
+        assert_eq!(level(&[("TERM", "dumb")]), ColorLevel::None);
+        assert_eq!(level(&[("TERM", "xterm-256color")]), ColorLevel::Ansi256);
+        assert_eq!(
+            level(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorLevel::TrueColor
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hyperlink_detection() {
+        let detect = |vars: &[(&str, &str)]| hyperlinks_from_lookup(lookup_from(vars));
+        assert!(!detect(&[]));
+        assert!(!detect(&[("TERM", "xterm-256color")]));
+        assert!(detect(&[("TERM_PROGRAM", "WezTerm")]));
+        assert!(detect(&[("TERM", "xterm-kitty")]));
+        assert!(detect(&[("VTE_VERSION", "6003")]));
+        assert!(!detect(&[("VTE_VERSION", "4205")]));
+        assert!(!detect(&[("WT_SESSION", "x"), ("FORCE_HYPERLINK", "0")]));
+        assert!(detect(&[("TERM", "dumb"), ("FORCE_HYPERLINK", "1")]));
+    }
+
+    #[test]
+    fn test_color_level_ordering() {
+        assert!(ColorLevel::TrueColor > ColorLevel::Ansi256);
+        assert!(ColorLevel::Ansi16 > ColorLevel::None);
+    }
+}
@@ -0,0 +1,362 @@
+//! asciinema v2 (`.cast`) reading and writing (requires the `cast` feature).
+//!
+//! A cast file is a JSON header line followed by one JSON array per event.
+//! Output events carry raw terminal data, which can be parsed with
+//! [`parse`](crate::parse) or rewritten before saving the cast again.
+//!
+//! ```rust
+//! use glyphs::cast::Cast;
+//!
+//! let input = "{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.5, \"o\", \"\\u001b[31mhi\\u001b[0m\"]\n";
+//! let mut cast = Cast::from_str(input)?;
+//! cast.map_output(|data| glyphs::strip_ansi(data));
+//! assert_eq!(cast.events[0].data, "hi");
+//!
+//! let mut out = Vec::new();
+//! cast.write(&mut out)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::parser::{parse, ParsedSequence};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// Error returned when reading a cast file.
+#[derive(Debug)]
+pub enum CastError {
+    /// The underlying reader failed.
+    Io(io::Error),
+    /// A line was not valid JSON.
+    Json {
+        /// One-based line number.
+        line: usize,
+        /// The JSON error.
+        source: serde_json::Error,
+    },
+    /// A line was valid JSON but not a valid header or event.
+    Format {
+        /// One-based line number.
+        line: usize,
+        /// What was wrong with the line.
+        message: String,
+    },
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read cast: {e}"),
+            Self::Json { line, source } => write!(f, "invalid JSON on line {line}: {source}"),
+            Self::Format { line, message } => write!(f, "invalid cast on line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CastError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json { source, .. } => Some(source),
+            Self::Format { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CastError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// The header line of a cast file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastHeader {
+    /// Terminal width in columns.
+    pub width: u16,
+    /// Terminal height in rows.
+    pub height: u16,
+    /// Recording start time (Unix seconds).
+    pub timestamp: Option<u64>,
+    /// Recording title.
+    pub title: Option<String>,
+    /// Any other header fields (`env`, `theme`, ...), preserved when writing.
+    pub extra: Map<String, Value>,
+}
+
+impl CastHeader {
+    /// Create a header for a terminal of the given size.
+    #[must_use]
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            timestamp: None,
+            title: None,
+            extra: Map::new(),
+        }
+    }
+
+    fn from_value(value: Value, line: usize) -> Result<Self, CastError> {
+        let format_err = |message: &str| CastError::Format {
+            line,
+            message: message.to_string(),
+        };
+        let Value::Object(mut map) = value else {
+            return Err(format_err("header is not an object"));
+        };
+        if map.remove("version").and_then(|v| v.as_u64()) != Some(2) {
+            return Err(format_err("only version 2 casts are supported"));
+        }
+        let mut dimension = |key: &str| {
+            map.remove(key)
+                .and_then(|v| v.as_u64())
+                .and_then(|v| u16::try_from(v).ok())
+                .ok_or_else(|| format_err(&format!("missing or invalid `{key}`")))
+        };
+        let width = dimension("width")?;
+        let height = dimension("height")?;
+        let timestamp = map.remove("timestamp").and_then(|v| v.as_u64());
+        let title = map
+            .remove("title")
+            .and_then(|v| v.as_str().map(ToString::to_string));
+        Ok(Self {
+            width,
+            height,
+            timestamp,
+            title,
+            extra: map,
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("version".into(), 2.into());
+        map.insert("width".into(), self.width.into());
+        map.insert("height".into(), self.height.into());
+        if let Some(timestamp) = self.timestamp {
+            map.insert("timestamp".into(), timestamp.into());
+        }
+        if let Some(title) = &self.title {
+            map.insert("title".into(), title.clone().into());
+        }
+        map.extend(self.extra.clone());
+        Value::Object(map)
+    }
+}
+
+/// The kind of a cast event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// Data written to the terminal (`"o"`).
+    Output,
+    /// Data read from the keyboard (`"i"`).
+    Input,
+    /// A marker (`"m"`).
+    Marker,
+    /// A terminal resize (`"r"`), with `COLSxROWS` data.
+    Resize,
+    /// Any other event code.
+    Other(String),
+}
+
+impl EventKind {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "o" => Self::Output,
+            "i" => Self::Input,
+            "m" => Self::Marker,
+            "r" => Self::Resize,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// The single-letter code used in cast files.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Output => "o",
+            Self::Input => "i",
+            Self::Marker => "m",
+            Self::Resize => "r",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+/// One event of a cast file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    /// Seconds since the start of the recording.
+    pub time: f64,
+    /// The event kind.
+    pub kind: EventKind,
+    /// The event payload.
+    pub data: String,
+}
+
+impl CastEvent {
+    /// Create an output event.
+    #[must_use]
+    pub fn output(time: f64, data: impl Into<String>) -> Self {
+        Self {
+            time,
+            kind: EventKind::Output,
+            data: data.into(),
+        }
+    }
+
+    /// Parse the event payload into text and escape sequences.
+    #[must_use]
+    pub fn parse(&self) -> Vec<ParsedSequence> {
+        parse(&self.data)
+    }
+
+    fn from_value(value: &Value, line: usize) -> Result<Self, CastError> {
+        match value.as_array().map(Vec::as_slice) {
+            Some([Value::Number(time), Value::String(code), Value::String(data)]) => Ok(Self {
+                time: time.as_f64().unwrap_or_default(),
+                kind: EventKind::from_code(code),
+                data: data.clone(),
+            }),
+            _ => Err(CastError::Format {
+                line,
+                message: "event is not a [time, code, data] array".to_string(),
+            }),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Array(vec![
+            self.time.into(),
+            self.kind.code().into(),
+            self.data.clone().into(),
+        ])
+    }
+}
+
+/// A complete asciinema v2 recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast {
+    /// The header line.
+    pub header: CastHeader,
+    /// The events, in file order.
+    pub events: Vec<CastEvent>,
+}
+
+impl Cast {
+    /// Create an empty cast.
+    #[must_use]
+    pub const fn new(header: CastHeader) -> Self {
+        Self {
+            header,
+            events: Vec::new(),
+        }
+    }
+
+    /// Read a cast from a buffered reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or the data is not a valid v2 cast.
+    pub fn read<R: BufRead>(reader: R) -> Result<Self, CastError> {
+        let mut header = None;
+        let mut events = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value: Value = serde_json::from_str(&line).map_err(|source| CastError::Json {
+                line: line_no,
+                source,
+            })?;
+            if header.is_none() {
+                header = Some(CastHeader::from_value(value, line_no)?);
+            } else {
+                events.push(CastEvent::from_value(&value, line_no)?);
+            }
+        }
+
+        let header = header.ok_or_else(|| CastError::Format {
+            line: 1,
+            message: "missing header".to_string(),
+        })?;
+        Ok(Self { header, events })
+    }
+
+    /// Read a cast from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a valid v2 cast.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &str) -> Result<Self, CastError> {
+        Self::read(input.as_bytes())
+    }
+
+    /// Write the cast in asciinema v2 format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self.header.to_value())?;
+        for event in &self.events {
+            writeln!(writer, "{}", event.to_value())?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the payload of every output event.
+    pub fn map_output(&mut self, mut f: impl FnMut(&str) -> String) {
+        for event in &mut self.events {
+            if event.kind == EventKind::Output {
+                event.data = f(&event.data);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{"version": 2, "width": 80, "height": 24, "env": {"TERM": "xterm"}}
+[0.1, "o", "\u001b[1mbold\u001b[0m"]
+[0.2, "i", "q"]
+"#;
+
+    #[test]
+    fn test_read_and_parse() {
+        let cast = Cast::from_str(SAMPLE).unwrap();
+        assert_eq!(cast.header.width, 80);
+        assert!(cast.header.extra.contains_key("env"));
+        assert_eq!(cast.events.len(), 2);
+        assert_eq!(cast.events[1].kind, EventKind::Input);
+        assert_eq!(cast.events[0].parse().len(), 3);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cast = Cast::from_str(SAMPLE).unwrap();
+        let mut out = Vec::new();
+        cast.write(&mut out).unwrap();
+        let again = Cast::from_str(std::str::from_utf8(&out).unwrap()).unwrap();
+        assert_eq!(cast, again);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Cast::from_str("{\"version\": 1}"),
+            Err(CastError::Format { line: 1, .. })
+        ));
+        assert!(matches!(
+            Cast::from_str("{\"version\": 2, \"width\": 1, \"height\": 1}\nnope"),
+            Err(CastError::Json { line: 2, .. })
+        ));
+    }
+}
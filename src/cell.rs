@@ -0,0 +1,117 @@
+//! Splitting styled text into the cells it takes on screen.
+
+use crate::parser::{parse, ParsedSequence};
+use crate::style::Style;
+use crate::tracker::StyleTracker;
+use crate::width::WidthPolicy;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// One grapheme of styled text, from [`cells`].
+///
+/// Unlike [`emulate::Cell`](crate::emulate::Cell), which is a position on
+/// an emulated screen, this is a piece of the input in the order it was
+/// written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The grapheme cluster, such as `a`, `e` with a combining accent, or
+    /// a joined emoji sequence.
+    pub grapheme: String,
+    /// Terminal columns the grapheme takes: usually 1, 2 for wide
+    /// characters, and 0 for a combining mark separated from its base by
+    /// an escape sequence.
+    pub width: usize,
+    /// The style active where the grapheme was written.
+    pub style: Style,
+}
+
+/// Split styled text into graphemes, each with its width and style.
+///
+/// Escape sequences and control characters produce no cells; SGR
+/// sequences and terminal resets change the style of the cells after
+/// them. Widths follow the default [`WidthPolicy`]; use
+/// [`WidthPolicy::cells`] for another.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{cells, Color};
+///
+/// let cells: Vec<_> = cells("\x1b[31m\u{4f60}\x1b[0mok").collect();
+/// assert_eq!(cells.len(), 3);
+/// assert_eq!((cells[0].grapheme.as_str(), cells[0].width), ("\u{4f60}", 2));
+/// assert_eq!(cells[0].style.foreground, Some(Color::Red));
+/// assert!(cells[1].style.is_plain());
+/// ```
+pub fn cells(input: &str) -> impl Iterator<Item = Cell> {
+    WidthPolicy::new().cells(input)
+}
+
+impl WidthPolicy {
+    /// Split styled text into cells as [`cells`] does, measuring widths
+    /// with this policy.
+    pub fn cells(&self, input: &str) -> impl Iterator<Item = Cell> {
+        let mut out = Vec::new();
+        let mut tracker = StyleTracker::new();
+        for segment in parse(input) {
+            match &segment {
+                ParsedSequence::Text(text) => {
+                    out.extend(text.graphemes(true).map(|grapheme| Cell {
+                        grapheme: grapheme.to_string(),
+                        width: self.grapheme_width(grapheme),
+                        style: tracker.style(),
+                    }));
+                }
+                ParsedSequence::Escape(e) => {
+                    tracker.apply(e);
+                }
+                ParsedSequence::Control(_) | ParsedSequence::Malformed { .. } => {}
+            }
+        }
+        out.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::modifier::Modifier;
+
+    fn summary(input: &str) -> Vec<(String, usize)> {
+        cells(input).map(|c| (c.grapheme, c.width)).collect()
+    }
+
+    #[test]
+    fn test_cells_graphemes_and_widths() {
+        assert_eq!(
+            summary("e\u{301}\t\u{1f600}\n\x1b[2Kx"),
+            [
+                ("e\u{301}".to_string(), 1),
+                ("\u{1f600}".to_string(), 2),
+                ("x".to_string(), 1),
+            ]
+        );
+        assert_eq!(summary("a\x1b[1m\u{301}")[1], ("\u{301}".to_string(), 0));
+        assert!(cells("\x1b[31m\x1b[0m").next().is_none());
+    }
+
+    #[test]
+    fn test_cells_styles() {
+        let styles: Vec<Style> = cells("\x1b[1ma\x1b[34mb\x1b[22mc\x1bcd")
+            .map(|c| c.style)
+            .collect();
+        let bold = Style::new().modifier(Modifier::Bold);
+        assert_eq!(styles[0], bold);
+        assert_eq!(styles[1], bold.fg(Color::Blue));
+        assert_eq!(styles[2], Style::new().fg(Color::Blue));
+        assert_eq!(styles[3], Style::new());
+    }
+
+    #[test]
+    fn test_cells_with_policy() {
+        let policy = WidthPolicy::new().ambiguous_wide(true);
+        assert_eq!(policy.cells("\u{b1}").next().unwrap().width, 2);
+    }
+}
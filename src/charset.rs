@@ -0,0 +1,213 @@
+//! Character set designation (SCS) and the DEC line-drawing set.
+//!
+//! Programs using ncurses often draw boxes by switching G0 to the DEC
+//! Special Graphics set (`ESC ( 0`), printing letters such as `lqqk`, and
+//! switching back (`ESC ( B`). [`CharsetState`] follows those switches so
+//! the letters can be shown as the box characters a terminal would draw.
+
+use crate::control::ControlChar;
+use crate::escape::Escape;
+use crate::parser::{parse, ParsedSequence};
+use alloc::string::String;
+
+/// A character set that can be designated into G0-G3.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// US ASCII (`B`), the default.
+    #[default]
+    Ascii,
+    /// DEC Special Graphics (`0`), used for line drawing.
+    DecLineDrawing,
+    /// United Kingdom (`A`), which shows `#` as `£`.
+    Uk,
+    /// Any other set, by its designator.
+    Other(char),
+}
+
+impl Charset {
+    /// The set selected by an SCS final character.
+    #[must_use]
+    pub const fn from_designator(c: char) -> Self {
+        match c {
+            'B' => Self::Ascii,
+            '0' => Self::DecLineDrawing,
+            'A' => Self::Uk,
+            other => Self::Other(other),
+        }
+    }
+
+    /// Human-readable name of the set.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Ascii => "US ASCII",
+            Self::DecLineDrawing => "DEC line drawing",
+            Self::Uk => "UK",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// How `c` is displayed while this set is active.
+    ///
+    /// Sets other than line drawing and UK leave characters unchanged.
+    #[must_use]
+    pub const fn translate(self, c: char) -> char {
+        match self {
+            Self::DecLineDrawing => match dec_line_drawing(c) {
+                Some(mapped) => mapped,
+                None => c,
+            },
+            Self::Uk if c == '#' => '£',
+            _ => c,
+        }
+    }
+}
+
+/// The Unicode character the DEC Special Graphics set shows for `c`, or
+/// `None` if `c` is outside `_` through `~`.
+///
+/// ```rust
+/// use glyphs::dec_line_drawing;
+///
+/// assert_eq!(dec_line_drawing('l'), Some('┌'));
+/// assert_eq!(dec_line_drawing('A'), None);
+/// ```
+#[must_use]
+pub const fn dec_line_drawing(c: char) -> Option<char> {
+    Some(match c {
+        '_' => '\u{a0}',
+        '`' => '◆',
+        'a' => '▒',
+        'b' => '␉',
+        'c' => '␌',
+        'd' => '␍',
+        'e' => '␊',
+        'f' => '°',
+        'g' => '±',
+        'h' => '␤',
+        'i' => '␋',
+        'j' => '┘',
+        'k' => '┐',
+        'l' => '┌',
+        'm' => '└',
+        'n' => '┼',
+        'o' => '⎺',
+        'p' => '⎻',
+        'q' => '─',
+        'r' => '⎼',
+        's' => '⎽',
+        't' => '├',
+        'u' => '┤',
+        'v' => '┴',
+        'w' => '┬',
+        'x' => '│',
+        'y' => '≤',
+        'z' => '≥',
+        '{' => 'π',
+        '|' => '≠',
+        '}' => '£',
+        '~' => '·',
+        _ => return None,
+    })
+}
+
+/// The G0/G1 designations and the shift state (SO/SI) of a terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CharsetState {
+    g0: Charset,
+    g1: Charset,
+    shifted: bool,
+}
+
+impl CharsetState {
+    /// ASCII in G0 and G1, G0 active.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The set characters are currently shown in.
+    #[must_use]
+    pub const fn active(&self) -> Charset {
+        if self.shifted {
+            self.g1
+        } else {
+            self.g0
+        }
+    }
+
+    /// Apply an `ESC (` or `ESC )` designation, returning whether it was one.
+    ///
+    /// `ESC c` (full reset) also restores the defaults.
+    pub fn apply_escape(&mut self, escape: &Escape) -> bool {
+        let mut chars = escape.raw.chars().skip(1);
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some('('), Some(c), None) => self.g0 = Charset::from_designator(c),
+            (Some(')'), Some(c), None) => self.g1 = Charset::from_designator(c),
+            (Some('c'), None, None) => *self = Self::new(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply SO (shift out, G1) or SI (shift in, G0), returning whether
+    /// `control` was one of them.
+    pub fn apply_control(&mut self, control: ControlChar) -> bool {
+        match control {
+            ControlChar::Other(0x0e) => self.shifted = true,
+            ControlChar::Other(0x0f) => self.shifted = false,
+            _ => return false,
+        }
+        true
+    }
+
+    /// How `c` is displayed in the active set.
+    #[must_use]
+    pub const fn translate(&self, c: char) -> char {
+        self.active().translate(c)
+    }
+}
+
+/// Strip escape sequences like [`strip_ansi`](crate::strip_ansi), showing
+/// text printed in the DEC line-drawing set as box characters.
+///
+/// The SO and SI shift controls are removed along with the escapes.
+///
+/// ```rust
+/// use glyphs::strip_ansi_translated;
+///
+/// assert_eq!(strip_ansi_translated("\x1b(0lqk\x1b(B ok"), "┌─┐ ok");
+/// ```
+#[must_use]
+pub fn strip_ansi_translated(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut state = CharsetState::new();
+    for segment in parse(input) {
+        match &segment {
+            ParsedSequence::Text(text) if state.active() == Charset::Ascii => out.push_str(text),
+            ParsedSequence::Text(text) => out.extend(text.chars().map(|c| state.translate(c))),
+            ParsedSequence::Control(c) => {
+                if !state.apply_control(*c) {
+                    out.push(c.as_char());
+                }
+            }
+            ParsedSequence::Escape(e) => {
+                state.apply_escape(e);
+            }
+            ParsedSequence::Malformed { .. } => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_out_and_reset() {
+        let input = "\x1b)0\x0emqj\x0f mqj\x1b(A#\x1bc#";
+        assert_eq!(strip_ansi_translated(input), "└─┘ mqj£#");
+        assert_eq!(Charset::from_designator('%'), Charset::Other('%'));
+    }
+}
@@ -1,6 +1,14 @@
 //! Color definitions for terminal styling.
 
-use std::fmt;
+use crate::caps::ColorLevel;
+use crate::color_names::{color_names, ColorNames};
+use crate::error::Error;
+use crate::float;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::f32::consts::TAU;
+use core::fmt;
+use core::hash::{Hash, Hasher};
 
 /// A terminal color.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -63,14 +71,35 @@ impl Color {
     ///
     /// # Panics
     ///
-    /// Panics if the hex string is invalid.
+    /// Panics if the hex string is invalid; see [`Color::try_from_hex`].
     #[must_use]
     pub fn from_hex(hex: &str) -> Self {
-        let hex = hex.trim_start_matches('#');
-        let r = u8::from_str_radix(&hex[0..2], 16).expect("Invalid hex");
-        let g = u8::from_str_radix(&hex[2..4], 16).expect("Invalid hex");
-        let b = u8::from_str_radix(&hex[4..6], 16).expect("Invalid hex");
-        Self::Rgb { r, g, b }
+        Self::try_from_hex(hex).expect("Invalid hex")
+    }
+
+    /// Create a color from a `#rrggbb` or `#rgb` hex string (`#` optional).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ColorParse`] if the string is not a valid hex color.
+    pub fn try_from_hex(hex: &str) -> Result<Self, Error> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let valid = digits.bytes().all(|b| b.is_ascii_hexdigit());
+        let channel = |i: usize, width: usize| {
+            u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).unwrap_or(0)
+        };
+
+        match (valid, digits.len()) {
+            (true, 6) => Ok(Self::rgb(channel(0, 2), channel(1, 2), channel(2, 2))),
+            (true, 3) => Ok(Self::rgb(
+                channel(0, 1) * 17,
+                channel(1, 1) * 17,
+                channel(2, 1) * 17,
+            )),
+            _ => Err(Error::ColorParse {
+                input: hex.to_string(),
+            }),
+        }
     }
 
     /// Create a 256-color palette color.
@@ -82,56 +111,41 @@ impl Color {
     /// Get the ANSI SGR code for foreground.
     #[must_use]
     pub fn fg_code(&self) -> String {
-        match self {
-            Self::Default => "39".to_string(),
-            Self::Black => "30".to_string(),
-            Self::Red => "31".to_string(),
-            Self::Green => "32".to_string(),
-            Self::Yellow => "33".to_string(),
-            Self::Blue => "34".to_string(),
-            Self::Magenta => "35".to_string(),
-            Self::Cyan => "36".to_string(),
-            Self::White => "37".to_string(),
-            Self::BrightBlack => "90".to_string(),
-            Self::BrightRed => "91".to_string(),
-            Self::BrightGreen => "92".to_string(),
-            Self::BrightYellow => "93".to_string(),
-            Self::BrightBlue => "94".to_string(),
-            Self::BrightMagenta => "95".to_string(),
-            Self::BrightCyan => "96".to_string(),
-            Self::BrightWhite => "97".to_string(),
-            Self::Ansi256(code) => format!("38;5;{code}"),
-            Self::Rgb { r, g, b } => format!("38;2;{r};{g};{b}"),
-        }
+        let mut code = String::new();
+        let _ = self.write_code(&mut code, 30);
+        code
     }
 
     /// Get the ANSI SGR code for background.
     #[must_use]
     pub fn bg_code(&self) -> String {
+        let mut code = String::new();
+        let _ = self.write_code(&mut code, 40);
+        code
+    }
+
+    /// Write the SGR code for this color, with `base` 30 for foreground or
+    /// 40 for background.
+    pub(crate) fn write_code<W: fmt::Write>(self, out: &mut W, base: u8) -> fmt::Result {
         match self {
-            Self::Default => "49".to_string(),
-            Self::Black => "40".to_string(),
-            Self::Red => "41".to_string(),
-            Self::Green => "42".to_string(),
-            Self::Yellow => "43".to_string(),
-            Self::Blue => "44".to_string(),
-            Self::Magenta => "45".to_string(),
-            Self::Cyan => "46".to_string(),
-            Self::White => "47".to_string(),
-            Self::BrightBlack => "100".to_string(),
-            Self::BrightRed => "101".to_string(),
-            Self::BrightGreen => "102".to_string(),
-            Self::BrightYellow => "103".to_string(),
-            Self::BrightBlue => "104".to_string(),
-            Self::BrightMagenta => "105".to_string(),
-            Self::BrightCyan => "106".to_string(),
-            Self::BrightWhite => "107".to_string(),
-            Self::Ansi256(code) => format!("48;5;{code}"),
-            Self::Rgb { r, g, b } => format!("48;2;{r};{g};{b}"),
+            Self::Default => write!(out, "{}", base + 9),
+            Self::Ansi256(code) => write!(out, "{};5;{code}", base + 8),
+            Self::Rgb { r, g, b } => write!(out, "{};2;{r};{g};{b}", base + 8),
+            basic => {
+                let index = basic.basic_index().unwrap_or(0);
+                if index < 8 {
+                    write!(out, "{}", base + index)
+                } else {
+                    write!(out, "{}", base + 60 + index - 8)
+                }
+            }
         }
     }
 
     /// Get a human-readable name for the color.
+    ///
+    /// 256-palette colors are named by number, with the name from
+    /// [`set_color_names`](crate::set_color_names) after it if one is set.
     #[must_use]
     pub fn name(&self) -> String {
         match self {
@@ -152,10 +166,436 @@ impl Color {
             Self::BrightMagenta => "bright magenta".to_string(),
             Self::BrightCyan => "bright cyan".to_string(),
             Self::BrightWhite => "bright white".to_string(),
-            Self::Ansi256(code) => format!("color {code}"),
+            Self::Ansi256(code) => ansi256_name(*code, color_names()),
             Self::Rgb { r, g, b } => format!("rgb({r}, {g}, {b})"),
         }
     }
+
+    /// Get the approximate RGB value of this color.
+    ///
+    /// The 16 basic colors use the xterm default palette. Returns `None` for
+    /// [`Color::Default`], whose value depends on the terminal.
+    #[must_use]
+    pub const fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            Self::Default => None,
+            Self::Rgb { r, g, b } => Some((*r, *g, *b)),
+            Self::Ansi256(code) => Some(ansi256_to_rgb(*code)),
+            _ => match self.basic_index() {
+                Some(index) => Some(ansi256_to_rgb(index)),
+                None => None,
+            },
+        }
+    }
+
+    /// Index (0-15) of a basic color, or `None` for default/256/RGB colors.
+    pub(crate) const fn basic_index(self) -> Option<u8> {
+        match self {
+            Self::Black => Some(0),
+            Self::Red => Some(1),
+            Self::Green => Some(2),
+            Self::Yellow => Some(3),
+            Self::Blue => Some(4),
+            Self::Magenta => Some(5),
+            Self::Cyan => Some(6),
+            Self::White => Some(7),
+            Self::BrightBlack => Some(8),
+            Self::BrightRed => Some(9),
+            Self::BrightGreen => Some(10),
+            Self::BrightYellow => Some(11),
+            Self::BrightBlue => Some(12),
+            Self::BrightMagenta => Some(13),
+            Self::BrightCyan => Some(14),
+            Self::BrightWhite => Some(15),
+            Self::Default | Self::Ansi256(_) | Self::Rgb { .. } => None,
+        }
+    }
+
+    /// Convert to the nearest color in the 256-color palette.
+    ///
+    /// Basic and default colors are returned unchanged.
+    #[must_use]
+    pub fn to_ansi256(&self) -> Self {
+        match self {
+            Self::Rgb { r, g, b } => Self::Ansi256(rgb_to_ansi256(*r, *g, *b)),
+            other => *other,
+        }
+    }
+
+    /// Convert to the nearest of the 16 basic colors, as measured by
+    /// [`Color::distance`].
+    ///
+    /// The default color is returned unchanged.
+    #[must_use]
+    pub fn to_ansi16(&self) -> Self {
+        match self {
+            Self::Ansi256(_) | Self::Rgb { .. } => {
+                let (r, g, b) = self.to_rgb().unwrap_or((0, 0, 0));
+                BASIC[nearest_basic(r, g, b)]
+            }
+            other => *other,
+        }
+    }
+
+    /// Relative luminance as defined by WCAG 2.x (0.0 for black, 1.0 for white).
+    ///
+    /// Returns `None` for [`Color::Default`].
+    #[must_use]
+    pub fn luminance(&self) -> Option<f32> {
+        let (r, g, b) = self.to_rgb()?;
+        Some(float::mul_add(
+            0.0722,
+            linear(b),
+            float::mul_add(0.2126, linear(r), 0.7152 * linear(g)),
+        ))
+    }
+
+    /// Perceptual distance to `other`: the Euclidean distance between the
+    /// two colors in the `OKLab` color space.
+    ///
+    /// `0.0` means identical and black to white is `1.0`; differences
+    /// below about `0.02` are hard to see. [`Color::Default`] has no RGB
+    /// value, so its distance to any other color is `f32::INFINITY`.
+    ///
+    /// ```rust
+    /// use glyphs::Color;
+    ///
+    /// let orange = Color::rgb(249, 115, 22);
+    /// assert!(orange.distance(&Color::rgb(250, 120, 20)) < orange.distance(&Color::Yellow));
+    /// ```
+    #[must_use]
+    pub fn distance(&self, other: &Self) -> f32 {
+        match (self.to_rgb(), other.to_rgb()) {
+            (Some(a), Some(b)) => perceptual_distance(a, b),
+            (None, None) => 0.0,
+            _ => f32::INFINITY,
+        }
+    }
+
+    /// WCAG contrast ratio between two colors (1.0 to 21.0).
+    ///
+    /// Returns `None` if either color is [`Color::Default`].
+    #[must_use]
+    pub fn contrast_ratio(&self, other: &Self) -> Option<f32> {
+        let a = self.luminance()?;
+        let b = other.luminance()?;
+        let (light, dark) = if a > b { (a, b) } else { (b, a) };
+        Some((light + 0.05) / (dark + 0.05))
+    }
+
+    /// Mix this color with `other`: `t = 0.0` gives `self`, `t = 1.0` gives
+    /// `other`, and values in between interpolate each RGB channel.
+    ///
+    /// `t` is clamped to `0.0..=1.0`. The result is always an RGB color,
+    /// except that a mix involving [`Color::Default`] has no RGB value to
+    /// interpolate and returns whichever color `t` is closer to.
+    ///
+    /// ```rust
+    /// use glyphs::Color;
+    ///
+    /// let mid = Color::rgb(0, 0, 0).blend(Color::rgb(255, 100, 50), 0.5);
+    /// assert_eq!(mid, Color::rgb(128, 50, 25));
+    /// ```
+    #[must_use]
+    pub fn blend(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let (Some(from), Some(to)) = (self.to_rgb(), other.to_rgb()) else {
+            return if t < 0.5 { self } else { other };
+        };
+        let mix = |a: u8, b: u8| {
+            let a = f32::from(a);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let out = float::round(float::mul_add(f32::from(b) - a, t, a)).clamp(0.0, 255.0) as u8;
+            out
+        };
+        Self::rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    }
+
+    /// The color seen when this color is drawn with opacity `alpha` over
+    /// `background`, for overlays and shadows.
+    ///
+    /// Equivalent to `background.blend(self, alpha)`.
+    ///
+    /// ```rust
+    /// use glyphs::Color;
+    ///
+    /// let shadow = Color::rgb(0, 0, 0).with_alpha_over(0.25, Color::rgb(200, 200, 200));
+    /// assert_eq!(shadow, Color::rgb(150, 150, 150));
+    /// ```
+    #[must_use]
+    pub fn with_alpha_over(self, alpha: f32, background: Self) -> Self {
+        background.blend(self, alpha)
+    }
+
+    /// A readable RGB color picked by hashing `key`, for telling apart
+    /// usernames, thread ids, or container names in log output.
+    ///
+    /// The same key gets the same color within a build, so across runs of
+    /// one binary. Other builds may pick differently, since [`Hash`] impls
+    /// and the floating-point color math can change between Rust versions
+    /// and platforms; don't store the colors.
+    ///
+    /// The hash picks the hue freely but keeps `OKLab` lightness and
+    /// chroma in a narrow band, so every color reads well on a dark
+    /// background and none stands out more than the others.
+    ///
+    /// ```rust
+    /// use glyphs::{style, Color};
+    ///
+    /// for user in ["alice", "bob", "carol"] {
+    ///     println!("{}: hello", style(user).fg(Color::from_hash(user)));
+    /// }
+    /// assert_eq!(Color::from_hash("alice"), Color::from_hash("alice"));
+    /// ```
+    #[must_use]
+    pub fn from_hash<T: Hash + ?Sized>(key: &T) -> Self {
+        let mut hasher = Fnv(FNV_OFFSET);
+        key.hash(&mut hasher);
+        let [hue_low, hue_high, lightness_byte, chroma_byte, ..] = hasher.finish().to_le_bytes();
+
+        let hue = f32::from(u16::from_le_bytes([hue_low, hue_high])) / 65_536.0 * TAU;
+        let lightness = float::mul_add(f32::from(lightness_byte) / 255.0, 0.08, 0.70);
+        let chroma = float::mul_add(f32::from(chroma_byte) / 255.0, 0.04, 0.10);
+        let (sin, cos) = float::sin_cos(hue);
+        let (r, g, b) = from_oklab([lightness, chroma * cos, chroma * sin]);
+        Self::rgb(r, g, b)
+    }
+
+    /// Downgrade this color so it can be displayed at the given color level.
+    ///
+    /// Returns `None` for [`ColorLevel::None`].
+    #[must_use]
+    pub fn downgrade(&self, level: ColorLevel) -> Option<Self> {
+        match level {
+            ColorLevel::None => None,
+            ColorLevel::Ansi16 => Some(self.to_ansi16()),
+            ColorLevel::Ansi256 => Some(self.to_ansi256()),
+            ColorLevel::TrueColor => Some(*self),
+        }
+    }
+
+    /// Like [`Color::downgrade`], but reports a level without colors as an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedCapability`] for [`ColorLevel::None`].
+    pub fn try_downgrade(&self, level: ColorLevel) -> Result<Self, Error> {
+        self.downgrade(level)
+            .ok_or(Error::UnsupportedCapability("colors"))
+    }
+}
+
+/// The basic color at palette index `index` (0-15).
+pub(crate) const fn basic(index: u8) -> Color {
+    BASIC[(index & 0x0f) as usize]
+}
+
+/// The 16 basic colors in palette order.
+const BASIC: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// xterm default values for the 16 basic colors.
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel values of the 6x6x6 color cube.
+const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+const fn ansi256_to_rgb(code: u8) -> (u8, u8, u8) {
+    match code {
+        0..=15 => BASIC_RGB[code as usize],
+        16..=231 => {
+            let i = code - 16;
+            (
+                CUBE_STEPS[(i / 36) as usize],
+                CUBE_STEPS[((i / 6) % 6) as usize],
+                CUBE_STEPS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (code - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    // Index of the nearest cube step for a channel.
+    let step = |v: u8| -> u8 {
+        match v {
+            0..=47 => 0,
+            48..=114 => 1,
+            _ => (v - 35) / 40,
+        }
+    };
+    let (ri, gi, bi) = (step(r), step(g), step(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+
+    // Nearest grey ramp entry.
+    let avg = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let grey_index = if avg > 238 {
+        23
+    } else {
+        avg.saturating_sub(3) / 10
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    let grey = 232 + grey_index as u8;
+
+    let distance = |code: u8| perceptual_distance((r, g, b), ansi256_to_rgb(code));
+    if distance(grey) < distance(cube) {
+        grey
+    } else {
+        cube
+    }
+}
+
+fn nearest_basic(r: u8, g: u8, b: u8) -> usize {
+    let distances = BASIC_RGB.map(|basic| perceptual_distance((r, g, b), basic));
+    (0..BASIC_RGB.len())
+        .min_by(|&i, &j| distances[i].total_cmp(&distances[j]))
+        .unwrap_or(0)
+}
+
+/// An sRGB channel converted to linear light (0.0 to 1.0).
+fn linear(v: u8) -> f32 {
+    let c = f32::from(v) / 255.0;
+    if c <= 0.039_28 {
+        c / 12.92
+    } else {
+        float::powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Linear sRGB to LMS cone response, from the `OKLab` definition.
+const TO_LMS: [[f32; 3]; 3] = [
+    [0.412_221_5, 0.536_332_5, 0.051_446],
+    [0.211_903_5, 0.680_699_5, 0.107_397],
+    [0.088_302_46, 0.281_718_8, 0.629_978_7],
+];
+
+/// Cube-rooted LMS to `OKLab` `[L, a, b]`.
+const TO_LAB: [[f32; 3]; 3] = [
+    [0.210_454_3, 0.793_617_8, -0.004_072_047],
+    [1.977_998, -2.428_592, 0.450_593_7],
+    [0.025_904_04, 0.782_771_8, -0.808_675_8],
+];
+
+fn transform(matrix: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| float::mul_add(row[0], v[0], float::mul_add(row[1], v[1], row[2] * v[2])))
+}
+
+/// `OKLab` `[L, a, b]` to cube-rooted LMS.
+const FROM_LAB: [[f32; 3]; 3] = [
+    [1.0, 0.396_337_8, 0.215_803_8],
+    [1.0, -0.105_561_3, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_486],
+];
+
+/// LMS cone response to linear sRGB.
+const FROM_LMS: [[f32; 3]; 3] = [
+    [4.076_742, -3.307_712, 0.230_97],
+    [-1.268_438, 2.609_757, -0.341_319_4],
+    [-0.004_196_086, -0.703_418_6, 1.707_615],
+];
+
+/// Convert `OKLab` `[L, a, b]` to sRGB, clamping colors outside the gamut.
+fn from_oklab(lab: [f32; 3]) -> (u8, u8, u8) {
+    let lms = transform(&FROM_LAB, lab).map(|v| v * v * v);
+    let [r, g, b] = transform(&FROM_LMS, lms).map(encode);
+    (r, g, b)
+}
+
+/// A linear-light channel (0.0 to 1.0) converted to sRGB.
+fn encode(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let v = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        float::mul_add(1.055, float::powf(c, 1.0 / 2.4), -0.055)
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let out = float::round(v * 255.0).clamp(0.0, 255.0) as u8;
+    out
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a, which unlike the standard library's hasher is the same in
+/// every build.
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        // Spread the last bytes written into the low bits.
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^ (h >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Convert an sRGB color to `OKLab` `[L, a, b]`.
+fn oklab(rgb: (u8, u8, u8)) -> [f32; 3] {
+    let lms = transform(&TO_LMS, [linear(rgb.0), linear(rgb.1), linear(rgb.2)]);
+    transform(&TO_LAB, lms.map(float::cbrt))
+}
+
+/// Euclidean distance between two sRGB colors in `OKLab`.
+fn perceptual_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> f32 {
+    let (a, b) = (oklab(a), oklab(b));
+    let d = |i: usize| a[i] - b[i];
+    float::sqrt(float::mul_add(
+        d(0),
+        d(0),
+        float::mul_add(d(1), d(1), d(2) * d(2)),
+    ))
+}
+
+/// The name of 256-palette color `code`, looked up in `names`.
+fn ansi256_name(code: u8, names: Option<&ColorNames>) -> String {
+    match names.map(|names| names[usize::from(code)]) {
+        Some(name) if !name.is_empty() => format!("color {code} ({name})"),
+        _ => format!("color {code}"),
+    }
 }
 
 impl fmt::Display for Color {
@@ -215,7 +655,11 @@ pub mod brand {
     };
 
     /// Forge Black - background color.
-    pub const FORGE_BLACK: Color = Color::Rgb { r: 10, g: 10, b: 10 };
+    pub const FORGE_BLACK: Color = Color::Rgb {
+        r: 10,
+        g: 10,
+        b: 10,
+    };
 
     /// Success green.
     pub const SUCCESS: Color = Color::Rgb {
@@ -239,10 +683,66 @@ pub mod brand {
     };
 }
 
+/// Invoke `$callback!` with every named color and its foreground and
+/// background method names.
+macro_rules! named_colors {
+    ($callback:ident) => {
+        $callback! {
+            Black => black, on_black;
+            Red => red, on_red;
+            Green => green, on_green;
+            Yellow => yellow, on_yellow;
+            Blue => blue, on_blue;
+            Magenta => magenta, on_magenta;
+            Cyan => cyan, on_cyan;
+            White => white, on_white;
+            BrightBlack => bright_black, on_bright_black;
+            BrightRed => bright_red, on_bright_red;
+            BrightGreen => bright_green, on_bright_green;
+            BrightYellow => bright_yellow, on_bright_yellow;
+            BrightBlue => bright_blue, on_bright_blue;
+            BrightMagenta => bright_magenta, on_bright_magenta;
+            BrightCyan => bright_cyan, on_bright_cyan;
+            BrightWhite => bright_white, on_bright_white;
+        }
+    };
+}
+pub(crate) use named_colors;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_hash() {
+        assert_eq!(Color::from_hash("alice"), Color::from_hash("alice"));
+        assert_ne!(Color::from_hash("alice"), Color::from_hash("bob"));
+        assert_eq!(Color::from_hash(&7_u32), Color::from_hash(&7_u32));
+
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        for key in ["alice", "bob", "carol", "worker-1", "worker-2", "db", ""] {
+            let color = Color::from_hash(key);
+            assert!(matches!(color, Color::Rgb { .. }));
+            assert!(
+                color.contrast_ratio(&black).unwrap() > 4.5,
+                "{key}: {color:?}"
+            );
+            assert!(
+                color.contrast_ratio(&white).unwrap() > 1.5,
+                "{key}: {color:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        for rgb in [(249, 115, 22), (0, 0, 0), (255, 255, 255), (30, 144, 255)] {
+            let (r, g, b) = from_oklab(oklab(rgb));
+            assert!(r.abs_diff(rgb.0) <= 1 && g.abs_diff(rgb.1) <= 1 && b.abs_diff(rgb.2) <= 1);
+        }
+    }
+
     #[test]
     fn test_fg_codes() {
         assert_eq!(Color::Red.fg_code(), "31");
@@ -251,9 +751,82 @@ mod tests {
         assert_eq!(Color::rgb(255, 128, 0).fg_code(), "38;2;255;128;0");
     }
 
+    #[test]
+    fn test_downgrade() {
+        assert_eq!(Color::rgb(255, 0, 0).to_ansi256(), Color::Ansi256(196));
+        assert_eq!(Color::rgb(128, 128, 128).to_ansi256(), Color::Ansi256(244));
+        assert_eq!(Color::rgb(250, 10, 10).to_ansi16(), Color::BrightRed);
+        assert_eq!(Color::Ansi256(16).to_ansi16(), Color::Black);
+        assert_eq!(Color::Red.downgrade(ColorLevel::None), None);
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = Color::rgb(0, 0, 0).contrast_ratio(&Color::rgb(255, 255, 255));
+        assert!((ratio.unwrap() - 21.0).abs() < 0.01);
+        assert_eq!(Color::Default.contrast_ratio(&Color::Black), None);
+    }
+
+    #[test]
+    fn test_blend() {
+        let (black, white) = (Color::rgb(0, 0, 0), Color::rgb(255, 255, 255));
+        assert_eq!(black.blend(white, 0.0), black);
+        assert_eq!(black.blend(white, 1.0), white);
+        assert_eq!(black.blend(white, 2.0), white);
+        assert_eq!(Color::Red.blend(Color::Blue, 0.5), Color::rgb(103, 0, 119));
+        assert_eq!(Color::Default.blend(white, 0.25), Color::Default);
+        assert_eq!(Color::Default.blend(white, 0.75), white);
+        assert_eq!(
+            Color::rgb(255, 0, 0).with_alpha_over(0.5, black),
+            Color::rgb(128, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        let (black, white) = (Color::rgb(0, 0, 0), Color::rgb(255, 255, 255));
+        assert!((black.distance(&white) - 1.0).abs() < 0.001);
+        assert!(black.distance(&Color::Black) < f32::EPSILON);
+        assert!(Color::Red.distance(&Color::BrightRed) < Color::Red.distance(&Color::Green));
+        assert!(Color::Default.distance(&Color::Default) < f32::EPSILON);
+        assert!(Color::Default.distance(&black).is_infinite());
+    }
+
     #[test]
     fn test_from_hex() {
         let color = Color::from_hex("#F97316");
-        assert_eq!(color, Color::Rgb { r: 249, g: 115, b: 22 });
+        assert_eq!(
+            color,
+            Color::Rgb {
+                r: 249,
+                g: 115,
+                b: 22
+            }
+        );
+        assert_eq!(
+            Color::try_from_hex("f73").ok(),
+            Some(Color::rgb(255, 119, 51))
+        );
+        for bad in ["", "#12345", "+12345", "gg0000", "\u{e9}\u{e9}\u{e9}"] {
+            assert!(matches!(
+                Color::try_from_hex(bad),
+                Err(Error::ColorParse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ansi256_name() {
+        let mut names: ColorNames = [""; 256];
+        names[42] = "SpringGreen2";
+        assert_eq!(ansi256_name(42, Some(&names)), "color 42 (SpringGreen2)");
+        assert_eq!(ansi256_name(43, Some(&names)), "color 43");
+        assert_eq!(ansi256_name(42, None), "color 42");
+        #[cfg(feature = "color-names")]
+        {
+            use crate::color_names::XTERM_COLOR_NAMES;
+            assert_eq!(XTERM_COLOR_NAMES[208], "DarkOrange");
+            assert_eq!(XTERM_COLOR_NAMES[255], "Grey93");
+        }
     }
 }
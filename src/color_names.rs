@@ -0,0 +1,322 @@
+//! Names for the 256-color palette in color descriptions.
+
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// Names for the 256-color palette, indexed by color number.
+///
+/// An empty name leaves that color unnamed.
+pub type ColorNames = [&'static str; 256];
+
+/// The xterm names for the 256-color palette (requires the
+/// `color-names` feature), as used by most color charts: `SpringGreen2`
+/// for 42, `DarkOrange` for 208, `Grey50` for 244.
+///
+/// Several colors share a name, such as `Green3` for 34 and 40.
+#[cfg(feature = "color-names")]
+pub static XTERM_COLOR_NAMES: ColorNames = [
+    // 0-15: the basic colors.
+    "Black",
+    "Maroon",
+    "Green",
+    "Olive",
+    "Navy",
+    "Purple",
+    "Teal",
+    "Silver",
+    "Grey",
+    "Red",
+    "Lime",
+    "Yellow",
+    "Blue",
+    "Fuchsia",
+    "Aqua",
+    "White",
+    // 16-231: the 6x6x6 color cube.
+    "Grey0",
+    "NavyBlue",
+    "DarkBlue",
+    "Blue3",
+    "Blue3",
+    "Blue1",
+    "DarkGreen",
+    "DeepSkyBlue4",
+    "DeepSkyBlue4",
+    "DeepSkyBlue4",
+    "DodgerBlue3",
+    "DodgerBlue2",
+    "Green4",
+    "SpringGreen4",
+    "Turquoise4",
+    "DeepSkyBlue3",
+    "DeepSkyBlue3",
+    "DodgerBlue1",
+    "Green3",
+    "SpringGreen3",
+    "DarkCyan",
+    "LightSeaGreen",
+    "DeepSkyBlue2",
+    "DeepSkyBlue1",
+    "Green3",
+    "SpringGreen3",
+    "SpringGreen2",
+    "Cyan3",
+    "DarkTurquoise",
+    "Turquoise2",
+    "Green1",
+    "SpringGreen2",
+    "SpringGreen1",
+    "MediumSpringGreen",
+    "Cyan2",
+    "Cyan1",
+    "DarkRed",
+    "DeepPink4",
+    "Purple4",
+    "Purple4",
+    "Purple3",
+    "BlueViolet",
+    "Orange4",
+    "Grey37",
+    "MediumPurple4",
+    "SlateBlue3",
+    "SlateBlue3",
+    "RoyalBlue1",
+    "Chartreuse4",
+    "DarkSeaGreen4",
+    "PaleTurquoise4",
+    "SteelBlue",
+    "SteelBlue3",
+    "CornflowerBlue",
+    "Chartreuse3",
+    "DarkSeaGreen4",
+    "CadetBlue",
+    "CadetBlue",
+    "SkyBlue3",
+    "SteelBlue1",
+    "Chartreuse3",
+    "PaleGreen3",
+    "SeaGreen3",
+    "Aquamarine3",
+    "MediumTurquoise",
+    "SteelBlue1",
+    "Chartreuse2",
+    "SeaGreen2",
+    "SeaGreen1",
+    "SeaGreen1",
+    "Aquamarine1",
+    "DarkSlateGray2",
+    "DarkRed",
+    "DeepPink4",
+    "DarkMagenta",
+    "DarkMagenta",
+    "DarkViolet",
+    "Purple",
+    "Orange4",
+    "LightPink4",
+    "Plum4",
+    "MediumPurple3",
+    "MediumPurple3",
+    "SlateBlue1",
+    "Yellow4",
+    "Wheat4",
+    "Grey53",
+    "LightSlateGrey",
+    "MediumPurple",
+    "LightSlateBlue",
+    "Yellow4",
+    "DarkOliveGreen3",
+    "DarkSeaGreen",
+    "LightSkyBlue3",
+    "LightSkyBlue3",
+    "SkyBlue2",
+    "Chartreuse2",
+    "DarkOliveGreen3",
+    "PaleGreen3",
+    "DarkSeaGreen3",
+    "DarkSlateGray3",
+    "SkyBlue1",
+    "Chartreuse1",
+    "LightGreen",
+    "LightGreen",
+    "PaleGreen1",
+    "Aquamarine1",
+    "DarkSlateGray1",
+    "Red3",
+    "DeepPink4",
+    "MediumVioletRed",
+    "Magenta3",
+    "DarkViolet",
+    "Purple",
+    "DarkOrange3",
+    "IndianRed",
+    "HotPink3",
+    "MediumOrchid3",
+    "MediumOrchid",
+    "MediumPurple2",
+    "DarkGoldenrod",
+    "LightSalmon3",
+    "RosyBrown",
+    "Grey63",
+    "MediumPurple2",
+    "MediumPurple1",
+    "Gold3",
+    "DarkKhaki",
+    "NavajoWhite3",
+    "Grey69",
+    "LightSteelBlue3",
+    "LightSteelBlue",
+    "Yellow3",
+    "DarkOliveGreen3",
+    "DarkSeaGreen3",
+    "DarkSeaGreen2",
+    "LightCyan3",
+    "LightSkyBlue1",
+    "GreenYellow",
+    "DarkOliveGreen2",
+    "PaleGreen1",
+    "DarkSeaGreen2",
+    "DarkSeaGreen1",
+    "PaleTurquoise1",
+    "Red3",
+    "DeepPink3",
+    "DeepPink3",
+    "Magenta3",
+    "Magenta3",
+    "Magenta2",
+    "DarkOrange3",
+    "IndianRed",
+    "HotPink3",
+    "HotPink2",
+    "Orchid",
+    "MediumOrchid1",
+    "Orange3",
+    "LightSalmon3",
+    "LightPink3",
+    "Pink3",
+    "Plum3",
+    "Violet",
+    "Gold3",
+    "LightGoldenrod3",
+    "Tan",
+    "MistyRose3",
+    "Thistle3",
+    "Plum2",
+    "Yellow3",
+    "Khaki3",
+    "LightGoldenrod2",
+    "LightYellow3",
+    "Grey84",
+    "LightSteelBlue1",
+    "Yellow2",
+    "DarkOliveGreen1",
+    "DarkOliveGreen1",
+    "DarkSeaGreen1",
+    "Honeydew2",
+    "LightCyan1",
+    "Red1",
+    "DeepPink2",
+    "DeepPink1",
+    "DeepPink1",
+    "Magenta2",
+    "Magenta1",
+    "OrangeRed1",
+    "IndianRed1",
+    "IndianRed1",
+    "HotPink",
+    "HotPink",
+    "MediumOrchid1",
+    "DarkOrange",
+    "Salmon1",
+    "LightCoral",
+    "PaleVioletRed1",
+    "Orchid2",
+    "Orchid1",
+    "Orange1",
+    "SandyBrown",
+    "LightSalmon1",
+    "LightPink1",
+    "Pink1",
+    "Plum1",
+    "Gold1",
+    "LightGoldenrod2",
+    "LightGoldenrod2",
+    "NavajoWhite1",
+    "MistyRose1",
+    "Thistle1",
+    "Yellow1",
+    "LightGoldenrod1",
+    "Khaki1",
+    "Wheat1",
+    "Cornsilk1",
+    "Grey100",
+    // 232-255: the grayscale ramp.
+    "Grey3",
+    "Grey7",
+    "Grey11",
+    "Grey15",
+    "Grey19",
+    "Grey23",
+    "Grey27",
+    "Grey30",
+    "Grey35",
+    "Grey39",
+    "Grey42",
+    "Grey46",
+    "Grey50",
+    "Grey54",
+    "Grey58",
+    "Grey62",
+    "Grey66",
+    "Grey70",
+    "Grey74",
+    "Grey78",
+    "Grey82",
+    "Grey85",
+    "Grey89",
+    "Grey93",
+];
+
+#[cfg(feature = "std")]
+static NAMES: RwLock<Option<&'static ColorNames>> = RwLock::new(None);
+
+/// Name 256-palette colors in descriptions from `names`, or go back to
+/// plain numbers with `None`.
+///
+/// [`Color::name`](crate::Color::name), and with it SGR descriptions,
+/// then add the name after the number.
+///
+/// ```rust
+/// # #[cfg(feature = "color-names")]
+/// # {
+/// use glyphs::{set_color_names, Color, XTERM_COLOR_NAMES};
+///
+/// set_color_names(Some(&XTERM_COLOR_NAMES));
+/// assert_eq!(Color::Ansi256(208).name(), "color 208 (DarkOrange)");
+/// set_color_names(None);
+/// assert_eq!(Color::Ansi256(208).name(), "color 208");
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn set_color_names(names: Option<&'static ColorNames>) {
+    *NAMES
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = names;
+}
+
+/// The names set with [`set_color_names`], if any.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn color_names() -> Option<&'static ColorNames> {
+    *NAMES
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// The names set with `set_color_names`, if any.
+///
+/// Without `std` there are never any.
+#[cfg(not(feature = "std"))]
+#[must_use]
+pub const fn color_names() -> Option<&'static ColorNames> {
+    None
+}
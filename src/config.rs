@@ -0,0 +1,166 @@
+//! Global color configuration driven by the environment.
+//!
+//! The environment is evaluated once, the first time colors are rendered:
+//!
+//! - `CLICOLOR_FORCE` or `FORCE_COLOR` set to anything but `0`/`false` forces colors on.
+//! - `NO_COLOR` set to a non-empty value turns colors off.
+//! - `CLICOLOR=0` turns colors off.
+//!
+//! [`set_color_choice`] overrides whatever the environment says, which is how
+//! `--color=always|auto|never` flags should be wired up.
+//!
+//! Without the `std` feature the environment is not consulted and the
+//! choice defaults to [`ColorChoice::Auto`].
+
+#[cfg(feature = "std")]
+use alloc::string::String;
+use core::sync::atomic::{AtomicU8, Ordering};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+/// Whether styled output should contain escape sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Always emit escape sequences.
+    Always,
+    /// Emit escape sequences unless the environment says otherwise.
+    #[default]
+    Auto,
+    /// Never emit escape sequences.
+    Never,
+}
+
+impl ColorChoice {
+    /// Evaluate `NO_COLOR`, `CLICOLOR`, `CLICOLOR_FORCE`, and `FORCE_COLOR`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    #[cfg(feature = "std")]
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let truthy =
+            |name: &str| lookup(name).is_some_and(|v| !v.is_empty() && v != "0" && v != "false");
+
+        if truthy("CLICOLOR_FORCE") || truthy("FORCE_COLOR") {
+            Self::Always
+        } else if lookup("NO_COLOR").is_some_and(|v| !v.is_empty())
+            || lookup("CLICOLOR").is_some_and(|v| v == "0")
+        {
+            Self::Never
+        } else {
+            Self::Auto
+        }
+    }
+
+    /// Parse an `always`, `auto`, or `never` value, as used by
+    /// `--color` flags and variables like `CARGO_TERM_COLOR`.
+    ///
+    /// ```rust
+    /// use glyphs::ColorChoice;
+    ///
+    /// assert_eq!(ColorChoice::parse("Never"), Some(ColorChoice::Never));
+    /// assert_eq!(ColorChoice::parse("sometimes"), None);
+    /// ```
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "always" => Some(Self::Always),
+            "auto" => Some(Self::Auto),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether this choice allows colors when the output stream is unknown.
+    ///
+    /// `Auto` resolves to `true` here; stream-aware callers should check
+    /// whether their stream is a terminal instead.
+    #[must_use]
+    pub const fn allows_color(self) -> bool {
+        !matches!(self, Self::Never)
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Always => 1,
+            Self::Auto => 2,
+            Self::Never => 3,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Always),
+            2 => Some(Self::Auto),
+            3 => Some(Self::Never),
+            _ => None,
+        }
+    }
+}
+
+/// Explicit override; `0` means "not set, use the environment".
+static OVERRIDE: AtomicU8 = AtomicU8::new(0);
+#[cfg(feature = "std")]
+static FROM_ENV: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Override the color choice for the whole process.
+pub fn set_color_choice(choice: ColorChoice) {
+    OVERRIDE.store(choice.to_u8(), Ordering::Relaxed);
+}
+
+/// Drop any override set with [`set_color_choice`] and go back to the environment.
+pub fn reset_color_choice() {
+    OVERRIDE.store(0, Ordering::Relaxed);
+}
+
+/// The color choice currently in effect.
+#[must_use]
+pub fn color_choice() -> ColorChoice {
+    let choice = ColorChoice::from_u8(OVERRIDE.load(Ordering::Relaxed));
+    #[cfg(feature = "std")]
+    let choice = choice.or_else(|| Some(*FROM_ENV.get_or_init(ColorChoice::from_env)));
+    choice.unwrap_or_default()
+}
+
+/// Whether [`Styled::render`](crate::Styled::render) and
+/// [`Style::apply`](crate::Style::apply) should emit escape sequences.
+#[must_use]
+pub fn colors_enabled() -> bool {
+    color_choice().allows_color()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::testing::lookup_from;
+
+    #[cfg(feature = "std")]
+    fn choice(vars: &[(&str, &str)]) -> ColorChoice {
+        ColorChoice::from_lookup(lookup_from(vars))
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_env_precedence() {
+        assert_eq!(choice(&[]), ColorChoice::Auto);
+        assert_eq!(choice(&[("NO_COLOR", "1")]), ColorChoice::Never);
+        assert_eq!(choice(&[("NO_COLOR", "")]), ColorChoice::Auto);
+        assert_eq!(choice(&[("CLICOLOR", "0")]), ColorChoice::Never);
+        assert_eq!(
+            choice(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")]),
+            ColorChoice::Always
+        );
+        assert_eq!(choice(&[("FORCE_COLOR", "0")]), ColorChoice::Auto);
+        assert_eq!(choice(&[("CLICOLOR_FORCE", "1")]), ColorChoice::Always);
+    }
+
+    #[test]
+    fn test_allows_color() {
+        assert!(ColorChoice::Always.allows_color());
+        assert!(ColorChoice::Auto.allows_color());
+        assert!(!ColorChoice::Never.allows_color());
+    }
+}
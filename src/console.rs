@@ -0,0 +1,232 @@
+//! Per-stream styling contexts.
+
+use crate::caps::{ColorLevel, TermCaps};
+use crate::config::{self, ColorChoice};
+use crate::hyperlink::link_or_fallback;
+use crate::style::{Style, Styled};
+use std::borrow::Cow;
+use std::io::IsTerminal;
+
+/// An output stream a [`Console`] writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// Standard output.
+    Stdout,
+    /// Standard error.
+    Stderr,
+}
+
+impl Stream {
+    /// Whether this stream is connected to a terminal.
+    #[must_use]
+    pub fn is_terminal(self) -> bool {
+        match self {
+            Self::Stdout => std::io::stdout().is_terminal(),
+            Self::Stderr => std::io::stderr().is_terminal(),
+        }
+    }
+}
+
+/// A styling context bound to one output stream.
+///
+/// Each console carries its own capabilities and color choice, so stdout
+/// can be colored while stderr is redirected to a file (or vice versa).
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{Color, Console};
+///
+/// let err = Console::stderr();
+/// eprintln!("{}", err.style("error:").fg(Color::Red).bold());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Console {
+    stream: Stream,
+    is_terminal: bool,
+    caps: TermCaps,
+    choice: ColorChoice,
+}
+
+impl Console {
+    /// A console for standard output.
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::detect(Stream::Stdout)
+    }
+
+    /// A console for standard error.
+    #[must_use]
+    pub fn stderr() -> Self {
+        Self::detect(Stream::Stderr)
+    }
+
+    fn detect(stream: Stream) -> Self {
+        Self {
+            stream,
+            is_terminal: stream.is_terminal(),
+            caps: TermCaps::from_env(),
+            choice: config::color_choice(),
+        }
+    }
+
+    /// Override the color choice for this console only.
+    #[must_use]
+    pub const fn with_color_choice(mut self, choice: ColorChoice) -> Self {
+        self.choice = choice;
+        self
+    }
+
+    /// Override the detected capabilities for this console only.
+    #[must_use]
+    pub const fn with_caps(mut self, caps: TermCaps) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// The stream this console writes to.
+    #[must_use]
+    pub const fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    /// Whether the stream was a terminal when the console was created.
+    #[must_use]
+    pub const fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    /// The detected capabilities of the stream.
+    #[must_use]
+    pub const fn caps(&self) -> TermCaps {
+        self.caps
+    }
+
+    /// The color choice in effect for this console.
+    #[must_use]
+    pub const fn color_choice(&self) -> ColorChoice {
+        self.choice
+    }
+
+    /// The color level output is degraded to, after applying the color choice.
+    #[must_use]
+    pub fn color_level(&self) -> ColorLevel {
+        match self.choice {
+            ColorChoice::Always => self.caps.color.max(ColorLevel::Ansi16),
+            ColorChoice::Auto if self.is_terminal => self.caps.color,
+            ColorChoice::Auto | ColorChoice::Never => ColorLevel::None,
+        }
+    }
+
+    /// Create a styled string that renders appropriately for this stream.
+    #[must_use]
+    pub fn style<'a, S: Into<Cow<'a, str>>>(&self, text: S) -> Styled<'a> {
+        Styled::new(text).color_level(self.color_level())
+    }
+
+    /// Render `text` in `style` for this stream.
+    #[must_use]
+    pub fn paint<'a>(&self, style: &Style, text: &'a str) -> Styled<'a> {
+        self.style(text)
+            .fg_opt(style.foreground)
+            .bg_opt(style.background)
+            .modifiers(style.modifiers.iter())
+    }
+
+    /// Link `text` to `url` for this stream, falling back to
+    /// `text (url)` as [`link_or_fallback`] does.
+    ///
+    /// Links are only written to a terminal that supports them, and not
+    /// when colors are turned off, since the output may then be read by
+    /// something that doesn't understand escape sequences.
+    #[must_use]
+    pub fn link(&self, text: &str, url: &str) -> String {
+        let caps = TermCaps {
+            hyperlinks: self.caps.hyperlinks && self.color_level() != ColorLevel::None,
+            ..self.caps
+        };
+        link_or_fallback(text, url, &caps)
+    }
+}
+
+impl Style {
+    /// Print `text` in this style to standard output, with colors as
+    /// [`Console::stdout`] renders them: plain when stdout is redirected,
+    /// and degraded to what the terminal supports.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Style};
+    ///
+    /// Style::new().fg(Color::Green).print("done\n");
+    /// ```
+    pub fn print(&self, text: &str) {
+        print!("{}", Console::stdout().paint(self, text));
+    }
+
+    /// Print `text` in this style to standard error, with colors as
+    /// [`Console::stderr`] renders them.
+    pub fn eprint(&self, text: &str) {
+        eprint!("{}", Console::stderr().paint(self, text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    fn console(is_terminal: bool, color: ColorLevel, choice: ColorChoice) -> Console {
+        Console {
+            stream: Stream::Stdout,
+            is_terminal,
+            caps: TermCaps {
+                color,
+                ..TermCaps::default()
+            },
+            choice,
+        }
+    }
+
+    #[test]
+    fn test_color_level_resolution() {
+        let piped = console(false, ColorLevel::TrueColor, ColorChoice::Auto);
+        assert_eq!(piped.color_level(), ColorLevel::None);
+
+        let tty = console(true, ColorLevel::Ansi256, ColorChoice::Auto);
+        assert_eq!(tty.color_level(), ColorLevel::Ansi256);
+
+        let forced = console(false, ColorLevel::None, ColorChoice::Always);
+        assert_eq!(forced.color_level(), ColorLevel::Ansi16);
+    }
+
+    #[test]
+    fn test_console_style_degrades() {
+        let piped = console(false, ColorLevel::TrueColor, ColorChoice::Auto);
+        assert_eq!(piped.style("hi").fg(Color::Red).to_string(), "hi");
+
+        let basic = console(true, ColorLevel::Ansi16, ColorChoice::Auto);
+        let s = basic.style("hi").fg(Color::rgb(250, 10, 10)).to_string();
+        assert_eq!(s, "\x1b[91mhi\x1b[0m");
+    }
+
+    #[test]
+    fn test_console_link() {
+        let mut tty = console(true, ColorLevel::Ansi16, ColorChoice::Auto);
+        assert_eq!(tty.link("docs", "u"), "docs (u)");
+        tty.caps.hyperlinks = true;
+        assert_eq!(tty.link("docs", "u"), "\x1b]8;;u\x1b\\docs\x1b]8;;\x1b\\");
+
+        let mut piped = console(false, ColorLevel::Ansi16, ColorChoice::Auto);
+        piped.caps.hyperlinks = true;
+        assert_eq!(piped.link("docs", "u"), "docs (u)");
+    }
+
+    #[test]
+    fn test_console_paint() {
+        let style = Style::new().fg(Color::Red).bg(Color::Black);
+        let forced = console(false, ColorLevel::Ansi16, ColorChoice::Always);
+        assert_eq!(forced.paint(&style, "x").to_string(), "\x1b[31;40mx\x1b[0m");
+        let never = console(true, ColorLevel::TrueColor, ColorChoice::Never);
+        assert_eq!(never.paint(&style, "x").to_string(), "x");
+    }
+}
@@ -0,0 +1,115 @@
+//! C0 control characters.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// A C0 control character found in parsed output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlChar {
+    /// NUL (0x00).
+    Null,
+    /// BEL (0x07) - ring the terminal bell.
+    Bell,
+    /// BS (0x08) - move the cursor one column left.
+    Backspace,
+    /// HT (0x09) - move to the next tab stop.
+    Tab,
+    /// LF (0x0A) - move to the next line.
+    LineFeed,
+    /// VT (0x0B) - treated like a line feed by most terminals.
+    VerticalTab,
+    /// FF (0x0C) - treated like a line feed by most terminals.
+    FormFeed,
+    /// CR (0x0D) - move to the start of the line.
+    CarriageReturn,
+    /// DEL (0x7F).
+    Delete,
+    /// Any other C0 control (0x01-0x1F, excluding ESC).
+    Other(u8),
+}
+
+impl ControlChar {
+    /// Classify a character, returning `None` for ESC and non-control characters.
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '\0' => Some(Self::Null),
+            '\x07' => Some(Self::Bell),
+            '\x08' => Some(Self::Backspace),
+            '\t' => Some(Self::Tab),
+            '\n' => Some(Self::LineFeed),
+            '\x0b' => Some(Self::VerticalTab),
+            '\x0c' => Some(Self::FormFeed),
+            '\r' => Some(Self::CarriageReturn),
+            '\x7f' => Some(Self::Delete),
+            '\x1b' => None,
+            #[allow(clippy::cast_possible_truncation)]
+            '\x01'..='\x1f' => Some(Self::Other(c as u8)),
+            _ => None,
+        }
+    }
+
+    /// Whether `byte` is a control character that [`from_char`](Self::from_char) accepts.
+    #[must_use]
+    pub const fn is_control_byte(byte: u8) -> bool {
+        (byte < 0x20 && byte != 0x1b) || byte == 0x7f
+    }
+
+    /// The character itself.
+    #[must_use]
+    pub const fn as_char(&self) -> char {
+        match self {
+            Self::Null => '\0',
+            Self::Bell => '\x07',
+            Self::Backspace => '\x08',
+            Self::Tab => '\t',
+            Self::LineFeed => '\n',
+            Self::VerticalTab => '\x0b',
+            Self::FormFeed => '\x0c',
+            Self::CarriageReturn => '\r',
+            Self::Delete => '\x7f',
+            Self::Other(b) => *b as char,
+        }
+    }
+
+    /// Get a human-readable name for the control character.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::Null => "null".to_string(),
+            Self::Bell => "bell".to_string(),
+            Self::Backspace => "backspace".to_string(),
+            Self::Tab => "tab".to_string(),
+            Self::LineFeed => "line feed".to_string(),
+            Self::VerticalTab => "vertical tab".to_string(),
+            Self::FormFeed => "form feed".to_string(),
+            Self::CarriageReturn => "carriage return".to_string(),
+            Self::Delete => "delete".to_string(),
+            Self::Other(b) => format!("control 0x{b:02x}"),
+        }
+    }
+}
+
+impl fmt::Display for ControlChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_round_trip() {
+        for byte in (0u8..0x20).chain([0x7f]) {
+            let c = char::from(byte);
+            match ControlChar::from_char(c) {
+                Some(control) => assert_eq!(control.as_char(), c),
+                None => assert_eq!(byte, 0x1b),
+            }
+        }
+        assert_eq!(ControlChar::from_char('a'), None);
+    }
+}
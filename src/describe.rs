@@ -0,0 +1,135 @@
+//! Pluggable human-readable descriptions of escape sequences.
+
+use crate::escape::{describe_sgr, Escape, EscapeKind};
+use crate::op::{CursorOp, EraseOp};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// Produces the human-readable descriptions of escape sequences.
+///
+/// Every method has an English default, so a translation only needs to
+/// override the parts it covers; anything else falls back to the
+/// description the parser attached to the [`Escape`].
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{parse, CursorOp, Describer};
+///
+/// struct German;
+///
+/// impl Describer for German {
+///     fn cursor(&self, op: CursorOp) -> String {
+///         match op {
+///             CursorOp::Up(n) => format!("Cursor {n} nach oben"),
+///             other => glyphs::EnglishDescriber.cursor(other),
+///         }
+///     }
+/// }
+///
+/// let escape = parse("\x1b[2A")[0].as_escape().cloned().unwrap();
+/// assert_eq!(escape.describe_with(&German), "Cursor 2 nach oben");
+/// ```
+pub trait Describer {
+    /// Describe an SGR sequence from its parameters.
+    fn sgr(&self, params: &[u32]) -> String {
+        describe_sgr(params)
+    }
+
+    /// Describe a cursor operation.
+    fn cursor(&self, op: CursorOp) -> String {
+        match op {
+            CursorOp::Up(n) => format!("cursor up {n}"),
+            CursorOp::Down(n) => format!("cursor down {n}"),
+            CursorOp::Forward(n) => format!("cursor right {n}"),
+            CursorOp::Back(n) => format!("cursor left {n}"),
+            CursorOp::Column(n) => format!("cursor to column {n}"),
+            CursorOp::Goto(row, col) => format!("cursor to ({row}, {col})"),
+            CursorOp::Save => "save cursor position".to_string(),
+            CursorOp::Restore => "restore cursor position".to_string(),
+        }
+    }
+
+    /// Describe an erase operation.
+    fn erase(&self, op: EraseOp) -> String {
+        let description = match op {
+            EraseOp::ToScreenEnd => "clear to end of screen",
+            EraseOp::ToScreenStart => "clear to start of screen",
+            EraseOp::Screen => "clear entire screen",
+            EraseOp::ScreenAndScrollback => "clear screen and scrollback",
+            EraseOp::ToLineEnd => "clear to end of line",
+            EraseOp::ToLineStart => "clear to start of line",
+            EraseOp::Line => "clear entire line",
+        };
+        description.to_string()
+    }
+
+    /// Describe any other sequence; defaults to the parser's description.
+    fn other(&self, escape: &Escape) -> String {
+        escape.description.clone()
+    }
+
+    /// Describe `escape`, dispatching to the methods above.
+    fn describe(&self, escape: &Escape) -> String {
+        if escape.kind == EscapeKind::Sgr && escape.unparsed.is_empty() {
+            return self.sgr(&escape.params);
+        }
+        if let Some(op) = escape.cursor_op() {
+            return self.cursor(op);
+        }
+        if let Some(op) = escape.erase_op() {
+            return self.erase(op);
+        }
+        self.other(escape)
+    }
+}
+
+/// The built-in English descriptions, as stored in
+/// [`Escape::description`] by the parser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnglishDescriber;
+
+impl Describer for EnglishDescriber {}
+
+impl Escape {
+    /// Describe this sequence with a custom [`Describer`].
+    #[must_use]
+    pub fn describe_with(&self, describer: &dyn Describer) -> String {
+        describer.describe(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_english_matches_parser() {
+        let input = "\x1b[1;31m\x1b[3A\x1b[2;4H\x1b7\x1b[u\x1b[2J\x1b[K\x1b[?25l\x1b]0;t\x07";
+        for segment in parse(input) {
+            let escape = segment.as_escape().unwrap();
+            assert_eq!(escape.describe_with(&EnglishDescriber), escape.description);
+        }
+    }
+
+    #[test]
+    fn test_partial_override() {
+        struct Terse;
+        impl Describer for Terse {
+            fn erase(&self, _: EraseOp) -> String {
+                "erase".to_string()
+            }
+        }
+
+        let segments = parse("\x1b[2K\x1b[1m");
+        assert_eq!(
+            segments[0].as_escape().unwrap().describe_with(&Terse),
+            "erase"
+        );
+        assert_eq!(
+            segments[1].as_escape().unwrap().describe_with(&Terse),
+            "bold"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Inline diffs of two strings rendered with styles.
+
+use crate::color::Color;
+use crate::modifier::Modifier;
+use crate::style::Style;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The unit a diff compares.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DiffMode {
+    /// Compare words; runs of whitespace are compared as their own tokens.
+    #[default]
+    Word,
+    /// Compare characters.
+    Char,
+}
+
+/// Styles and granularity for [`InlineDiff::render`].
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{Color, DiffMode, InlineDiff, Style};
+///
+/// let diff = InlineDiff {
+///     mode: DiffMode::Char,
+///     changed: Style::new().fg(Color::Cyan),
+///     ..InlineDiff::default()
+/// };
+/// println!("{}", diff.render("expected", "exported"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InlineDiff {
+    /// Compare words or characters.
+    pub mode: DiffMode,
+    /// Style of text only present in the new string.
+    pub added: Style,
+    /// Style of text only present in the old string.
+    pub removed: Style,
+    /// Style of new text that replaces removed text.
+    pub changed: Style,
+}
+
+impl InlineDiff {
+    /// Word diff with green additions, red struck-through removals, and
+    /// yellow replacements.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            mode: DiffMode::Word,
+            added: Style::new().fg(Color::Green),
+            removed: Style::new()
+                .fg(Color::Red)
+                .modifier(Modifier::Strikethrough),
+            changed: Style::new().fg(Color::Yellow),
+        }
+    }
+
+    /// Render `old` and `new` as one string.
+    ///
+    /// Unchanged text is kept plain, removed text is followed by the text
+    /// replacing it, and each styled run is rendered with
+    /// [`Style::apply`], so colors follow [`crate::config`].
+    ///
+    /// Text the two strings start and end with is always kept. When what
+    /// is left between is too long to compare token by token (more than
+    /// about a million token pairs), it is shown as one replacement.
+    #[must_use]
+    pub fn render(&self, old: &str, new: &str) -> String {
+        let old = tokenize(old, self.mode);
+        let new = tokenize(new, self.mode);
+        let runs = diff_runs(&old, &new);
+
+        let mut out = String::new();
+        let mut i = 0;
+        while i < runs.len() {
+            let (op, text) = &runs[i];
+            let replaced = runs
+                .get(i + 1)
+                .filter(|(next, _)| *op != Op::Equal && *next != Op::Equal && next != op);
+            match (op, replaced) {
+                (Op::Equal, _) => out.push_str(text),
+                (_, Some((_, next))) => {
+                    let (removed, added) = if *op == Op::Delete {
+                        (text, next)
+                    } else {
+                        (next, text)
+                    };
+                    out.push_str(&self.removed.apply(removed));
+                    out.push_str(&self.changed.apply(added));
+                    i += 1;
+                }
+                (Op::Delete, None) => out.push_str(&self.removed.apply(text)),
+                (Op::Insert, None) => out.push_str(&self.added.apply(text)),
+            }
+            i += 1;
+        }
+        out
+    }
+}
+
+impl Default for InlineDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render a word diff of `old` and `new` with the default styles.
+///
+/// ```rust
+/// let out = glyphs::diff("the quick fox", "the slow fox");
+/// assert_eq!(glyphs::strip_ansi(&out), "the quickslow fox");
+/// ```
+#[must_use]
+pub fn diff(old: &str, new: &str) -> String {
+    InlineDiff::new().render(old, new)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+fn tokenize(text: &str, mode: DiffMode) -> Vec<&str> {
+    match mode {
+        DiffMode::Char => text
+            .char_indices()
+            .map(|(i, c)| &text[i..i + c.len_utf8()])
+            .collect(),
+        DiffMode::Word => {
+            let mut tokens = Vec::new();
+            let mut start = 0;
+            let mut space = None;
+            for (i, c) in text.char_indices() {
+                let is_space = c.is_whitespace();
+                if space.is_some_and(|s| s != is_space) {
+                    tokens.push(&text[start..i]);
+                    start = i;
+                }
+                space = Some(is_space);
+            }
+            if start < text.len() {
+                tokens.push(&text[start..]);
+            }
+            tokens
+        }
+    }
+}
+
+/// The most cells [`diff_runs`] allocates for its LCS table.
+const MAX_TABLE: usize = 1 << 20;
+
+/// Longest-common-subsequence diff, merged into runs of the same operation.
+///
+/// A common prefix and suffix are matched first; if the table for the
+/// rest would exceed [`MAX_TABLE`] cells, the rest is one delete and one
+/// insert.
+fn diff_runs(old: &[&str], new: &[&str]) -> Vec<(Op, String)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut runs: Vec<(Op, String)> = Vec::new();
+    for token in &old[..prefix] {
+        push_run(&mut runs, Op::Equal, token);
+    }
+    let old_rest = &old[prefix..old.len() - suffix];
+    let new_rest = &new[prefix..new.len() - suffix];
+    if (old_rest.len() + 1).saturating_mul(new_rest.len() + 1) > MAX_TABLE {
+        for token in old_rest {
+            push_run(&mut runs, Op::Delete, token);
+        }
+        for token in new_rest {
+            push_run(&mut runs, Op::Insert, token);
+        }
+    } else {
+        lcs_runs(old_rest, new_rest, &mut runs);
+    }
+    for token in &old[old.len() - suffix..] {
+        push_run(&mut runs, Op::Equal, token);
+    }
+    runs
+}
+
+fn push_run(runs: &mut Vec<(Op, String)>, op: Op, token: &str) {
+    match runs.last_mut() {
+        Some((last, text)) if *last == op => text.push_str(token),
+        _ => runs.push((op, String::from(token))),
+    }
+}
+
+fn lcs_runs(old: &[&str], new: &[&str], runs: &mut Vec<(Op, String)>) {
+    let width = new.len() + 1;
+    // lcs[i * width + j] is the LCS length of old[i..] and new[j..].
+    let mut lcs = vec![0_usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push_run(runs, Op::Equal, old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len()
+            || (i < old.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            push_run(runs, Op::Delete, old[i]);
+            i += 1;
+        } else {
+            push_run(runs, Op::Insert, new[j]);
+            j += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{set_color_choice, ColorChoice};
+    use alloc::format;
+
+    #[test]
+    fn test_word_and_char_runs() {
+        let old = tokenize("a  bc d", DiffMode::Word);
+        assert_eq!(old, ["a", "  ", "bc", " ", "d"]);
+        let new = tokenize("a  xy d e", DiffMode::Word);
+        let runs = diff_runs(&old, &new);
+        let runs: Vec<_> = runs.iter().map(|(op, t)| (*op, t.as_str())).collect();
+        assert_eq!(
+            runs,
+            [
+                (Op::Equal, "a  "),
+                (Op::Delete, "bc"),
+                (Op::Insert, "xy"),
+                (Op::Equal, " d"),
+                (Op::Insert, " e"),
+            ]
+        );
+
+        let runs = diff_runs(
+            &tokenize("kitten", DiffMode::Char),
+            &tokenize("sitting", DiffMode::Char),
+        );
+        let text: Vec<_> = runs.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(text, ["k", "s", "itt", "e", "i", "n", "g"]);
+    }
+
+    #[test]
+    fn test_large_diff_falls_back_to_replace() {
+        let old = format!("<{}>", "a".repeat(1100));
+        let new = format!("<{}>", "ab".repeat(500));
+        let runs = diff_runs(
+            &tokenize(&old, DiffMode::Char),
+            &tokenize(&new, DiffMode::Char),
+        );
+        let runs: Vec<_> = runs.iter().map(|(op, t)| (*op, t.len())).collect();
+        assert_eq!(
+            runs,
+            [
+                (Op::Equal, 2),
+                (Op::Delete, 1099),
+                (Op::Insert, 999),
+                (Op::Equal, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_styles() {
+        set_color_choice(ColorChoice::Always);
+        let diff = InlineDiff {
+            mode: DiffMode::Char,
+            ..InlineDiff::new()
+        };
+        assert_eq!(
+            diff.render("ab", "b!"),
+            "\x1b[9;31ma\x1b[0mb\x1b[32m!\x1b[0m"
+        );
+        assert_eq!(diff.render("x", "y"), "\x1b[9;31mx\x1b[0m\x1b[33my\x1b[0m");
+    }
+}
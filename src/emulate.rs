@@ -0,0 +1,353 @@
+//! A minimal terminal emulator for rendering captured output.
+//!
+//! [`TerminalState`] applies parsed output to a fixed-size grid of cells,
+//! handling text, cursor movement, erasing, SGR, and the common control
+//! characters. It answers "what would the user have seen?" for output that
+//! redraws itself, such as progress bars.
+//!
+//! ```rust
+//! use glyphs::emulate::TerminalState;
+//!
+//! let mut term = TerminalState::new(20, 2);
+//! term.feed("downloading 10%\rdownloading 100%\n\x1b[32mdone\x1b[0m");
+//! assert_eq!(term.plain_lines(), vec!["downloading 100%", "done"]);
+//! ```
+
+use crate::charset::CharsetState;
+use crate::control::ControlChar;
+use crate::escape::{Escape, EscapeKind};
+use crate::op::{CursorOp, EraseOp};
+use crate::parser::{parse, ParsedSequence};
+use crate::style::Style;
+use crate::{CSI, RESET, SGR_SUFFIX};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// One character cell of the emulated screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    /// The character shown in the cell.
+    pub ch: char,
+    /// The style the character was printed with.
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            style: Style::new(),
+        }
+    }
+}
+
+/// The state of an emulated terminal screen.
+#[derive(Debug, Clone)]
+pub struct TerminalState {
+    width: usize,
+    height: usize,
+    grid: Vec<Vec<Cell>>,
+    row: usize,
+    col: usize,
+    style: Style,
+    saved_cursor: (usize, usize),
+    charsets: CharsetState,
+    /// Add rows at the bottom instead of scrolling.
+    grow: bool,
+}
+
+/// Most rows a growing screen will add.
+const MAX_GROWN_HEIGHT: usize = 8192;
+
+impl TerminalState {
+    /// Create a blank screen of the given size (at least 1x1).
+    #[must_use]
+    pub fn new(width: u16, height: u16) -> Self {
+        let width = usize::from(width.max(1));
+        let height = usize::from(height.max(1));
+        Self {
+            width,
+            height,
+            grid: vec![vec![Cell::default(); width]; height],
+            row: 0,
+            col: 0,
+            style: Style::new(),
+            saved_cursor: (0, 0),
+            charsets: CharsetState::new(),
+            grow: false,
+        }
+    }
+
+    /// A screen `width` columns wide that starts with one row and gains
+    /// rows as output moves below the bottom, so nothing scrolls away.
+    #[cfg(feature = "art")]
+    pub(crate) fn growing(width: u16) -> Self {
+        Self {
+            grow: true,
+            ..Self::new(width, 1)
+        }
+    }
+
+    /// Screen width in columns.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Screen height in rows.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cursor position as zero-based `(row, col)`.
+    #[must_use]
+    pub const fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// The style that will be used for the next printed character.
+    #[must_use]
+    pub const fn current_style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the cell at zero-based `(row, col)`.
+    #[must_use]
+    pub fn cell(&self, row: usize, col: usize) -> Option<&Cell> {
+        self.grid.get(row)?.get(col)
+    }
+
+    /// Parse `input` and apply it to the screen.
+    pub fn feed(&mut self, input: &str) {
+        for segment in parse(input) {
+            self.apply(&segment);
+        }
+    }
+
+    /// Apply a single parsed segment to the screen.
+    pub fn apply(&mut self, segment: &ParsedSequence) {
+        match segment {
+            ParsedSequence::Text(text) => text.chars().for_each(|c| self.print(c)),
+            ParsedSequence::Control(c) => self.control(*c),
+            ParsedSequence::Escape(e) => self.escape(e),
+            ParsedSequence::Malformed { .. } => {}
+        }
+    }
+
+    /// The screen contents as plain text, one string per row, with trailing
+    /// spaces removed.
+    #[must_use]
+    pub fn plain_lines(&self) -> Vec<String> {
+        self.grid
+            .iter()
+            .map(|row| {
+                let line: String = row.iter().map(|c| c.ch).collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// The screen contents as styled lines, with trailing unstyled blanks removed.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        self.grid.iter().map(|row| render_row(row)).collect()
+    }
+
+    fn print(&mut self, c: char) {
+        let c = self.charsets.translate(c);
+        if self.col >= self.width {
+            self.col = 0;
+            self.line_feed();
+        }
+        self.grid[self.row][self.col] = Cell {
+            ch: c,
+            style: self.style,
+        };
+        self.col += 1;
+    }
+
+    fn line_feed(&mut self) {
+        if self.row + 1 < self.height || (self.grow && self.height < MAX_GROWN_HEIGHT) {
+            self.row = self.clamp_row(self.row + 1);
+        } else {
+            self.grid.remove(0);
+            self.grid.push(vec![Cell::default(); self.width]);
+        }
+    }
+
+    fn control(&mut self, c: ControlChar) {
+        if self.charsets.apply_control(c) {
+            return;
+        }
+        match c {
+            // Captured output has usually passed through a tty with `onlcr`
+            // set, so a line feed also returns to the first column.
+            ControlChar::LineFeed | ControlChar::VerticalTab | ControlChar::FormFeed => {
+                self.col = 0;
+                self.line_feed();
+            }
+            ControlChar::CarriageReturn => self.col = 0,
+            ControlChar::Backspace => self.col = self.col.min(self.width - 1).saturating_sub(1),
+            ControlChar::Tab => self.col = ((self.col / 8 + 1) * 8).min(self.width - 1),
+            _ => {}
+        }
+    }
+
+    fn escape(&mut self, e: &Escape) {
+        if self.charsets.apply_escape(e) {
+            return;
+        }
+        if e.kind == EscapeKind::Sgr {
+            self.style.apply_sgr(&e.params);
+        } else if let Some(op) = e.cursor_op() {
+            self.move_cursor(op);
+        } else if let Some(op) = e.erase_op() {
+            match op {
+                EraseOp::ToScreenEnd => self.erase_screen(0),
+                EraseOp::ToScreenStart => self.erase_screen(1),
+                EraseOp::Screen | EraseOp::ScreenAndScrollback => self.erase_screen(2),
+                EraseOp::ToLineEnd => self.erase_line(0),
+                EraseOp::ToLineStart => self.erase_line(1),
+                EraseOp::Line => self.erase_line(2),
+            }
+        }
+    }
+
+    fn move_cursor(&mut self, op: CursorOp) {
+        let n = |p: u32| usize::try_from(p).unwrap_or(usize::MAX);
+        match op {
+            CursorOp::Up(rows) => self.row = self.row.saturating_sub(n(rows)),
+            CursorOp::Down(rows) => self.row = self.clamp_row(self.row.saturating_add(n(rows))),
+            CursorOp::Forward(cols) => {
+                self.col = self.col.saturating_add(n(cols)).min(self.width - 1);
+            }
+            CursorOp::Back(cols) => self.col = self.col.min(self.width - 1).saturating_sub(n(cols)),
+            CursorOp::Column(col) => self.col = (n(col) - 1).min(self.width - 1),
+            CursorOp::Goto(row, col) => {
+                self.row = self.clamp_row(n(row) - 1);
+                self.col = (n(col) - 1).min(self.width - 1);
+            }
+            CursorOp::Save => self.saved_cursor = (self.row, self.col),
+            CursorOp::Restore => self.restore_cursor(),
+        }
+    }
+
+    /// Limit `row` to the screen, first adding rows to a growing screen.
+    fn clamp_row(&mut self, row: usize) -> usize {
+        if self.grow {
+            let height = (row + 1).min(MAX_GROWN_HEIGHT);
+            self.grid
+                .resize(height.max(self.height), vec![Cell::default(); self.width]);
+            self.height = self.grid.len();
+        }
+        row.min(self.height - 1)
+    }
+
+    fn restore_cursor(&mut self) {
+        (self.row, self.col) = self.saved_cursor;
+    }
+
+    fn blank(&self) -> Cell {
+        // Erased cells keep the current background, like a real terminal.
+        Cell {
+            ch: ' ',
+            style: Style {
+                background: self.style.background,
+                ..Style::new()
+            },
+        }
+    }
+
+    fn erase_line(&mut self, mode: u32) {
+        let blank = self.blank();
+        let col = self.col.min(self.width);
+        let row = &mut self.grid[self.row];
+        let range = match mode {
+            0 => col..row.len(),
+            1 => 0..(col + 1).min(row.len()),
+            _ => 0..row.len(),
+        };
+        row[range].fill(blank);
+    }
+
+    fn erase_screen(&mut self, mode: u32) {
+        let blank = self.blank();
+        let rows = match mode {
+            0 => {
+                self.erase_line(0);
+                self.row + 1..self.height
+            }
+            1 => {
+                self.erase_line(1);
+                0..self.row
+            }
+            _ => 0..self.height,
+        };
+        for row in &mut self.grid[rows] {
+            row.fill(blank);
+        }
+    }
+}
+
+fn render_row(row: &[Cell]) -> String {
+    let is_plain = |c: &Cell| c.ch == ' ' && c.style.is_plain();
+    let end = row.iter().rposition(|c| !is_plain(c)).map_or(0, |i| i + 1);
+
+    let mut out = String::new();
+    let mut current = Style::new();
+    for cell in &row[..end] {
+        if cell.style != current {
+            if cell.style.is_plain() {
+                out.push_str(RESET);
+            } else {
+                out.push_str(CSI);
+                out.push_str("0;");
+                let _ = cell.style.write_codes(&mut out);
+                out.push_str(SGR_SUFFIX);
+            }
+            current = cell.style;
+        }
+        out.push(cell.ch);
+    }
+    if !current.is_plain() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    #[test]
+    fn test_carriage_return_redraw() {
+        let mut term = TerminalState::new(10, 3);
+        term.feed("50%\r100%\nnext\x1b[1;1Hx");
+        assert_eq!(term.plain_lines(), vec!["x00%", "next", ""]);
+    }
+
+    #[test]
+    fn test_erase_and_scroll() {
+        let mut term = TerminalState::new(5, 2);
+        term.feed("aaaaa\nbbbbb\ncc\x1b[1D\x1b[K");
+        assert_eq!(term.plain_lines(), vec!["bbbbb", "c"]);
+    }
+
+    #[test]
+    fn test_line_drawing() {
+        let mut term = TerminalState::new(6, 2);
+        term.feed("\x1b(0lqqk\r\nmqqj\x1b(B");
+        assert_eq!(term.plain_lines(), vec!["┌──┐", "└──┘"]);
+    }
+
+    #[test]
+    fn test_styled_lines() {
+        let mut term = TerminalState::new(10, 1);
+        term.feed("\x1b[31mred\x1b[0m ok");
+        assert_eq!(term.lines()[0], "\x1b[0;31mred\x1b[0m ok");
+        assert_eq!(term.cell(0, 0).unwrap().style.foreground, Some(Color::Red));
+    }
+}
@@ -0,0 +1,71 @@
+//! Crate-wide error type.
+
+use crate::parser::ParseError;
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned by the fallible APIs in this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A color string could not be parsed.
+    ColorParse {
+        /// The rejected input.
+        input: String,
+    },
+    /// An escape sequence was truncated or invalid.
+    MalformedSequence(ParseError),
+    /// The terminal cannot display what was asked for.
+    UnsupportedCapability(&'static str),
+    /// A `FIGlet` font file could not be parsed.
+    InvalidFont {
+        /// The 1-based line where parsing failed.
+        line: usize,
+        /// What was wrong with it.
+        reason: &'static str,
+    },
+    /// Reading or writing failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ColorParse { input } => write!(f, "invalid color {input:?}"),
+            Self::MalformedSequence(e) => write!(f, "malformed escape sequence: {e}"),
+            Self::UnsupportedCapability(what) => write!(f, "terminal does not support {what}"),
+            Self::InvalidFont { line, reason } => {
+                write!(f, "invalid FIGlet font at line {line}: {reason}")
+            }
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MalformedSequence(e) => Some(e),
+            Self::Io(e) => Some(e),
+            Self::ColorParse { .. } | Self::UnsupportedCapability(_) | Self::InvalidFont { .. } => {
+                None
+            }
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::MalformedSequence(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
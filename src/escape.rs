@@ -1,9 +1,22 @@
 //! Escape sequence types.
 
-use std::fmt;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use smallvec::SmallVec;
+
+/// Numeric parameters of an escape sequence.
+///
+/// Stored inline for up to eight parameters, which covers every SGR
+/// sequence short of several chained RGB colors.
+pub type Params = SmallVec<[u32; 8]>;
 
 /// Kind of escape sequence.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EscapeKind {
     /// SGR (Select Graphic Rendition) - colors and text styling.
     Sgr,
@@ -15,6 +28,12 @@ pub enum EscapeKind {
     Mode,
     /// OSC (Operating System Command).
     Osc,
+    /// Character set designation (e.g., DEC line drawing).
+    Charset,
+    /// Device status and attribute requests and reports.
+    Device,
+    /// Rectangular area operations (DECCRA, DECERA, DECFRA, DECCARA).
+    Rectangle,
     /// Unknown/other escape.
     Unknown,
 }
@@ -27,12 +46,122 @@ impl fmt::Display for EscapeKind {
             Self::Erase => write!(f, "erase"),
             Self::Mode => write!(f, "mode"),
             Self::Osc => write!(f, "OSC"),
+            Self::Charset => write!(f, "charset"),
+            Self::Device => write!(f, "device"),
+            Self::Rectangle => write!(f, "rectangle"),
             Self::Unknown => write!(f, "unknown"),
         }
     }
 }
 
+/// What an escape sequence does, in more detail than its [`EscapeKind`].
+///
+/// Returned by [`Escape::detail`] so filters can match on the operation
+/// instead of the description text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EscapeDetail {
+    /// Cursor movement (`CSI A` to `CSI D`, `CSI G`, `CSI H`).
+    CursorMove,
+    /// Cursor save (`ESC 7`, `CSI s`).
+    CursorSave,
+    /// Cursor restore (`ESC 8`, `CSI u`).
+    CursorRestore,
+    /// Cursor shape (`CSI Ps SP q`).
+    CursorStyle,
+    /// Clearing part or all of the line (`CSI K`).
+    EraseLine,
+    /// Clearing part or all of the screen (`CSI J`).
+    EraseScreen,
+    /// Enabling a mode (`CSI h`, `CSI ? h`).
+    ModeSet,
+    /// Disabling a mode (`CSI l`, `CSI ? l`).
+    ModeReset,
+    /// Setting or resetting the scroll region (`CSI r`).
+    ScrollRegion,
+    /// Application keypad mode (`ESC =`).
+    KeypadApplication,
+    /// Numeric keypad mode (`ESC >`).
+    KeypadNumeric,
+    /// Double-height, double-width, or single-width line (`ESC # 3` to
+    /// `ESC # 6`).
+    LineSize,
+    /// Full (`ESC c`) or soft (`CSI ! p`) terminal reset.
+    TerminalReset,
+    /// Device status or cursor position request (`CSI n`).
+    DeviceStatus,
+    /// Device attributes request or report (`CSI c`).
+    DeviceAttributes,
+    /// Cursor position report sent by the terminal (`CSI row ; col R`).
+    CursorPositionReport,
+    /// Focus in (`CSI I`) or out (`CSI O`) event sent by the terminal.
+    FocusEvent,
+    /// Copying a rectangular area (`CSI ... $ v`).
+    CopyRectangle,
+    /// Erasing a rectangular area (`CSI ... $ z`).
+    EraseRectangle,
+    /// Filling a rectangular area with a character (`CSI ... $ x`).
+    FillRectangle,
+    /// Changing the attributes in a rectangular area (`CSI ... $ r`).
+    ChangeRectangleAttributes,
+    /// Anything else; the [`EscapeKind`] is all there is.
+    Other,
+}
+
+/// How much detail [`Escape::human_readable_with`] includes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Verbosity {
+    /// The description alone, for inline annotations (`red fg`).
+    Short,
+    /// The kind and description (`[SGR (style)] red fg`).
+    #[default]
+    Normal,
+    /// The kind and description followed by the raw bytes, the spec
+    /// reference (see [`Escape::reference`]), and the meaning of each
+    /// parameter, one per line.
+    Verbose,
+}
+
+/// Options for [`Escape::human_readable_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DescribeOptions {
+    /// How much detail to include.
+    pub verbosity: Verbosity,
+}
+
+impl DescribeOptions {
+    /// Options for [`Verbosity::Normal`] descriptions.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            verbosity: Verbosity::Normal,
+        }
+    }
+
+    /// Set the verbosity.
+    #[must_use]
+    pub const fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+}
+
+/// How [`Escape::display`] shows a sequence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DisplayMode {
+    /// The kind and description, as in [`Escape::human_readable`].
+    #[default]
+    Description,
+    /// The raw sequence in caret notation, as in [`Escape::display_raw`].
+    Caret,
+    /// A hex dump of the raw bytes, as in [`Escape::display_hex`].
+    Hex,
+}
+
 /// A parsed escape sequence.
+///
+/// Escapes compare by meaning: SGR sequences that set the same attributes
+/// are equal regardless of parameter order (`\x1b[1;31m` == `\x1b[31;1m`),
+/// and other sequences compare by their raw text.
 #[derive(Debug, Clone)]
 pub struct Escape {
     /// The raw sequence string.
@@ -42,7 +171,9 @@ pub struct Escape {
     /// Human-readable description.
     pub description: String,
     /// Parameters (if any).
-    pub params: Vec<u16>,
+    pub params: Params,
+    /// Parameters that could not be read as numbers, kept verbatim.
+    pub unparsed: Vec<String>,
 }
 
 impl Escape {
@@ -53,14 +184,15 @@ impl Escape {
             raw,
             kind,
             description,
-            params: Vec::new(),
+            params: Params::new(),
+            unparsed: Vec::new(),
         }
     }
 
     /// Create with parameters.
     #[must_use]
-    pub fn with_params(mut self, params: Vec<u16>) -> Self {
-        self.params = params;
+    pub fn with_params(mut self, params: impl Into<Params>) -> Self {
+        self.params = params.into();
         self
     }
 
@@ -69,6 +201,210 @@ impl Escape {
     pub fn human_readable(&self) -> String {
         format!("[{}] {}", self.kind, self.description)
     }
+
+    /// Get a human-readable representation with the given level of detail.
+    ///
+    /// ```rust
+    /// use glyphs::{parse, DescribeOptions, Verbosity};
+    ///
+    /// let escape = parse("\x1b[1;31m")[0].as_escape().cloned().unwrap();
+    /// let short = DescribeOptions::new().verbosity(Verbosity::Short);
+    /// assert_eq!(escape.human_readable_with(short), "bold, red fg");
+    ///
+    /// let verbose = DescribeOptions::new().verbosity(Verbosity::Verbose);
+    /// assert_eq!(
+    ///     escape.human_readable_with(verbose),
+    ///     "[SGR (style)] bold, red fg\n  \
+    ///      raw: ^[[1;31m (1b 5b 31 3b 33 31 6d)\n  \
+    ///      spec: SGR (Select Graphic Rendition), ECMA-48\n  \
+    ///      params: 1 (bold), 31 (red fg)"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn human_readable_with(&self, options: DescribeOptions) -> String {
+        match options.verbosity {
+            Verbosity::Short => self.description.clone(),
+            Verbosity::Normal => self.human_readable(),
+            Verbosity::Verbose => {
+                let mut out = format!(
+                    "{}\n  raw: {} ({})",
+                    self.human_readable(),
+                    self.display_raw(),
+                    self.display_hex()
+                );
+                if let Some(info) = self.reference() {
+                    out.push_str("\n  spec: ");
+                    out.push_str(info.mnemonic);
+                    out.push_str(" (");
+                    out.push_str(info.name);
+                    out.push_str("), ");
+                    out.push_str(info.standard);
+                }
+                if !self.params.is_empty() {
+                    out.push_str("\n  params: ");
+                    out.push_str(&self.param_meanings());
+                }
+                out
+            }
+        }
+    }
+
+    /// The parameters, with what each SGR parameter group means.
+    fn param_meanings(&self) -> String {
+        if self.kind != EscapeKind::Sgr {
+            let params: Vec<String> = self.params.iter().map(ToString::to_string).collect();
+            return params.join(", ");
+        }
+        let mut groups = Vec::new();
+        let mut rest = &self.params[..];
+        while !rest.is_empty() {
+            let len = match rest {
+                [38 | 48 | 58, 5, ..] => 3,
+                [38 | 48 | 58, 2, ..] => 5,
+                _ => 1,
+            };
+            let (group, tail) = rest.split_at(len.min(rest.len()));
+            let codes: Vec<String> = group.iter().map(ToString::to_string).collect();
+            groups.push(format!("{} ({})", codes.join(";"), describe_sgr(group)));
+            rest = tail;
+        }
+        groups.join(", ")
+    }
+
+    /// What this sequence does, in more detail than [`Escape::kind`].
+    ///
+    /// ```rust
+    /// use glyphs::{parse, EscapeDetail};
+    ///
+    /// let detail = |s: &str| parse(s)[0].as_escape().unwrap().detail();
+    /// assert_eq!(detail("\x1b[2K"), EscapeDetail::EraseLine);
+    /// assert_eq!(detail("\x1b[?25l"), EscapeDetail::ModeReset);
+    /// assert_eq!(detail("\x1b7"), EscapeDetail::CursorSave);
+    /// ```
+    #[must_use]
+    pub fn detail(&self) -> EscapeDetail {
+        let Some(body) = self
+            .raw
+            .strip_prefix("\x1b[")
+            .or_else(|| self.raw.strip_prefix('\u{9b}'))
+        else {
+            return match self.raw.as_str() {
+                "\x1b7" => EscapeDetail::CursorSave,
+                "\x1b8" => EscapeDetail::CursorRestore,
+                "\x1b=" => EscapeDetail::KeypadApplication,
+                "\x1b>" => EscapeDetail::KeypadNumeric,
+                "\x1b#3" | "\x1b#4" | "\x1b#5" | "\x1b#6" => EscapeDetail::LineSize,
+                "\x1bc" => EscapeDetail::TerminalReset,
+                _ => EscapeDetail::Other,
+            };
+        };
+
+        let final_char = body.chars().last().unwrap_or_default();
+        match (&self.kind, final_char) {
+            (EscapeKind::Mode, 'p') if body == "!p" => EscapeDetail::TerminalReset,
+            (EscapeKind::Cursor, 's') => EscapeDetail::CursorSave,
+            (EscapeKind::Cursor, 'u') => EscapeDetail::CursorRestore,
+            (EscapeKind::Cursor, 'q') => EscapeDetail::CursorStyle,
+            (EscapeKind::Cursor, _) => EscapeDetail::CursorMove,
+            (EscapeKind::Erase, 'K') => EscapeDetail::EraseLine,
+            (EscapeKind::Erase, 'J') => EscapeDetail::EraseScreen,
+            (EscapeKind::Mode, 'h') => EscapeDetail::ModeSet,
+            (EscapeKind::Mode, 'l') => EscapeDetail::ModeReset,
+            (EscapeKind::Mode, 'r') => EscapeDetail::ScrollRegion,
+            (EscapeKind::Device, 'n') => EscapeDetail::DeviceStatus,
+            (EscapeKind::Device, 'c') => EscapeDetail::DeviceAttributes,
+            (EscapeKind::Device, 'R') => EscapeDetail::CursorPositionReport,
+            (EscapeKind::Device, 'I' | 'O') => EscapeDetail::FocusEvent,
+            (EscapeKind::Rectangle, 'v') => EscapeDetail::CopyRectangle,
+            (EscapeKind::Rectangle, 'z') => EscapeDetail::EraseRectangle,
+            (EscapeKind::Rectangle, 'x') => EscapeDetail::FillRectangle,
+            (EscapeKind::Rectangle, 'r') => EscapeDetail::ChangeRectangleAttributes,
+            _ => EscapeDetail::Other,
+        }
+    }
+
+    /// The raw sequence with control characters in caret notation, like
+    /// `cat -v`: ESC is `^[`, DEL is `^?`, and C1 controls get an `M-`
+    /// prefix (CSI is `M-^[`).
+    ///
+    /// ```rust
+    /// use glyphs::parse;
+    ///
+    /// let escape = parse("\x1b[31m")[0].as_escape().cloned().unwrap();
+    /// assert_eq!(escape.display_raw(), "^[[31m");
+    /// ```
+    #[must_use]
+    pub fn display_raw(&self) -> String {
+        let mut out = String::with_capacity(self.raw.len() + 2);
+        for c in self.raw.chars() {
+            match u32::from(c) {
+                code @ (0x00..=0x1f | 0x7f) => {
+                    out.push('^');
+                    out.push(char::from_u32(code ^ 0x40).unwrap_or('?'));
+                }
+                code @ 0x80..=0x9f => {
+                    out.push_str("M-^");
+                    out.push(char::from_u32((code - 0x80) ^ 0x40).unwrap_or('?'));
+                }
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// The raw bytes as space-separated lowercase hex.
+    ///
+    /// ```rust
+    /// use glyphs::parse;
+    ///
+    /// let escape = parse("\x1b[31m")[0].as_escape().cloned().unwrap();
+    /// assert_eq!(escape.display_hex(), "1b 5b 33 31 6d");
+    /// ```
+    #[must_use]
+    pub fn display_hex(&self) -> String {
+        let hex: Vec<String> = self.raw.bytes().map(|b| format!("{b:02x}")).collect();
+        hex.join(" ")
+    }
+
+    /// Show the sequence in the given mode.
+    #[must_use]
+    pub fn display(&self, mode: DisplayMode) -> String {
+        match mode {
+            DisplayMode::Description => self.human_readable(),
+            DisplayMode::Caret => self.display_raw(),
+            DisplayMode::Hex => self.display_hex(),
+        }
+    }
+}
+
+impl Escape {
+    fn canonical(&self) -> Cow<'_, str> {
+        if self.kind == EscapeKind::Sgr {
+            let mut canonical = normalize_sgr(&self.params);
+            for param in &self.unparsed {
+                canonical.push(';');
+                canonical.push_str(param);
+            }
+            Cow::Owned(canonical)
+        } else {
+            Cow::Borrowed(&self.raw)
+        }
+    }
+}
+
+impl PartialEq for Escape {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for Escape {}
+
+impl Hash for Escape {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.canonical().hash(state);
+    }
 }
 
 impl fmt::Display for Escape {
@@ -77,10 +413,106 @@ impl fmt::Display for Escape {
     }
 }
 
+/// Ordering key for one SGR parameter group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Slot {
+    Code(u32),
+    Fg,
+    Bg,
+}
+
+/// On-codes cancelled by an off-code.
+fn cancels(off: u32) -> &'static [u32] {
+    match off {
+        22 => &[1, 2],
+        23 => &[3, 20],
+        24 => &[4, 21],
+        25 => &[5, 6],
+        27 => &[7],
+        28 => &[8],
+        29 => &[9],
+        54 => &[51, 52],
+        55 => &[53],
+        75 => &[73, 74],
+        _ => &[],
+    }
+}
+
+/// Canonical form of an SGR sequence; see [`crate::testing::normalize_ansi`].
+pub(crate) fn normalize_sgr(params: &[u32]) -> String {
+    let mut reset = params.is_empty();
+    let mut slots: BTreeMap<Slot, String> = BTreeMap::new();
+    let mut i = 0;
+
+    while i < params.len() {
+        let code = params[i];
+        let len = match (code, params.get(i + 1)) {
+            (38 | 48, Some(5)) => 3,
+            (38 | 48, Some(2)) => 5,
+            _ => 1,
+        }
+        .min(params.len() - i);
+        let group = params[i..i + len]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(";");
+        i += len;
+
+        match code {
+            0 => {
+                reset = true;
+                slots.clear();
+            }
+            30..=39 | 90..=97 => {
+                slots.insert(Slot::Fg, group);
+            }
+            40..=49 | 100..=107 => {
+                slots.insert(Slot::Bg, group);
+            }
+            _ => {
+                for on in cancels(code) {
+                    slots.remove(&Slot::Code(*on));
+                }
+                for off in [22, 23, 24, 25, 27, 28, 29, 54, 55, 75] {
+                    if cancels(off).contains(&code) {
+                        slots.remove(&Slot::Code(off));
+                    }
+                }
+                // Superscript and subscript replace each other.
+                match code {
+                    73 => slots.remove(&Slot::Code(74)),
+                    74 => slots.remove(&Slot::Code(73)),
+                    _ => None,
+                };
+                slots.insert(Slot::Code(code), group);
+            }
+        }
+    }
+
+    let mut parts: Vec<String> = Vec::with_capacity(slots.len() + 1);
+    if reset {
+        parts.push("0".to_string());
+    }
+    parts.extend(slots.into_values());
+    format!("\x1b[{}m", parts.join(";"))
+}
+
 /// Parse SGR (style) parameters into a description.
+///
+/// Each code is described in the order it appears, separated by `, `,
+/// with an extended color and its arguments counting as one code, so the
+/// output is stable for a given sequence. An empty list or a lone `0` is
+/// `reset`.
+///
+/// ```rust
+/// use glyphs::describe_sgr;
+///
+/// assert_eq!(describe_sgr(&[1, 38, 5, 208, 4]), "bold, fg: color 208, underline");
+/// ```
 #[must_use]
 #[allow(clippy::too_many_lines)]
-pub fn describe_sgr(params: &[u16]) -> String {
+pub fn describe_sgr(params: &[u32]) -> String {
     if params.is_empty() || params == [0] {
         return "reset".to_string();
     }
@@ -98,11 +530,21 @@ pub fn describe_sgr(params: &[u16]) -> String {
             5 => "blink",
             7 => "reverse",
             8 => "hidden",
+            6 => "rapid blink",
             9 => "strikethrough",
+            10 => "primary font",
+            11..=19 => {
+                descriptions.push(format!("alternative font {}", params[i] - 10));
+                i += 1;
+                continue;
+            }
+            20 => "fraktur",
+            21 => "double underline",
             22 => "normal intensity",
-            23 => "not italic",
+            23 => "not italic or fraktur",
             24 => "not underlined",
             25 => "not blinking",
+            26 => "proportional spacing",
             27 => "not reversed",
             28 => "not hidden",
             29 => "not strikethrough",
@@ -114,25 +556,6 @@ pub fn describe_sgr(params: &[u16]) -> String {
             35 => "magenta fg",
             36 => "cyan fg",
             37 => "white fg",
-            38 => {
-                // Extended foreground color
-                if params.len() > i + 2 && params[i + 1] == 5 {
-                    let code = params[i + 2];
-                    i += 2;
-                    descriptions.push(format!("fg: color {code}"));
-                    i += 1;
-                    continue;
-                } else if params.len() > i + 4 && params[i + 1] == 2 {
-                    let r = params[i + 2];
-                    let g = params[i + 3];
-                    let b = params[i + 4];
-                    i += 4;
-                    descriptions.push(format!("fg: rgb({r}, {g}, {b})"));
-                    i += 1;
-                    continue;
-                }
-                "extended fg"
-            }
             39 => "default fg",
             40 => "black bg",
             41 => "red bg",
@@ -142,37 +565,72 @@ pub fn describe_sgr(params: &[u16]) -> String {
             45 => "magenta bg",
             46 => "cyan bg",
             47 => "white bg",
-            48 => {
-                // Extended background color
+            38 | 48 | 58 => {
+                // Extended foreground, background, or underline color
+                let target = match params[i] {
+                    38 => "fg",
+                    48 => "bg",
+                    _ => "underline color",
+                };
                 if params.len() > i + 2 && params[i + 1] == 5 {
                     let code = params[i + 2];
-                    i += 2;
-                    descriptions.push(format!("bg: color {code}"));
-                    i += 1;
+                    descriptions.push(format!("{target}: color {code}"));
+                    i += 3;
                     continue;
                 } else if params.len() > i + 4 && params[i + 1] == 2 {
-                    let r = params[i + 2];
-                    let g = params[i + 3];
-                    let b = params[i + 4];
-                    i += 4;
-                    descriptions.push(format!("bg: rgb({r}, {g}, {b})"));
-                    i += 1;
+                    let (r, g, b) = (params[i + 2], params[i + 3], params[i + 4]);
+                    descriptions.push(format!("{target}: rgb({r}, {g}, {b})"));
+                    i += 5;
                     continue;
                 }
-                "extended bg"
+                descriptions.push(format!("extended {target}"));
+                i += 1;
+                continue;
             }
             49 => "default bg",
+            50 => "no proportional spacing",
+            51 => "framed",
+            52 => "encircled",
+            53 => "overline",
+            54 => "not framed or encircled",
+            55 => "not overlined",
+            59 => "default underline color",
+            60 => "ideogram underline",
+            61 => "ideogram double underline",
+            62 => "ideogram overline",
+            63 => "ideogram double overline",
+            64 => "ideogram stress marking",
+            65 => "no ideogram attributes",
+            73 => "superscript",
+            74 => "subscript",
+            75 => "not superscript or subscript",
             90..=97 => {
-                let colors = ["bright black", "bright red", "bright green", "bright yellow",
-                              "bright blue", "bright magenta", "bright cyan", "bright white"];
+                let colors = [
+                    "bright black",
+                    "bright red",
+                    "bright green",
+                    "bright yellow",
+                    "bright blue",
+                    "bright magenta",
+                    "bright cyan",
+                    "bright white",
+                ];
                 let color = colors[(params[i] - 90) as usize];
                 descriptions.push(format!("{color} fg"));
                 i += 1;
                 continue;
             }
             100..=107 => {
-                let colors = ["bright black", "bright red", "bright green", "bright yellow",
-                              "bright blue", "bright magenta", "bright cyan", "bright white"];
+                let colors = [
+                    "bright black",
+                    "bright red",
+                    "bright green",
+                    "bright yellow",
+                    "bright blue",
+                    "bright magenta",
+                    "bright cyan",
+                    "bright white",
+                ];
                 let color = colors[(params[i] - 100) as usize];
                 descriptions.push(format!("{color} bg"));
                 i += 1;
@@ -202,5 +660,99 @@ mod tests {
         assert_eq!(describe_sgr(&[31]), "red fg");
         assert_eq!(describe_sgr(&[1, 31]), "bold, red fg");
         assert_eq!(describe_sgr(&[38, 2, 255, 128, 0]), "fg: rgb(255, 128, 0)");
+        assert_eq!(
+            describe_sgr(&[12, 20, 51, 73]),
+            "alternative font 2, fraktur, framed, superscript"
+        );
+        assert_eq!(
+            describe_sgr(&[58, 5, 9, 59, 64]),
+            "underline color: color 9, default underline color, ideogram stress marking"
+        );
+    }
+
+    #[test]
+    fn test_semantic_equality() {
+        use crate::parser::parse;
+
+        let escapes: Vec<Escape> = parse("\x1b[1;31m\x1b[31;1m\x1b[m\x1b[0m\x1b[2J")
+            .into_iter()
+            .filter_map(|s| s.as_escape().cloned())
+            .collect();
+        assert_eq!(escapes[0], escapes[1]);
+        assert_eq!(escapes[2], escapes[3]);
+        assert_ne!(escapes[0], escapes[2]);
+        assert_ne!(escapes[3], escapes[4]);
+    }
+
+    #[test]
+    fn test_escape_detail() {
+        use crate::parser::parse;
+
+        let details: Vec<EscapeDetail> = parse("\x1b[5A\x1b[s\u{9b}u\x1b[2 q\x1b[J\x1b[?1049h\x1b[4l\x1b[1;5r\x1b>\x1b#6\x1bc\x1b[!p\x1b[6n\x1b[>c\x1b[3;7R\x1b[O\x1b[1;1;2;2;1;5;5;1$v\x1b[$z\x1b[46$x\x1b[;;;;1$r\x1b[31m")
+            .iter()
+            .filter_map(|s| s.as_escape().map(Escape::detail))
+            .collect();
+        assert_eq!(
+            details,
+            [
+                EscapeDetail::CursorMove,
+                EscapeDetail::CursorSave,
+                EscapeDetail::CursorRestore,
+                EscapeDetail::CursorStyle,
+                EscapeDetail::EraseScreen,
+                EscapeDetail::ModeSet,
+                EscapeDetail::ModeReset,
+                EscapeDetail::ScrollRegion,
+                EscapeDetail::KeypadNumeric,
+                EscapeDetail::LineSize,
+                EscapeDetail::TerminalReset,
+                EscapeDetail::TerminalReset,
+                EscapeDetail::DeviceStatus,
+                EscapeDetail::DeviceAttributes,
+                EscapeDetail::CursorPositionReport,
+                EscapeDetail::FocusEvent,
+                EscapeDetail::CopyRectangle,
+                EscapeDetail::EraseRectangle,
+                EscapeDetail::FillRectangle,
+                EscapeDetail::ChangeRectangleAttributes,
+                EscapeDetail::Other,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verbosity() {
+        let escape = Escape::new(
+            "\x1b[5;10H".to_string(),
+            EscapeKind::Cursor,
+            "cursor to (5, 10)".to_string(),
+        )
+        .with_params(&[5, 10][..]);
+        let with =
+            |verbosity| escape.human_readable_with(DescribeOptions::new().verbosity(verbosity));
+        assert_eq!(with(Verbosity::Short), "cursor to (5, 10)");
+        assert_eq!(with(Verbosity::Normal), escape.human_readable());
+        assert!(with(Verbosity::Verbose).ends_with("\n  params: 5, 10"));
+
+        let sgr = crate::parser::parse("\x1b[38;5;208;4m")[0]
+            .as_escape()
+            .cloned()
+            .unwrap();
+        let verbose = sgr.human_readable_with(DescribeOptions::new().verbosity(Verbosity::Verbose));
+        assert!(verbose.ends_with("params: 38;5;208 (fg: color 208), 4 (underline)"));
+    }
+
+    #[test]
+    fn test_display_modes() {
+        let escape = Escape::new(
+            "\u{9b}2J\x07\x7f".to_string(),
+            EscapeKind::Erase,
+            "clear".to_string(),
+        );
+        assert_eq!(escape.display_raw(), "M-^[2J^G^?");
+        assert_eq!(escape.display_hex(), "c2 9b 32 4a 07 7f");
+        assert_eq!(escape.display(DisplayMode::Description), "[erase] clear");
+        assert_eq!(escape.display(DisplayMode::Caret), escape.display_raw());
+        assert_eq!(escape.display(DisplayMode::Hex), escape.display_hex());
     }
 }
@@ -0,0 +1,513 @@
+//! Writers that rewrite escape sequences on the fly.
+
+use crate::caps::ColorLevel;
+use crate::color::Color;
+use crate::control::ControlChar;
+use crate::escape::EscapeKind;
+use crate::parser::{ParsedSequence, StreamParser};
+use crate::tracker::StyleTracker;
+use crate::{CSI, RESET, SGR_SUFFIX};
+use std::io::{self, Write};
+
+/// Removes every escape sequence from what is written through it.
+///
+/// Text and control characters are passed on; sequences split across
+/// writes are recognized, so nothing is collected in memory beyond an
+/// incomplete sequence.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::StripWriter;
+/// use std::io::Write;
+///
+/// let mut out = StripWriter::new(Vec::new());
+/// out.write_all(b"\x1b[3")?;
+/// out.write_all(b"1mred\x1b[0m\n")?;
+/// assert_eq!(out.finish()?, b"red\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StripWriter<W: Write> {
+    inner: W,
+    parser: StreamParser,
+}
+
+impl<W: Write> StripWriter<W> {
+    /// Wrap `inner`.
+    #[must_use]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            parser: StreamParser::new(),
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write out anything still buffered and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        let segments = self.parser.finish();
+        self.emit(segments)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn emit(&mut self, segments: Vec<ParsedSequence>) -> io::Result<()> {
+        for segment in segments {
+            match segment {
+                ParsedSequence::Text(text) => self.inner.write_all(text.as_bytes())?,
+                ParsedSequence::Control(c) => {
+                    let mut buf = [0; 4];
+                    self.inner
+                        .write_all(c.as_char().encode_utf8(&mut buf).as_bytes())?;
+                }
+                ParsedSequence::Escape(_) | ParsedSequence::Malformed { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for StripWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let segments = self.parser.push_bytes(buf);
+        self.emit(segments)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Rewrites colors in SGR sequences to fit a [`ColorLevel`].
+///
+/// RGB and 256-color codes are replaced by the nearest color the level
+/// supports; at [`ColorLevel::None`] SGR sequences are dropped. Everything
+/// else is passed on unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{ColorLevel, DowngradeWriter};
+/// use std::io::Write;
+///
+/// let mut out = DowngradeWriter::new(Vec::new(), ColorLevel::Ansi16);
+/// out.write_all(b"\x1b[1;38;2;255;0;0mhot\x1b[0m")?;
+/// assert_eq!(out.finish()?, b"\x1b[1;91mhot\x1b[0m");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct DowngradeWriter<W: Write> {
+    inner: W,
+    parser: StreamParser,
+    level: ColorLevel,
+}
+
+impl<W: Write> DowngradeWriter<W> {
+    /// Wrap `inner`, rewriting colors for `level`.
+    #[must_use]
+    pub fn new(inner: W, level: ColorLevel) -> Self {
+        Self {
+            inner,
+            parser: StreamParser::new(),
+            level,
+        }
+    }
+
+    /// Wrap `inner`, rewriting colors for the level detected from the
+    /// environment (see [`ColorLevel::from_env`]).
+    #[must_use]
+    pub fn from_env(inner: W) -> Self {
+        Self::new(inner, ColorLevel::from_env())
+    }
+
+    /// Get a reference to the inner writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write out anything still buffered and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        let segments = self.parser.finish();
+        self.emit(segments)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn emit(&mut self, segments: Vec<ParsedSequence>) -> io::Result<()> {
+        for segment in segments {
+            match segment {
+                ParsedSequence::Text(text) => self.inner.write_all(text.as_bytes())?,
+                ParsedSequence::Control(c) => {
+                    let mut buf = [0; 4];
+                    self.inner
+                        .write_all(c.as_char().encode_utf8(&mut buf).as_bytes())?;
+                }
+                ParsedSequence::Escape(e)
+                    if e.kind == EscapeKind::Sgr
+                        && self.level != ColorLevel::TrueColor
+                        && e.unparsed.is_empty() =>
+                {
+                    if let Some(sgr) = downgrade_sgr(&e.params, self.level) {
+                        self.inner.write_all(sgr.as_bytes())?;
+                    }
+                }
+                ParsedSequence::Escape(e) => self.inner.write_all(e.raw.as_bytes())?,
+                ParsedSequence::Malformed { raw, .. } => self.inner.write_all(raw.as_bytes())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for DowngradeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let segments = self.parser.push_bytes(buf);
+        self.emit(segments)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Passes text and SGR styling through and drops every other escape
+/// sequence.
+///
+/// Meant for output from untrusted programs: colors and text attributes
+/// survive, but cursor movement, screen and mode changes, OSC sequences
+/// (titles, hyperlinks, clipboard access), and DCS, SOS, PM, and APC
+/// strings with their contents are removed, as are malformed sequences.
+/// Of the control characters only tab, line feed, and carriage return
+/// are kept; C1 controls in the text are dropped too.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::SanitizeWriter;
+/// use std::io::Write;
+///
+/// let mut out = SanitizeWriter::new(Vec::new());
+/// out.write_all(b"\x1b]0;pwned\x07\x1b[2J\x1b[31mred\x1b[0m\x1b[H\n")?;
+/// assert_eq!(out.finish()?, b"\x1b[31mred\x1b[0m\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SanitizeWriter<W: Write> {
+    inner: W,
+    parser: StreamParser,
+    /// Inside a DCS, SOS, PM, or APC string, whose contents are dropped
+    /// up to the string terminator.
+    in_string: bool,
+}
+
+impl<W: Write> SanitizeWriter<W> {
+    /// Wrap `inner`.
+    #[must_use]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            parser: StreamParser::new(),
+            in_string: false,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write out anything still buffered and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        let segments = self.parser.finish();
+        self.emit(segments)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn emit(&mut self, segments: Vec<ParsedSequence>) -> io::Result<()> {
+        for segment in segments {
+            if self.in_string {
+                self.in_string = !segment.as_escape().is_some_and(|e| e.raw == "\x1b\\");
+                continue;
+            }
+            match segment {
+                ParsedSequence::Text(text) => {
+                    let text: String = text
+                        .chars()
+                        .filter(|c| !('\u{80}'..='\u{9f}').contains(c))
+                        .collect();
+                    self.inner.write_all(text.as_bytes())?;
+                }
+                ParsedSequence::Control(
+                    c @ (ControlChar::Tab | ControlChar::LineFeed | ControlChar::CarriageReturn),
+                ) => {
+                    let mut buf = [0; 4];
+                    self.inner
+                        .write_all(c.as_char().encode_utf8(&mut buf).as_bytes())?;
+                }
+                ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr => {
+                    self.inner.write_all(e.raw.as_bytes())?;
+                }
+                ParsedSequence::Escape(e) => {
+                    self.in_string =
+                        matches!(e.raw.as_str(), "\x1bP" | "\x1bX" | "\x1b^" | "\x1b_");
+                }
+                ParsedSequence::Control(_) | ParsedSequence::Malformed { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for SanitizeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let segments = self.parser.push_bytes(buf);
+        self.emit(segments)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Closes the active style before each line feed and reopens it after.
+///
+/// Log collectors and CI consoles often store or cut output line by line,
+/// and a background color left on at a line break bleeds into whatever is
+/// shown next to the stored line. Styles are tracked across writes, and
+/// underline colors, which aren't tracked, are not reopened.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::LineResetWriter;
+/// use std::io::Write;
+///
+/// let mut out = LineResetWriter::new(Vec::new());
+/// out.write_all(b"\x1b[44mone\ntwo\x1b[0m\n")?;
+/// assert_eq!(out.finish()?, b"\x1b[44mone\x1b[0m\n\x1b[44mtwo\x1b[0m\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct LineResetWriter<W: Write> {
+    inner: W,
+    parser: StreamParser,
+    tracker: StyleTracker,
+}
+
+impl<W: Write> LineResetWriter<W> {
+    /// Wrap `inner`.
+    #[must_use]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            parser: StreamParser::new(),
+            tracker: StyleTracker::new(),
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write out anything still buffered and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        let segments = self.parser.finish();
+        self.emit(segments)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn emit(&mut self, segments: Vec<ParsedSequence>) -> io::Result<()> {
+        for segment in segments {
+            match segment {
+                ParsedSequence::Text(text) => self.inner.write_all(text.as_bytes())?,
+                ParsedSequence::Control(ControlChar::LineFeed) => {
+                    let style = self.tracker.style();
+                    if style.is_plain() {
+                        self.inner.write_all(b"\n")?;
+                    } else {
+                        let mut reopen = String::from(CSI);
+                        let _ = style.write_codes(&mut reopen);
+                        reopen.push_str(SGR_SUFFIX);
+                        write!(self.inner, "{RESET}\n{reopen}")?;
+                    }
+                }
+                ParsedSequence::Control(c) => {
+                    let mut buf = [0; 4];
+                    self.inner
+                        .write_all(c.as_char().encode_utf8(&mut buf).as_bytes())?;
+                }
+                ParsedSequence::Escape(e) => {
+                    self.tracker.apply(&e);
+                    self.inner.write_all(e.raw.as_bytes())?;
+                }
+                ParsedSequence::Malformed { raw, .. } => self.inner.write_all(raw.as_bytes())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for LineResetWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let segments = self.parser.push_bytes(buf);
+        self.emit(segments)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The SGR sequence for `params` with colors downgraded to `level`, or
+/// `None` if nothing is left of it.
+fn downgrade_sgr(params: &[u32], level: ColorLevel) -> Option<String> {
+    if level == ColorLevel::None {
+        return None;
+    }
+    if params.is_empty() {
+        return Some(String::from("\x1b[m"));
+    }
+
+    let mut codes = Vec::new();
+    let mut i = 0;
+    while i < params.len() {
+        let base = params[i];
+        let (color, len) = match (base, &params[i + 1..]) {
+            (38 | 48 | 58, [5, n, ..]) => (u8::try_from(*n).ok().map(Color::Ansi256), 3),
+            (38 | 48 | 58, [2, r, g, b, ..]) => {
+                let rgb = (u8::try_from(*r), u8::try_from(*g), u8::try_from(*b));
+                match rgb {
+                    (Ok(r), Ok(g), Ok(b)) => (Some(Color::rgb(r, g, b)), 5),
+                    _ => (None, 5),
+                }
+            }
+            _ => {
+                codes.push(base.to_string());
+                i += 1;
+                continue;
+            }
+        };
+        i += len;
+
+        let Some(color) = color.and_then(|c| c.downgrade(level)) else {
+            continue;
+        };
+        // Underline colors have no basic form; use the palette index.
+        let code = if let (58, Some(index)) = (base, color.basic_index()) {
+            format!("58;5;{index}")
+        } else {
+            let mut code = String::new();
+            let _ = color.write_code(&mut code, u8::try_from(base - 8).unwrap_or(30));
+            code
+        };
+        codes.push(code);
+    }
+
+    if codes.is_empty() {
+        return None;
+    }
+    Some(format!("\x1b[{}m", codes.join(";")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downgrade_sgr() {
+        let ansi256 = |params: &[u32]| downgrade_sgr(params, ColorLevel::Ansi256);
+        assert_eq!(
+            ansi256(&[48, 2, 0, 0, 0, 4]).as_deref(),
+            Some("\x1b[48;5;16;4m")
+        );
+        assert_eq!(
+            downgrade_sgr(&[58, 5, 196], ColorLevel::Ansi16).as_deref(),
+            Some("\x1b[58;5;9m")
+        );
+        assert_eq!(
+            downgrade_sgr(&[38, 2, 1], ColorLevel::Ansi16).as_deref(),
+            Some("\x1b[38;2;1m")
+        );
+        assert_eq!(downgrade_sgr(&[1], ColorLevel::None), None);
+    }
+
+    #[test]
+    fn test_writers_pass_text_through() {
+        let mut strip = StripWriter::new(Vec::new());
+        strip
+            .write_all("\x1b]0;t\x07a\tb\u{e9}".as_bytes())
+            .unwrap();
+        assert_eq!(strip.finish().unwrap(), "a\tb\u{e9}".as_bytes());
+
+        let mut none = DowngradeWriter::new(Vec::new(), ColorLevel::None);
+        none.write_all(b"\x1b[31mx\x1b[2Jy").unwrap();
+        assert_eq!(none.finish().unwrap(), b"x\x1b[2Jy");
+    }
+
+    #[test]
+    fn test_sanitize_writer() {
+        let sanitize = |chunks: &[&str]| {
+            let mut out = SanitizeWriter::new(Vec::new());
+            for chunk in chunks {
+                out.write_all(chunk.as_bytes()).unwrap();
+            }
+            String::from_utf8(out.finish().unwrap()).unwrap()
+        };
+        assert_eq!(
+            sanitize(&["\x1b[1;3", "1ma\x1b[5A\x1b[?1049h\tb\x1b[0m\r\n"]),
+            "\x1b[1;31ma\tb\x1b[0m\r\n"
+        );
+        assert_eq!(
+            sanitize(&["x\x1b]8;;https://e.x\x1b\\l\x1b]8;;\x1b\\", "\x07\x08y"]),
+            "xly"
+        );
+        assert_eq!(
+            sanitize(&["a\x1bPq#0;2;0", "\x1b[31m\x1b\\b\x1b_apc\x1b\\c"]),
+            "abc"
+        );
+        assert_eq!(sanitize(&["\u{9b}2J\u{85}\u{e9}\x1b[9"]), "\u{e9}");
+    }
+
+    #[test]
+    fn test_line_reset_writer() {
+        let mut out = LineResetWriter::new(Vec::new());
+        out.write_all(b"\x1b[1;3").unwrap();
+        out.write_all(b"1ma\r\nb\x1b[22m\n\x1b[39mc\n").unwrap();
+        assert_eq!(
+            out.finish().unwrap(),
+            b"\x1b[1;31ma\r\x1b[0m\n\x1b[1;31mb\x1b[22m\x1b[0m\n\x1b[31m\x1b[39mc\n"
+        );
+    }
+}
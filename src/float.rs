@@ -0,0 +1,61 @@
+//! Float math that also works without `std`.
+
+#[cfg(feature = "std")]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    x.powf(y)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    libm::powf(x, y)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn round(x: f32) -> f32 {
+    x.round()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn round(x: f32) -> f32 {
+    libm::roundf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn mul_add(x: f32, a: f32, b: f32) -> f32 {
+    x.mul_add(a, b)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn mul_add(x: f32, a: f32, b: f32) -> f32 {
+    libm::fmaf(x, a, b)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn cbrt(x: f32) -> f32 {
+    x.cbrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn cbrt(x: f32) -> f32 {
+    libm::cbrtf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrt(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrt(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sin_cos(x: f32) -> (f32, f32) {
+    x.sin_cos()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sin_cos(x: f32) -> (f32, f32) {
+    (libm::sinf(x), libm::cosf(x))
+}
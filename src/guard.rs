@@ -0,0 +1,205 @@
+//! Keeping a style active on a writer for a scope.
+
+use crate::config;
+use crate::policy;
+use crate::style::Style;
+use crate::{CSI, SGR_SUFFIX};
+use std::io::{self, Write};
+
+/// Turns a style on for as long as the guard lives.
+///
+/// The style's codes are written when the guard is created and its
+/// off-codes (`22` for bold, `39` for a foreground color, ...) when it is
+/// dropped, so text written in between, through the guard or the writer
+/// it wraps, is styled without being collected into one string. Like
+/// [`Style::apply`], nothing is written when colors are disabled, and the
+/// accessibility [`Policy`](crate::Policy) is applied.
+///
+/// Errors from the closing write are ignored on drop; call
+/// [`StyleGuard::finish`] to see them.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{Color, Style, StyleGuard};
+/// use std::io::Write;
+/// # glyphs::set_color_choice(glyphs::ColorChoice::Always);
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut red = StyleGuard::new(&mut out, Style::new().fg(Color::Red))?;
+///     for chunk in ["many ", "plain ", "chunks"] {
+///         red.write_all(chunk.as_bytes())?;
+///     }
+/// }
+/// assert_eq!(out, b"\x1b[31mmany plain chunks\x1b[39m");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StyleGuard<W: Write> {
+    writer: W,
+    off: String,
+}
+
+impl<W: Write> StyleGuard<W> {
+    /// Write the codes for `style` to `writer` and keep it on until the
+    /// guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the codes cannot be written.
+    pub fn new(mut writer: W, style: Style) -> io::Result<Self> {
+        style.write_start(&mut writer)?;
+        Ok(Self {
+            writer,
+            off: effective(&style).off_sequence(),
+        })
+    }
+
+    /// Get a mutable reference to the wrapped writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Turn the style off now, reporting errors the drop would ignore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the off-codes cannot be written.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.close()
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        let off = core::mem::take(&mut self.off);
+        self.writer.write_all(off.as_bytes())
+    }
+}
+
+impl<W: Write> Write for StyleGuard<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for StyleGuard<W> {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+impl Style {
+    /// Write the codes turning this style on to `writer`.
+    ///
+    /// Like [`Style::apply`], nothing is written when colors are disabled,
+    /// and the accessibility [`Policy`](crate::Policy) is applied. Follow
+    /// with [`Style::write_end`], or use [`Style::guard`] to have the style
+    /// turned off automatically.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Style};
+    /// use std::io::Write;
+    /// # glyphs::set_color_choice(glyphs::ColorChoice::Always);
+    ///
+    /// let warn = Style::new().fg(Color::Yellow);
+    /// let mut out = Vec::new();
+    /// warn.write_start(&mut out)?;
+    /// write!(out, "{} files skipped", 3)?;
+    /// warn.write_end(&mut out)?;
+    /// assert_eq!(out, b"\x1b[33m3 files skipped\x1b[39m");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the codes cannot be written.
+    pub fn write_start<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        let style = effective(self);
+        if style.is_plain() {
+            return Ok(());
+        }
+        let mut on = String::from(CSI);
+        let _ = style.write_codes(&mut on);
+        on.push_str(SGR_SUFFIX);
+        writer.write_all(on.as_bytes())
+    }
+
+    /// Write the codes turning off what [`Style::write_start`] turned on
+    /// (`22` for bold, `39` for a foreground color, ...), keeping any
+    /// other attributes active.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the codes cannot be written.
+    pub fn write_end<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(effective(self).off_sequence().as_bytes())
+    }
+
+    /// Turn this style on for `writer` until the returned guard is
+    /// dropped; see [`StyleGuard`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the codes cannot be written.
+    pub fn guard<W: Write>(self, writer: W) -> io::Result<StyleGuard<W>> {
+        StyleGuard::new(writer, self)
+    }
+}
+
+/// The style as it is written: plain when colors are disabled, and with
+/// the accessibility policy applied otherwise.
+fn effective(style: &Style) -> Style {
+    if config::colors_enabled() {
+        policy::policy().enforce(style)
+    } else {
+        Style::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::config::{set_color_choice, ColorChoice};
+    use crate::modifier::Modifier;
+
+    #[test]
+    fn test_style_guard() {
+        set_color_choice(ColorChoice::Always);
+        let mut out = Vec::new();
+        let style = Style::new().bg(Color::Blue).modifier(Modifier::Bold);
+        let mut guard = StyleGuard::new(&mut out, style).unwrap();
+        guard.write_all(b"a").unwrap();
+        guard.get_mut().write_all(b"b").unwrap();
+        guard.finish().unwrap();
+        assert_eq!(out, b"\x1b[1;44mab\x1b[22;49m");
+
+        let mut out = Vec::new();
+        drop(StyleGuard::new(&mut out, Style::new()).unwrap());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_write_start_end() {
+        set_color_choice(ColorChoice::Always);
+        let style = Style::new().fg(Color::Red).modifier(Modifier::Dim);
+        let mut out = Vec::new();
+        style.write_start(&mut out).unwrap();
+        out.extend_from_slice(b"x");
+        style.write_end(&mut out).unwrap();
+        {
+            let mut guard = style.guard(&mut out).unwrap();
+            guard.write_all(b"y").unwrap();
+        }
+        assert_eq!(out, b"\x1b[2;31mx\x1b[22;39m\x1b[2;31my\x1b[22;39m");
+
+        let mut out = Vec::new();
+        Style::new().write_start(&mut out).unwrap();
+        Style::new().write_end(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+//! Theme-colored help screens for `clap` (requires the `clap` feature).
+//!
+//! [`Theme::clap_styles`] turns a theme into clap's `Styles`, so clap
+//! colors its own help and errors to match the rest of the program:
+//!
+//! ```rust,ignore
+//! let cli = clap::Command::new("demo").styles(glyphs::Theme::dark().clap_styles());
+//! ```
+//!
+//! [`recolor_help`] restyles help text that has already been rendered,
+//! for help printed through other paths or captured from another tool.
+
+use crate::color::Color;
+use crate::modifier::Modifier;
+use crate::parser::strip_ansi;
+use crate::style::Style;
+use crate::theme::Theme;
+use clap::builder::styling::{self, Ansi256Color, AnsiColor, Effects, RgbColor, Styles};
+
+/// Deepest indentation of an option, argument, or command entry; deeper
+/// lines are wrapped descriptions.
+const MAX_ENTRY_INDENT: usize = 4;
+
+impl Theme {
+    /// Clap styles using this theme.
+    ///
+    /// Headings and the usage label use [`Theme::accent`] in bold, flags
+    /// and commands [`Theme::info`] in bold, placeholders
+    /// [`Theme::muted`], errors [`Theme::error`], and the valid and
+    /// invalid values in error messages [`Theme::success`] and
+    /// [`Theme::warning`].
+    #[must_use]
+    pub fn clap_styles(&self) -> Styles {
+        let bold = |style: Style| style.modifier(Modifier::Bold).into();
+        Styles::plain()
+            .header(bold(self.accent))
+            .usage(bold(self.accent))
+            .literal(bold(self.info))
+            .placeholder(self.muted.into())
+            .error(self.error.into())
+            .valid(self.success.into())
+            .invalid(self.warning.into())
+    }
+}
+
+impl From<Style> for styling::Style {
+    /// Convert to clap's style type. Modifiers it has no effect for, such
+    /// as overline and fraktur, are dropped; rapid blink becomes blink.
+    fn from(style: Style) -> Self {
+        let mut effects = Effects::new();
+        for modifier in style.modifiers.iter() {
+            effects |= match modifier {
+                Modifier::Bold => Effects::BOLD,
+                Modifier::Dim => Effects::DIMMED,
+                Modifier::Italic => Effects::ITALIC,
+                Modifier::Underline => Effects::UNDERLINE,
+                Modifier::DoubleUnderline => Effects::DOUBLE_UNDERLINE,
+                Modifier::Blink | Modifier::RapidBlink => Effects::BLINK,
+                Modifier::Reverse => Effects::INVERT,
+                Modifier::Hidden => Effects::HIDDEN,
+                Modifier::Strikethrough => Effects::STRIKETHROUGH,
+                _ => Effects::new(),
+            };
+        }
+        Self::new()
+            .fg_color(style.foreground.and_then(clap_color))
+            .bg_color(style.background.and_then(clap_color))
+            .effects(effects)
+    }
+}
+
+fn clap_color(color: Color) -> Option<styling::Color> {
+    let ansi = match color {
+        Color::Default => return None,
+        Color::Ansi256(n) => return Some(Ansi256Color(n).into()),
+        Color::Rgb { r, g, b } => return Some(RgbColor(r, g, b).into()),
+        Color::Black => AnsiColor::Black,
+        Color::Red => AnsiColor::Red,
+        Color::Green => AnsiColor::Green,
+        Color::Yellow => AnsiColor::Yellow,
+        Color::Blue => AnsiColor::Blue,
+        Color::Magenta => AnsiColor::Magenta,
+        Color::Cyan => AnsiColor::Cyan,
+        Color::White => AnsiColor::White,
+        Color::BrightBlack => AnsiColor::BrightBlack,
+        Color::BrightRed => AnsiColor::BrightRed,
+        Color::BrightGreen => AnsiColor::BrightGreen,
+        Color::BrightYellow => AnsiColor::BrightYellow,
+        Color::BrightBlue => AnsiColor::BrightBlue,
+        Color::BrightMagenta => AnsiColor::BrightMagenta,
+        Color::BrightCyan => AnsiColor::BrightCyan,
+        Color::BrightWhite => AnsiColor::BrightWhite,
+    };
+    Some(ansi.into())
+}
+
+/// Restyle rendered clap help with `theme`, as [`Theme::clap_styles`]
+/// would have.
+///
+/// Existing escape sequences are removed first. Unindented lines ending
+/// in `:` are headings; in entries such as `  -o, --output <FILE>  Where
+/// to write`, flags and command names are styled as literals and `<...>`
+/// and `[...]` as placeholders, and the description is left alone. The
+/// `Usage:` line is styled the same way.
+///
+/// ```rust
+/// use glyphs::{recolor_help, strip_ansi, Theme};
+///
+/// let help = "Usage: demo [OPTIONS] <FILE>\n\nOptions:\n  -q, --quiet  Print less\n";
+/// let colored = recolor_help(help, &Theme::dark());
+/// assert_eq!(strip_ansi(&colored), help);
+/// ```
+#[must_use]
+pub fn recolor_help(help: &str, theme: &Theme) -> String {
+    let plain = strip_ansi(help);
+    let heading = theme.accent.modifier(Modifier::Bold);
+    let mut out = String::with_capacity(plain.len() * 2);
+    for (i, line) in plain.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let body = line.trim_start();
+        let indent = line.len() - body.len();
+        if indent == 0 {
+            if let Some(usage) = line.strip_prefix("Usage:") {
+                out.push_str(&heading.apply("Usage:"));
+                recolor_terms(usage, theme, &mut out);
+            } else if line.ends_with(':') {
+                out.push_str(&heading.apply(line));
+            } else {
+                out.push_str(line);
+            }
+        } else if indent <= MAX_ENTRY_INDENT {
+            let (terms, description) = body.find("  ").map_or((body, ""), |at| body.split_at(at));
+            out.push_str(&line[..indent]);
+            recolor_terms(terms, theme, &mut out);
+            out.push_str(description);
+        } else {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+/// Style the words of a usage line or entry: `<...>` and `[...]` as
+/// placeholders, everything else as literals, separators as-is.
+fn recolor_terms(terms: &str, theme: &Theme, out: &mut String) {
+    let literal = theme.info.modifier(Modifier::Bold);
+    let mut rest = terms;
+    while !rest.is_empty() {
+        let word_len = rest.find([' ', ',']).unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(word_len);
+        if word.starts_with(['<', '[']) {
+            out.push_str(&theme.muted.apply(word));
+        } else if !word.is_empty() {
+            out.push_str(&literal.apply(word));
+        }
+        let separator_len = tail.len() - tail.trim_start_matches([' ', ',']).len();
+        out.push_str(&tail[..separator_len]);
+        rest = &tail[separator_len..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recolor_help() {
+        let theme = Theme::dark();
+        let literal = |s: &str| theme.info.modifier(Modifier::Bold).apply(s);
+        let placeholder = |s: &str| theme.muted.apply(s);
+        let help = "\x1b[1mOptions:\x1b[0m\n  -o, --output <FILE>  Where to write\n          [default: out]";
+        assert_eq!(
+            recolor_help(help, &theme),
+            format!(
+                "{}\n  {}, {} {}  Where to write\n          [default: out]",
+                theme.accent.modifier(Modifier::Bold).apply("Options:"),
+                literal("-o"),
+                literal("--output"),
+                placeholder("<FILE>"),
+            )
+        );
+    }
+
+    #[test]
+    fn test_clap_styles() {
+        let style = styling::Style::from(
+            Style::new()
+                .fg(Color::BrightRed)
+                .bg(Color::Ansi256(236))
+                .modifier(Modifier::Bold)
+                .modifier(Modifier::Overline),
+        );
+        assert_eq!(style.get_fg_color(), Some(AnsiColor::BrightRed.into()));
+        assert_eq!(style.get_bg_color(), Some(Ansi256Color(236).into()));
+        assert_eq!(style.get_effects(), Effects::BOLD);
+
+        let styles = Theme::dark().clap_styles();
+        assert_eq!(
+            styles.get_error().get_fg_color(),
+            Some(AnsiColor::BrightRed.into())
+        );
+        assert_eq!(
+            *styles.get_placeholder(),
+            clap::builder::styling::Style::from(Theme::dark().muted)
+        );
+    }
+}
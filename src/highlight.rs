@@ -0,0 +1,105 @@
+//! Hook for plugging syntax highlighters into styled output.
+
+use crate::style::Style;
+use crate::text::overlay;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A source of styled spans for a line of text.
+///
+/// Adapters for syntect, tree-sitter, or hand-written lexers implement this
+/// and get ANSI rendering through [`Highlighter::render`] without sigil
+/// depending on them. Closures taking a line and returning spans implement
+/// it too.
+///
+/// ```rust
+/// use glyphs::{Color, Highlighter, Style};
+/// use std::ops::Range;
+///
+/// struct Keywords;
+///
+/// impl Highlighter for Keywords {
+///     fn highlight(&self, line: &str) -> Vec<(Range<usize>, Style)> {
+///         line.match_indices("fn")
+///             .map(|(i, m)| (i..i + m.len(), Style::new().fg(Color::Magenta)))
+///             .collect()
+///     }
+/// }
+///
+/// assert_eq!(Keywords.render("fn main"), "\x1b[35mfn\x1b[0m main");
+/// ```
+pub trait Highlighter {
+    /// Styled byte ranges of `line`.
+    ///
+    /// `line` is plain text without escape sequences. Ranges may overlap;
+    /// later ones are patched over earlier ones (see [`Style::patch`]).
+    fn highlight(&self, line: &str) -> Vec<(Range<usize>, Style)>;
+
+    /// Highlight `line` and render it with ANSI escapes.
+    fn render(&self, line: &str) -> String {
+        render_highlights(line, &self.highlight(line))
+    }
+}
+
+impl<F> Highlighter for F
+where
+    F: Fn(&str) -> Vec<(Range<usize>, Style)>,
+{
+    fn highlight(&self, line: &str) -> Vec<(Range<usize>, Style)> {
+        self(line)
+    }
+}
+
+/// Render styled byte ranges of a plain line as ANSI.
+///
+/// Ranges are clamped to the line; text outside every range is left
+/// unstyled, and each styled run ends with a reset.
+///
+/// ```rust
+/// use glyphs::{render_highlights, Color, Modifier, Style};
+///
+/// let spans = [
+///     (0..3, Style::new().fg(Color::Blue)),
+///     (4..6, Style::new().modifier(Modifier::Bold)),
+/// ];
+/// assert_eq!(render_highlights("let x1", &spans), "\x1b[34mlet\x1b[0m \x1b[1mx1\x1b[0m");
+/// ```
+#[must_use]
+pub fn render_highlights(line: &str, spans: &[(Range<usize>, Style)]) -> String {
+    let starts: Vec<usize> = line.char_indices().map(|(i, _)| i).collect();
+    let position = |byte: usize| starts.partition_point(|&start| start < byte);
+
+    let mut layers = vec![Style::new(); starts.len()];
+    for (range, style) in spans {
+        let end = position(range.end.min(line.len()));
+        let start = position(range.start).min(end);
+        for layer in &mut layers[start..end] {
+            *layer = layer.patch(*style);
+        }
+    }
+    overlay(line, &layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::modifier::Modifier;
+
+    #[test]
+    fn test_overlapping_and_out_of_range_spans() {
+        let red = Style::new().fg(Color::Red);
+        let under = Style::new().modifier(Modifier::Underline);
+        let reversed = Range { start: 7, end: 3 };
+        let spans = [(0..4, red), (2..40, under), (reversed, red)];
+        assert_eq!(
+            render_highlights("abcdef", &spans),
+            "\x1b[31mab\x1b[0m\x1b[4;31mcd\x1b[0m\x1b[4mef\x1b[0m"
+        );
+
+        let words = |line: &str| vec![(0..line.find(' ').unwrap_or(line.len()), red)];
+        assert_eq!(words.render("héllo x"), "\x1b[31mhéllo\x1b[0m x");
+    }
+}
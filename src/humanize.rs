@@ -0,0 +1,221 @@
+//! Human-readable sizes and durations, colored by how large they are.
+
+use crate::theme::{Magnitude, Theme};
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::time::Duration;
+
+/// Binary units above bytes, for [`humanize_bytes`].
+const BYTE_UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Format a byte count with binary units, such as `1.5 KiB`.
+///
+/// Sizes under 1 MiB are [`Magnitude::Low`], under 1 GiB
+/// [`Magnitude::Medium`], and larger ones [`Magnitude::High`]; change
+/// this with [`Humanized::thresholds`].
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{humanize_bytes, Magnitude};
+///
+/// let size = humanize_bytes(3 * 1024 * 1024 / 2);
+/// assert_eq!(size.to_string(), "1.5 MiB");
+/// assert_eq!(size.magnitude(), Magnitude::Medium);
+/// println!("wrote {}", size.style_by_magnitude());
+/// ```
+#[must_use]
+pub fn humanize_bytes(bytes: u64) -> Humanized<u64> {
+    Humanized::new(format_bytes(bytes), bytes, 1 << 20, 1 << 30)
+}
+
+/// Format a duration with one or two significant decimals, such as
+/// `12.3ms` or `1m 05s`.
+///
+/// Durations under a second are [`Magnitude::Low`], under ten seconds
+/// [`Magnitude::Medium`], and longer ones [`Magnitude::High`]; change
+/// this with [`Humanized::thresholds`].
+///
+/// # Example
+///
+/// ```rust
+/// use core::time::Duration;
+/// use glyphs::{humanize_duration, Magnitude};
+///
+/// let elapsed = humanize_duration(Duration::from_micros(12_345));
+/// assert_eq!(elapsed.to_string(), "12.3ms");
+/// assert_eq!(elapsed.magnitude(), Magnitude::Low);
+/// ```
+#[must_use]
+pub fn humanize_duration(duration: Duration) -> Humanized<Duration> {
+    Humanized::new(
+        format_duration(duration),
+        duration,
+        Duration::from_secs(1),
+        Duration::from_secs(10),
+    )
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    // Move up a unit where one decimal would round to 1024.0.
+    while value >= 1023.95 && unit + 1 < BYTE_UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", BYTE_UNITS[unit])
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    // The cut-offs sit where rounding would carry into the next unit.
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 999_950 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 999_950_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else if nanos < 59_995_000_000 {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    } else {
+        let secs = (nanos + 500_000_000) / 1_000_000_000;
+        if secs < 3600 {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        } else {
+            format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+        }
+    }
+}
+
+/// A formatted measurement that knows its [`Magnitude`].
+///
+/// Displays as plain text; [`Humanized::style_by_magnitude`] colors it
+/// with the theme's style for its magnitude.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Humanized<T> {
+    text: String,
+    value: T,
+    medium: T,
+    high: T,
+    theme: Theme,
+}
+
+impl<T> Humanized<T> {
+    fn new(text: String, value: T, medium: T, high: T) -> Self {
+        Self {
+            text,
+            value,
+            medium,
+            high,
+            theme: Theme::current(),
+        }
+    }
+
+    /// Color with `theme` instead of [`Theme::current`].
+    #[must_use]
+    pub const fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The formatted text, without styling.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<T: PartialOrd> Humanized<T> {
+    /// Values from `medium` are [`Magnitude::Medium`], and values from
+    /// `high` [`Magnitude::High`].
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use glyphs::{humanize_duration, Magnitude};
+    ///
+    /// let step = humanize_duration(Duration::from_millis(300))
+    ///     .thresholds(Duration::from_millis(100), Duration::from_millis(500));
+    /// assert_eq!(step.magnitude(), Magnitude::Medium);
+    /// ```
+    #[must_use]
+    pub fn thresholds(mut self, medium: T, high: T) -> Self {
+        self.medium = medium;
+        self.high = high;
+        self
+    }
+
+    /// How large the value is compared to the thresholds.
+    #[must_use]
+    pub fn magnitude(&self) -> Magnitude {
+        if self.value >= self.high {
+            Magnitude::High
+        } else if self.value >= self.medium {
+            Magnitude::Medium
+        } else {
+            Magnitude::Low
+        }
+    }
+
+    /// The text in the theme's style for its magnitude, as chosen by
+    /// [`Theme::for_magnitude`].
+    #[must_use]
+    pub fn style_by_magnitude(&self) -> String {
+        self.theme.for_magnitude(self.magnitude()).apply(&self.text)
+    }
+}
+
+impl<T> fmt::Display for Humanized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_humanize_bytes() {
+        assert_eq!(humanize_bytes(0).text(), "0 B");
+        assert_eq!(humanize_bytes(1023).text(), "1023 B");
+        assert_eq!(humanize_bytes(1024).text(), "1.0 KiB");
+        assert_eq!(humanize_bytes((1 << 20) - 1).text(), "1.0 MiB");
+        assert_eq!(humanize_bytes(u64::MAX).text(), "16.0 EiB");
+
+        assert_eq!(humanize_bytes((1 << 20) - 1).magnitude(), Magnitude::Low);
+        assert_eq!(humanize_bytes(1 << 20).magnitude(), Magnitude::Medium);
+        assert_eq!(humanize_bytes(5 << 30).magnitude(), Magnitude::High);
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        let text = |d| String::from(humanize_duration(d).text());
+        assert_eq!(text(Duration::from_nanos(999)), "999ns");
+        assert_eq!(text(Duration::from_nanos(1_500)), "1.5µs");
+        assert_eq!(text(Duration::from_nanos(999_960)), "1.0ms");
+        assert_eq!(text(Duration::from_millis(2_500)), "2.50s");
+        assert_eq!(text(Duration::from_millis(59_996)), "1m 00s");
+        assert_eq!(text(Duration::from_secs(3 * 3600 + 5 * 60)), "3h 05m");
+
+        let magnitude = |ms| humanize_duration(Duration::from_millis(ms)).magnitude();
+        assert_eq!(magnitude(999), Magnitude::Low);
+        assert_eq!(magnitude(1_000), Magnitude::Medium);
+        assert_eq!(magnitude(10_000), Magnitude::High);
+    }
+
+    #[test]
+    fn test_style_by_magnitude() {
+        let fast = humanize_duration(Duration::from_millis(5)).theme(Theme::light());
+        assert_eq!(
+            fast.style_by_magnitude(),
+            Theme::light().success.apply("5.0ms")
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! OSC 8 hyperlinks, in parsed output and written with a fallback.
+
+use crate::caps::TermCaps;
+use crate::control::ControlChar;
+use crate::escape::{Escape, EscapeKind};
+use crate::parser::{osc_body, parse_visit, Visitor};
+use crate::OSC;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// String terminator ending an OSC sequence.
+const ST: &str = "\x1b\\";
+
+/// An OSC 8 hyperlink marker, from [`Escape::hyperlink`].
+///
+/// A marker with a URI opens a link; one with an empty URI closes the
+/// open link.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hyperlink {
+    /// The link target, empty for a closing marker.
+    pub uri: String,
+    /// The `id=` parameter, which ties together separate spans of the
+    /// same link, such as one wrapped across lines.
+    pub id: Option<String>,
+}
+
+impl Hyperlink {
+    /// Parse the body of an OSC sequence, such as `8;id=a;https://...`.
+    pub(crate) fn from_osc_body(body: &str) -> Option<Self> {
+        let (params, uri) = body.strip_prefix("8;")?.split_once(';')?;
+        let id = params
+            .split(':')
+            .find_map(|param| param.strip_prefix("id="))
+            .filter(|id| !id.is_empty())
+            .map(ToString::to_string);
+        Some(Self {
+            uri: uri.to_string(),
+            id,
+        })
+    }
+
+    /// Whether this marker closes a link rather than opening one.
+    #[must_use]
+    pub fn is_close(&self) -> bool {
+        self.uri.is_empty()
+    }
+}
+
+impl Escape {
+    /// The hyperlink marker this sequence is, if it is an OSC 8
+    /// sequence.
+    ///
+    /// ```rust
+    /// use glyphs::parse;
+    ///
+    /// let segments = parse("\x1b]8;id=42;https://example.com\x1b\\link\x1b]8;;\x1b\\");
+    /// let open = segments[0].as_escape().unwrap().hyperlink().unwrap();
+    /// assert_eq!(open.uri, "https://example.com");
+    /// assert_eq!(open.id.as_deref(), Some("42"));
+    /// assert!(segments[2].as_escape().unwrap().hyperlink().unwrap().is_close());
+    /// ```
+    #[must_use]
+    pub fn hyperlink(&self) -> Option<Hyperlink> {
+        if self.kind != EscapeKind::Osc {
+            return None;
+        }
+        Hyperlink::from_osc_body(osc_body(&self.raw))
+    }
+}
+
+/// A link and the text shown for it, from [`hyperlinks`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinkSpan {
+    /// The link target.
+    pub uri: String,
+    /// The `id=` parameter of the opening marker.
+    pub id: Option<String>,
+    /// The text between the markers, without escape sequences.
+    pub text: String,
+    /// Whether a closing marker ended the link. Links ended by another
+    /// link opening, or still open at the end of the input, are not
+    /// closed.
+    pub closed: bool,
+}
+
+/// Pair the OSC 8 markers in `input` into links, in order.
+///
+/// Like a terminal, a link opened while another is open replaces it, a
+/// closing marker without an open link is ignored, and a link left open
+/// runs to the end of the input. [`LinkSpan::closed`] tells these cases
+/// apart from properly closed links.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::hyperlinks;
+///
+/// let input = "see \x1b]8;;https://a.example\x07docs\x1b]8;;\x07 or \x1b]8;;https://b.example\x07more";
+/// let links = hyperlinks(input);
+/// assert_eq!(links.len(), 2);
+/// assert_eq!((links[0].text.as_str(), links[0].closed), ("docs", true));
+/// assert_eq!((links[1].text.as_str(), links[1].closed), ("more", false));
+/// ```
+#[must_use]
+pub fn hyperlinks(input: &str) -> Vec<LinkSpan> {
+    let mut collector = Collector::default();
+    parse_visit(input, &mut collector);
+    collector.links.extend(collector.open);
+    collector.links
+}
+
+/// Link `text` to `url` if the terminal shows hyperlinks, or write both
+/// as `text (url)` otherwise.
+///
+/// With [`TermCaps::hyperlinks`] set, `text` is wrapped in OSC 8 markers
+/// and the URL stays hidden; elsewhere, such as CI logs, the URL is
+/// written out so it can still be read and copied. When `text` is empty
+/// or the URL itself, the fallback is just the URL.
+///
+/// Control characters in `url` are percent-encoded in both forms, so a
+/// URL can't end the OSC sequence early or smuggle in escape sequences.
+///
+/// See [`Console::link`](crate::Console::link) to decide from the
+/// stream being written to.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{link_or_fallback, TermCaps};
+///
+/// let url = "https://example.com/docs";
+/// let plain = TermCaps::default();
+/// assert_eq!(link_or_fallback("docs", url, &plain), "docs (https://example.com/docs)");
+///
+/// let linking = TermCaps { hyperlinks: true, ..plain };
+/// assert_eq!(
+///     link_or_fallback("docs", url, &linking),
+///     "\x1b]8;;https://example.com/docs\x1b\\docs\x1b]8;;\x1b\\"
+/// );
+/// ```
+#[must_use]
+pub fn link_or_fallback(text: &str, url: &str, caps: &TermCaps) -> String {
+    let same = text.is_empty() || text == url;
+    let url = encode_controls(url);
+    if caps.hyperlinks {
+        let text = if same { &url } else { text };
+        format!("{OSC}8;;{url}{ST}{text}{OSC}8;;{ST}")
+    } else if same {
+        url
+    } else {
+        format!("{text} ({url})")
+    }
+}
+
+/// Percent-encode the C0 and C1 control characters and DEL in `url`.
+fn encode_controls(url: &str) -> String {
+    let mut out = String::with_capacity(url.len());
+    for c in url.chars() {
+        if c.is_control() {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[derive(Default)]
+struct Collector {
+    links: Vec<LinkSpan>,
+    open: Option<LinkSpan>,
+}
+
+impl Visitor for Collector {
+    fn on_text(&mut self, text: &str) {
+        if let Some(open) = &mut self.open {
+            open.text.push_str(text);
+        }
+    }
+
+    fn on_control(&mut self, control: ControlChar) {
+        if let Some(open) = &mut self.open {
+            open.text.push(control.as_char());
+        }
+    }
+
+    fn on_osc(&mut self, body: &str) {
+        let Some(link) = Hyperlink::from_osc_body(body) else {
+            return;
+        };
+        if let Some(mut open) = self.open.take() {
+            open.closed = link.is_close();
+            self.links.push(open);
+        }
+        if !link.is_close() {
+            self.open = Some(LinkSpan {
+                uri: link.uri,
+                id: link.id,
+                text: String::new(),
+                closed: false,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn span(uri: &str, id: Option<&str>, text: &str, closed: bool) -> LinkSpan {
+        LinkSpan {
+            uri: uri.to_string(),
+            id: id.map(ToString::to_string),
+            text: text.to_string(),
+            closed,
+        }
+    }
+
+    #[test]
+    fn test_hyperlink_marker() {
+        let marker = |raw: &str| parse(raw)[0].as_escape().unwrap().hyperlink();
+        assert_eq!(
+            marker("\x1b]8;foo=1:id=x;file:///tmp\x07"),
+            Some(Hyperlink {
+                uri: "file:///tmp".to_string(),
+                id: Some("x".to_string()),
+            })
+        );
+        assert_eq!(
+            marker("\u{9d}8;;u\u{9c}").map(|l| l.uri),
+            Some("u".to_string())
+        );
+        assert_eq!(marker("\x1b]8;id=;u\x07").unwrap().id, None);
+        assert_eq!(marker("\x1b]0;title\x07"), None);
+        assert_eq!(marker("\x1b[8m"), None);
+    }
+
+    #[test]
+    fn test_link_or_fallback() {
+        let plain = TermCaps::default();
+        let linking = TermCaps {
+            hyperlinks: true,
+            ..plain
+        };
+        assert_eq!(link_or_fallback("", "u", &plain), "u");
+        assert_eq!(link_or_fallback("u", "u", &plain), "u");
+
+        let links = |text| hyperlinks(&link_or_fallback(text, "u", &linking));
+        assert_eq!(links(""), [span("u", None, "u", true)]);
+        assert_eq!(links("go"), [span("u", None, "go", true)]);
+
+        let url = "https://e.x/a\x1b\\\x1b[2Jb\x07\u{9c}";
+        assert_eq!(
+            link_or_fallback("go", url, &linking),
+            "\x1b]8;;https://e.x/a%1B\\%1B[2Jb%07%C2%9C\x1b\\go\x1b]8;;\x1b\\"
+        );
+        assert_eq!(
+            link_or_fallback("go", url, &plain),
+            "go (https://e.x/a%1B\\%1B[2Jb%07%C2%9C)"
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks_pairing() {
+        let input = "\x1b]8;;\x07a\x1b]8;id=1;u1\x07b\n\x1b[1mc\x1b]8;;u2\x07d\x1b]8;;\x07e\x1b]8;id=1;u1\x07f";
+        assert_eq!(
+            hyperlinks(input),
+            [
+                span("u1", Some("1"), "b\nc", false),
+                span("u2", None, "d", true),
+                span("u1", Some("1"), "f", false),
+            ]
+        );
+        assert!(hyperlinks("no links").is_empty());
+    }
+}
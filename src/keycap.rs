@@ -0,0 +1,184 @@
+//! Keyboard shortcut hints such as ` Ctrl + C ` for help footers.
+
+use crate::modifier::Modifier;
+use crate::theme::Theme;
+use crate::width::WidthPolicy;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Create a hint for a shortcut such as `Ctrl+C`.
+///
+/// Keys are separated by `+`; a `+` on its own is a key, so `Ctrl++`
+/// is Ctrl and plus.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{keycap, strip_ansi};
+///
+/// let quit = keycap("Ctrl+C").label("quit");
+/// assert_eq!(strip_ansi(&quit.to_string()), " Ctrl + C  quit");
+/// assert_eq!(quit.width(), 15);
+///
+/// let boxed = keycap("Ctrl++").boxed();
+/// assert_eq!(strip_ansi(&boxed.to_string()), "[Ctrl]+[+]");
+/// ```
+#[must_use]
+pub fn keycap(keys: &str) -> Keycap {
+    Keycap::new(keys)
+}
+
+/// How [`Keycap`] sets each key apart from the surrounding text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KeycapStyle {
+    /// Padded keys in reverse video, like ` Ctrl `.
+    #[default]
+    Inverse,
+    /// Keys in square brackets, like `[Ctrl]`, for terminals and logs
+    /// where reverse video is unavailable or too loud.
+    Boxed,
+}
+
+/// A styled keyboard shortcut with an optional label.
+///
+/// Keys use [`Theme::key`] and the label [`Theme::muted`], so every hint
+/// drawn with one theme matches.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Keycap {
+    keys: Vec<String>,
+    label: Option<String>,
+    style: KeycapStyle,
+    theme: Theme,
+}
+
+impl Keycap {
+    /// Create a hint for the `+`-separated `keys`.
+    #[must_use]
+    pub fn new(keys: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut key = String::new();
+        for c in keys.chars() {
+            if c == '+' && !key.is_empty() {
+                parts.push(core::mem::take(&mut key));
+            } else {
+                key.push(c);
+            }
+        }
+        if !key.is_empty() {
+            parts.push(key);
+        }
+        Self {
+            keys: parts,
+            label: None,
+            style: KeycapStyle::Inverse,
+            theme: Theme::current(),
+        }
+    }
+
+    /// Describe what the shortcut does, shown after the keys.
+    #[must_use]
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(String::from(label));
+        self
+    }
+
+    /// Draw keys in square brackets instead of reverse video.
+    #[must_use]
+    pub const fn boxed(self) -> Self {
+        self.keycap_style(KeycapStyle::Boxed)
+    }
+
+    /// Set how keys are drawn.
+    #[must_use]
+    pub const fn keycap_style(mut self, style: KeycapStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Style with `theme` instead of [`Theme::current`].
+    #[must_use]
+    pub const fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The keys of the shortcut, in order.
+    #[must_use]
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Visible width in terminal columns, as by
+    /// [`WidthPolicy::visible_width`], for lining up hints in a footer.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        let policy = WidthPolicy::new();
+        let keys: usize = self.keys.iter().map(|k| policy.visible_width(k) + 2).sum();
+        let separators = self.keys.len().saturating_sub(1);
+        let label = self
+            .label
+            .as_ref()
+            .map_or(0, |l| policy.visible_width(l) + 1);
+        keys + separators + label
+    }
+}
+
+impl fmt::Display for Keycap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (key, open, close) = match self.style {
+            KeycapStyle::Inverse => (self.theme.key.modifier(Modifier::Reverse), ' ', ' '),
+            KeycapStyle::Boxed => (self.theme.key, '[', ']'),
+        };
+        for (i, name) in self.keys.iter().enumerate() {
+            if i > 0 {
+                f.write_str("+")?;
+            }
+            let mut cap = String::with_capacity(name.len() + 2);
+            cap.push(open);
+            cap.push_str(name);
+            cap.push(close);
+            f.write_str(&key.apply(&cap))?;
+        }
+        if let Some(label) = &self.label {
+            write!(f, " {}", self.theme.muted.apply(label))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::strip_ansi;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(keycap("Ctrl+Shift+P").keys(), ["Ctrl", "Shift", "P"]);
+        assert_eq!(keycap("Ctrl++").keys(), ["Ctrl", "+"]);
+        assert_eq!(keycap("+").keys(), ["+"]);
+        assert!(keycap("").keys().is_empty());
+    }
+
+    #[test]
+    fn test_render_and_width() {
+        let cap = keycap("Alt+F4").label("close");
+        assert_eq!(strip_ansi(&cap.to_string()), " Alt + F4  close");
+        assert_eq!(
+            cap.width(),
+            WidthPolicy::new().visible_width(&cap.to_string())
+        );
+
+        let cap = keycap("Esc").boxed().theme(Theme::new());
+        assert_eq!(cap.to_string(), "[Esc]");
+        assert_eq!(cap.width(), 5);
+
+        let cap = keycap("Ctrl+\u{00e4}\u{0308}").label("\u{4fdd}\u{5b58}");
+        assert_eq!(cap.width(), 15);
+        assert_eq!(
+            cap.width(),
+            WidthPolicy::new().visible_width(&cap.to_string())
+        );
+    }
+}
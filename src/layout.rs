@@ -0,0 +1,358 @@
+//! Laying out styled text for a terminal width.
+
+use crate::text::slice_visible;
+use crate::width::WidthPolicy;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Marker appended to items cut down by [`Columns::max_item_width`].
+pub const ELLIPSIS: char = '…';
+
+/// An `ls`-style column layout.
+///
+/// Items are filled top to bottom, then left to right, using as many
+/// columns as fit in the width. Widths are measured in terminal columns
+/// with [`WidthPolicy::visible_width`], so styled and wide items line up.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::Columns;
+///
+/// let items = ["Cargo.toml", "README.md", "src", "target", "tests"];
+/// let out = Columns::new(30).render(&items);
+/// assert_eq!(out, "Cargo.toml  src     tests\nREADME.md   target");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Columns {
+    width: usize,
+    gap: usize,
+    max_item_width: Option<usize>,
+    policy: WidthPolicy,
+}
+
+impl Columns {
+    /// A layout for `width` columns with two spaces between items.
+    #[must_use]
+    pub const fn new(width: usize) -> Self {
+        Self {
+            width,
+            gap: 2,
+            max_item_width: None,
+            policy: WidthPolicy::new(),
+        }
+    }
+
+    /// A layout for the width of the current terminal, or 80 columns if it
+    /// can't be detected (see [`term_size`](crate::term_size)).
+    #[cfg(feature = "term")]
+    #[must_use]
+    pub fn for_terminal() -> Self {
+        Self::new(crate::term::term_size().map_or(80, |(cols, _)| usize::from(cols)))
+    }
+
+    /// Set the number of spaces between columns.
+    #[must_use]
+    pub const fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Cut items wider than `max` columns, ending them with [`ELLIPSIS`].
+    #[must_use]
+    pub const fn max_item_width(mut self, max: usize) -> Self {
+        self.max_item_width = Some(max);
+        self
+    }
+
+    /// Measure items with `policy` instead of the default
+    /// [`WidthPolicy`], to match the terminal in use.
+    #[must_use]
+    pub const fn width_policy(mut self, policy: WidthPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Lay out `items`, one output line per row and no trailing newline.
+    ///
+    /// A single item wider than the layout gets a row to itself.
+    #[must_use]
+    pub fn render<S: AsRef<str>>(&self, items: &[S]) -> String {
+        let items: Vec<(String, usize)> = items
+            .iter()
+            .map(|item| {
+                let item = self.fit(item.as_ref());
+                let width = self.policy.visible_width(&item);
+                (item, width)
+            })
+            .collect();
+        if items.is_empty() {
+            return String::new();
+        }
+
+        let (rows, widths) = self.grid(&items);
+        let mut out = String::new();
+        for row in 0..rows {
+            if row > 0 {
+                out.push('\n');
+            }
+            let cells: Vec<_> = (row..items.len()).step_by(rows).collect();
+            for (col, &index) in cells.iter().enumerate() {
+                let (item, width) = &items[index];
+                out.push_str(item);
+                if col + 1 < cells.len() {
+                    let pad = widths[col] - width + self.gap;
+                    out.extend(core::iter::repeat(' ').take(pad));
+                }
+            }
+        }
+        out
+    }
+
+    /// The row count and column widths of the widest layout that fits.
+    fn grid(&self, items: &[(String, usize)]) -> (usize, Vec<usize>) {
+        for cols in (1..=items.len()).rev() {
+            let rows = items.len().div_ceil(cols);
+            let widths: Vec<usize> = items
+                .chunks(rows)
+                .map(|column| column.iter().map(|(_, w)| *w).max().unwrap_or(0))
+                .collect();
+            let total = widths.iter().sum::<usize>() + self.gap * (widths.len() - 1);
+            if total <= self.width || rows == items.len() {
+                return (rows, widths);
+            }
+        }
+        (items.len(), Vec::new())
+    }
+
+    fn fit(&self, item: &str) -> String {
+        match self.max_item_width {
+            Some(max) if self.policy.visible_width(item) > max => {
+                // Wide characters take two columns, so drop characters
+                // until the cut fits next to the ellipsis.
+                let room = max.saturating_sub(1);
+                let mut len = room;
+                let mut cut = slice_visible(item, ..len);
+                while len > 0 && self.policy.visible_width(&cut) > room {
+                    len -= 1;
+                    cut = slice_visible(item, ..len);
+                }
+                cut.push(ELLIPSIS);
+                cut
+            }
+            _ => String::from(item),
+        }
+    }
+}
+
+/// Lay out `items` in `ls`-style columns for a terminal `width`.
+///
+/// See [`Columns`] for the gap and per-item width options.
+#[must_use]
+pub fn columns<S: AsRef<str>>(items: &[S], width: usize) -> String {
+    Columns::new(width).render(items)
+}
+
+/// Aligned `key: value` lines, as printed by `--version` and `info`
+/// commands.
+///
+/// Widths are measured in terminal columns with
+/// [`WidthPolicy::visible_width`], so styled and wide keys line up. Values
+/// containing newlines continue at the value column.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::KvBlock;
+///
+/// let pairs = [("version", "0.1.0"), ("target", "x86_64-unknown-linux-gnu")];
+/// assert_eq!(
+///     KvBlock::new().render(&pairs),
+///     "version: 0.1.0\ntarget:  x86_64-unknown-linux-gnu"
+/// );
+/// assert_eq!(
+///     KvBlock::new().leaders('.').render(&pairs),
+///     "version ... 0.1.0\ntarget .... x86_64-unknown-linux-gnu"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KvBlock {
+    indent: usize,
+    leader: Option<char>,
+    policy: WidthPolicy,
+}
+
+impl KvBlock {
+    /// Keys followed by `:` and values aligned with spaces.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            indent: 0,
+            leader: None,
+            policy: WidthPolicy::new(),
+        }
+    }
+
+    /// Start every line with `indent` spaces.
+    #[must_use]
+    pub const fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Join keys and values with a row of `leader` characters, as in
+    /// `name ...... glyphs`, instead of a colon and spaces.
+    #[must_use]
+    pub const fn leaders(mut self, leader: char) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
+    /// Measure keys with `policy` instead of the default [`WidthPolicy`],
+    /// to match the terminal in use.
+    #[must_use]
+    pub const fn width_policy(mut self, policy: WidthPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Lay out `pairs`, one line per pair and no trailing newline.
+    #[must_use]
+    pub fn render<K: fmt::Display, V: fmt::Display>(&self, pairs: &[(K, V)]) -> String {
+        let keys: Vec<(String, usize)> = pairs
+            .iter()
+            .map(|(key, _)| {
+                let key = key.to_string();
+                let width = self.policy.visible_width(&key);
+                (key, width)
+            })
+            .collect();
+        let key_width = keys.iter().map(|(_, w)| *w).max().unwrap_or(0);
+        // Leaders need at least three characters to read as a row.
+        let value_column = self.indent
+            + key_width
+            + match self.leader {
+                Some(_) => 5,
+                None => 2,
+            };
+
+        let mut out = String::new();
+        for (i, ((key, width), (_, value))) in keys.iter().zip(pairs).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.extend(core::iter::repeat(' ').take(self.indent));
+            out.push_str(key);
+            let pad = key_width - width;
+            if let Some(leader) = self.leader {
+                out.push(' ');
+                out.extend(core::iter::repeat(leader).take(pad + 3));
+                out.push(' ');
+            } else {
+                out.push(':');
+                out.extend(core::iter::repeat(' ').take(pad + 1));
+            }
+            for (j, line) in value.to_string().split('\n').enumerate() {
+                if j > 0 {
+                    out.push('\n');
+                    out.extend(core::iter::repeat(' ').take(value_column));
+                }
+                out.push_str(line);
+            }
+        }
+        out
+    }
+}
+
+impl Default for KvBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lay out aligned `key: value` lines.
+///
+/// See [`KvBlock`] for indentation and dotted leaders.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{kv_block, strip_ansi, Color, Stylize};
+///
+/// let pairs = [
+///     ("name".bold(), "glyphs".fg(Color::Green)),
+///     ("license".bold(), "MIT".fg(Color::Green)),
+/// ];
+/// assert_eq!(strip_ansi(&kv_block(&pairs)), "name:    glyphs\nlicense: MIT");
+/// ```
+#[must_use]
+pub fn kv_block<K: fmt::Display, V: fmt::Display>(pairs: &[(K, V)]) -> String {
+    KvBlock::new().render(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_styled_and_truncated() {
+        let items = ["\x1b[34mbin\x1b[0m", "a", "longer-name", "b"];
+        assert_eq!(
+            columns(&items, 20),
+            "\x1b[34mbin\x1b[0m  longer-name\na    b"
+        );
+        assert_eq!(columns(&items, 5), "\x1b[34mbin\x1b[0m\na\nlonger-name\nb");
+
+        let out = Columns::new(20).gap(1).max_item_width(4).render(&items);
+        assert_eq!(out, "\x1b[34mbin\x1b[0m a lon… b");
+        assert_eq!(columns::<&str>(&[], 80), "");
+    }
+
+    #[test]
+    fn test_columns_wide_items() {
+        let items = ["\u{4f60}\u{597d}", "ab", "\u{4e16}", "c"];
+        assert_eq!(columns(&items, 9), "\u{4f60}\u{597d}  \u{4e16}\nab    c");
+
+        let out = Columns::new(20)
+            .max_item_width(4)
+            .render(&["\u{4f60}\u{597d}\u{4e16}"]);
+        assert_eq!(out, "\u{4f60}\u{2026}");
+
+        let cjk = WidthPolicy::new().ambiguous_wide(true);
+        let items = ["\u{b1}1", "x"];
+        assert_eq!(columns(&items, 5), "\u{b1}1  x");
+        assert_eq!(
+            Columns::new(5).width_policy(cjk).render(&items),
+            "\u{b1}1\nx"
+        );
+    }
+
+    #[test]
+    fn test_kv_block() {
+        let pairs = [("\x1b[1mkey\x1b[0m", "a\nb"), ("longer", "c")];
+        assert_eq!(
+            KvBlock::new().indent(2).render(&pairs),
+            "  \x1b[1mkey\x1b[0m:    a\n          b\n  longer: c"
+        );
+        assert_eq!(
+            KvBlock::new().leaders('·').render(&pairs),
+            "\x1b[1mkey\x1b[0m ······ a\n           b\nlonger ··· c"
+        );
+        assert_eq!(kv_block::<&str, &str>(&[]), "");
+    }
+
+    #[test]
+    fn test_kv_block_wide_keys() {
+        let pairs = [("\u{540d}\u{524d}", "glyphs"), ("name", "x")];
+        assert_eq!(kv_block(&pairs), "\u{540d}\u{524d}: glyphs\nname: x");
+
+        let cjk = WidthPolicy::new().ambiguous_wide(true);
+        let pairs = [("\u{b1}", "a"), ("ab", "b")];
+        assert_eq!(kv_block(&pairs), "\u{b1}:  a\nab: b");
+        assert_eq!(
+            KvBlock::new().width_policy(cjk).render(&pairs),
+            "\u{b1}: a\nab: b"
+        );
+    }
+}
@@ -33,28 +33,193 @@
 //! - **Type-safe** - No string manipulation, just types
 //! - **Human-readable** - Parse ANSI codes to readable descriptions
 //! - **Zero-copy** - Efficient string handling where possible
+//! - **Respects the environment** - `NO_COLOR`, `CLICOLOR`, and `FORCE_COLOR` via [`config`]
 //! - **Brand integration** - Optional Molten brand colors via `brand` feature
+//! - **Color names** - xterm names for the 256-color palette in descriptions via
+//!   `color-names` feature
+//! - **asciinema casts** - Read, rewrite, and write `.cast` v2 files via `cast` feature
+//! - **Log colorizing** - Recolor regex matches in plain or styled text via `regex` feature
+//! - **Async reading** - Parse subprocess output as it arrives via `tokio` feature
+//! - **ANSI art** - Load classic `.ans` files with CP437 translation via `art` feature
+//! - **Logging** - A colored logger for the `log` crate via `log` feature, and a
+//!   `tracing-subscriber` event formatter via `tracing` feature
+//! - **Help screens** - Theme-colored `clap` help via `clap` feature
+//! - **JSON output** - Syntax-colored `serde_json` values via `pretty` feature
+//! - **Fuzzing** - Random valid and malformed ANSI streams in [`testing`] via `proptest`
+//!   and `arbitrary` features
+//! - **Terminfo** - Read color and attribute support from the terminfo database via `terminfo` feature
+//! - **Terminal guards** - RAII raw mode / alternate screen / mouse capture via `term` feature
+//! - **`no_std`** - Colors, styles, sequences, and the parser only need `alloc`;
+//!   disable the default `std` feature to use them on embedded targets
+//!
+//! Without `std` there is no environment to consult: colors are rendered
+//! unless [`set_color_choice`] says otherwise, and the accessibility policy
+//! is always the default one.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![deny(clippy::all)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+extern crate alloc;
+
+mod analyze;
+#[cfg(feature = "art")]
+pub mod art;
+mod badge;
+pub mod banner;
+mod caps;
+#[cfg(feature = "cast")]
+pub mod cast;
+mod cell;
+mod charset;
 mod color;
+mod color_names;
+pub mod config;
+#[cfg(feature = "std")]
+mod console;
+mod control;
+mod describe;
+mod diff;
+pub mod emulate;
+mod error;
 mod escape;
+#[cfg(feature = "std")]
+mod filter;
+mod float;
+#[cfg(feature = "std")]
+mod guard;
+#[cfg(feature = "clap")]
+mod help;
+mod highlight;
+mod humanize;
+mod hyperlink;
+mod keycap;
+mod layout;
+mod live;
+#[cfg(feature = "log")]
+mod logger;
+mod ls_colors;
 mod modifier;
+mod op;
+mod optimize;
 mod parser;
+mod policy;
+#[cfg(feature = "pretty")]
+mod pretty;
+#[cfg(feature = "tokio")]
+mod reader;
+#[cfg(feature = "std")]
+mod recorder;
+mod reference;
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+pub mod replay;
+mod report;
+#[cfg(feature = "std")]
+mod restore;
+mod rule;
 mod sequence;
+mod spec;
 mod style;
-
-pub use color::Color;
+mod stylize;
+#[cfg(feature = "tracing")]
+mod subscriber;
+#[cfg(feature = "term")]
+pub mod term;
+#[cfg(feature = "terminfo")]
+mod terminfo;
+pub mod testing;
+mod text;
+mod theme;
+mod tracker;
+pub mod tree;
+mod validate;
+mod width;
+
+pub use analyze::{analyze, Analysis, Analyzer};
+pub use badge::{badge, key_value_badge, Badge, ROUND_LEFT, ROUND_RIGHT};
+pub use caps::{ColorLevel, TermCaps};
+pub use cell::{cells, Cell};
+pub use charset::{dec_line_drawing, strip_ansi_translated, Charset, CharsetState};
 #[cfg(feature = "brand")]
 pub use color::brand;
-pub use escape::{Escape, EscapeKind};
+pub use color::Color;
+#[cfg(feature = "std")]
+pub use color_names::set_color_names;
+#[cfg(feature = "color-names")]
+pub use color_names::XTERM_COLOR_NAMES;
+pub use color_names::{color_names, ColorNames};
+pub use config::{set_color_choice, ColorChoice};
+#[cfg(feature = "std")]
+pub use console::{Console, Stream};
+pub use control::ControlChar;
+pub use describe::{Describer, EnglishDescriber};
+pub use diff::{diff, DiffMode, InlineDiff};
+pub use error::Error;
+pub use escape::{
+    describe_sgr, DescribeOptions, DisplayMode, Escape, EscapeDetail, EscapeKind, Params, Verbosity,
+};
+#[cfg(feature = "std")]
+pub use filter::{DowngradeWriter, LineResetWriter, SanitizeWriter, StripWriter};
+#[cfg(feature = "std")]
+pub use guard::StyleGuard;
+#[cfg(feature = "clap")]
+pub use help::recolor_help;
+pub use highlight::{render_highlights, Highlighter};
+pub use humanize::{humanize_bytes, humanize_duration, Humanized};
+pub use hyperlink::{hyperlinks, link_or_fallback, Hyperlink, LinkSpan};
+pub use keycap::{keycap, Keycap, KeycapStyle};
+pub use layout::{columns, kv_block, Columns, KvBlock, ELLIPSIS};
+pub use live::{rewrite_line, rewrite_lines, LiveRegion};
+#[cfg(feature = "log")]
+pub use logger::SigilLogger;
+pub use ls_colors::{FileKind, LsColors};
 pub use modifier::Modifier;
-pub use parser::{parse, strip_ansi, visible_len, ParsedSequence};
-pub use sequence::{Sequence, SequenceBuilder};
-pub use style::{style, Style, Styled};
+pub use op::{CursorOp, EraseOp, SgrOp};
+pub use optimize::{optimize, render_spans};
+pub use parser::{
+    parse, parse_bytes, parse_bytes_with, parse_strict, parse_visit, strip_ansi, visible_len,
+    InvalidUtf8, MalformedReason, ParseError, ParsedSequence, StreamParser, Visitor,
+};
+#[cfg(feature = "std")]
+pub use policy::set_policy;
+pub use policy::{policy, Policy};
+#[cfg(feature = "pretty")]
+pub use pretty::pretty_json;
+#[cfg(feature = "tokio")]
+pub use reader::AnsiReader;
+#[cfg(feature = "std")]
+pub use recorder::Recorder;
+pub use reference::SeqInfo;
+#[cfg(feature = "std")]
+pub use registry::{CacheStats, StyleId, StyleRegistry};
+pub use report::Report;
+#[cfg(feature = "std")]
+pub use restore::{install_restore_hook, restore_sequence};
+pub use rule::{rule, titled_rule, RULE_CHAR};
+pub use sequence::{Rect, Sequence, SequenceBuilder};
+pub use spec::SpecError;
+pub use style::{style, ResetMode, SgrPrefix, Style, Styled};
+pub use stylize::Stylize;
+#[cfg(feature = "tracing")]
+pub use subscriber::SigilFormat;
+#[cfg(feature = "term")]
+pub use term::term_size;
+#[cfg(feature = "regex")]
+pub use text::colorize;
+pub use text::{
+    expand_tabs, find_visible, highlight_matches, indent_ansi, prefix_lines, slice_visible,
+    VisibleMatch,
+};
+#[cfg(feature = "std")]
+pub use theme::set_default_theme;
+pub use theme::{Background, LogLevel, Magnitude, Role, Theme};
+pub use tracker::StyleTracker;
+pub use validate::{validate, Issue, IssueKind};
+pub use width::WidthPolicy;
 
 /// CSI (Control Sequence Introducer) prefix.
 pub const CSI: &str = "\x1b[";
@@ -70,6 +235,9 @@ pub const RESET: &str = "\x1b[0m";
 
 /// Common escape sequences.
 pub mod sequences {
+    use alloc::format;
+    use alloc::string::String;
+
     /// Clear the entire screen.
     pub const CLEAR_SCREEN: &str = "\x1b[2J";
 
@@ -120,10 +288,108 @@ pub mod sequences {
 
     /// Disable bracketed paste mode.
     pub const BRACKETED_PASTE_DISABLE: &str = "\x1b[?2004l";
+
+    /// Enable alternate scroll mode (mouse wheel sends arrow keys on the
+    /// alternate screen).
+    pub const ALT_SCROLL_ENABLE: &str = "\x1b[?1007h";
+
+    /// Disable alternate scroll mode.
+    pub const ALT_SCROLL_DISABLE: &str = "\x1b[?1007l";
+
+    /// Reset the scroll region to the whole screen.
+    pub const RESET_SCROLL_REGION: &str = "\x1b[r";
+
+    /// Make the keypad send application sequences (DECKPAM).
+    pub const KEYPAD_APPLICATION: &str = "\x1b=";
+
+    /// Make the keypad send the digits and symbols printed on it (DECKPNM).
+    pub const KEYPAD_NUMERIC: &str = "\x1b>";
+
+    /// Enable focus reporting: the terminal sends [`FOCUS_IN`] and
+    /// [`FOCUS_OUT`] when its window gains or loses focus.
+    pub const FOCUS_REPORTING_ENABLE: &str = "\x1b[?1004h";
+
+    /// Disable focus reporting.
+    pub const FOCUS_REPORTING_DISABLE: &str = "\x1b[?1004l";
+
+    /// Sent by the terminal when it gains focus, with focus reporting on.
+    pub const FOCUS_IN: &str = "\x1b[I";
+
+    /// Sent by the terminal when it loses focus, with focus reporting on.
+    pub const FOCUS_OUT: &str = "\x1b[O";
+
+    /// Full terminal reset (RIS): clears the screen and scrollback and
+    /// resets every mode, like the `reset` command.
+    pub const RESET_TERMINAL: &str = "\x1bc";
+
+    /// Soft terminal reset (DECSTR): resets attributes, modes, and the
+    /// scroll region without clearing the screen.
+    pub const SOFT_RESET: &str = "\x1b[!p";
+
+    /// Draw the current line as the top half of double-height text
+    /// (DECDHL).
+    pub const DOUBLE_HEIGHT_TOP: &str = "\x1b#3";
+
+    /// Draw the current line as the bottom half of double-height text
+    /// (DECDHL).
+    pub const DOUBLE_HEIGHT_BOTTOM: &str = "\x1b#4";
+
+    /// Draw the current line at normal size (DECSWL).
+    pub const SINGLE_WIDTH: &str = "\x1b#5";
+
+    /// Draw the current line with double-width characters (DECDWL).
+    pub const DOUBLE_WIDTH: &str = "\x1b#6";
+
+    /// `text` as double-height text: two lines, the top and bottom halves.
+    ///
+    /// Terminals that don't support line sizes show the text twice at
+    /// normal size. Double-size lines hold half as many characters as the
+    /// screen is wide.
+    ///
+    /// ```rust
+    /// use glyphs::sequences::double_height;
+    ///
+    /// assert_eq!(double_height("Title"), "\x1b#3Title\n\x1b#4Title");
+    /// ```
+    #[must_use]
+    pub fn double_height(text: &str) -> String {
+        format!("{DOUBLE_HEIGHT_TOP}{text}\n{DOUBLE_HEIGHT_BOTTOM}{text}")
+    }
+
+    /// Put a terminal left in a bad state back to normal without clearing
+    /// the screen.
+    ///
+    /// Leaves the alternate screen, turns off mouse tracking and bracketed
+    /// paste, soft-resets ([`SOFT_RESET`]), and then shows the cursor and
+    /// resets attributes for terminals that ignore the soft reset. Write
+    /// [`RESET_TERMINAL`] instead to also clear the screen.
+    ///
+    /// ```rust
+    /// use glyphs::sequences::{cleanup, SOFT_RESET};
+    ///
+    /// print!("{}", cleanup());
+    /// assert!(cleanup().contains(SOFT_RESET));
+    /// ```
+    #[must_use]
+    pub fn cleanup() -> String {
+        format!(
+            "{ALT_SCREEN_EXIT}{MOUSE_DISABLE}{BRACKETED_PASTE_DISABLE}{SOFT_RESET}{CURSOR_SHOW}{}",
+            crate::RESET
+        )
+    }
+
+    /// `text` on a double-width line.
+    #[must_use]
+    pub fn double_width(text: &str) -> String {
+        format!("{DOUBLE_WIDTH}{text}")
+    }
 }
 
 /// Cursor movement helpers.
 pub mod cursor {
+    use alloc::format;
+    use alloc::string::String;
+
     /// Move cursor up by n rows.
     #[must_use]
     pub fn up(n: u16) -> String {
@@ -154,6 +420,12 @@ pub mod cursor {
         format!("\x1b[{row};{col}H")
     }
 
+    /// Restrict scrolling to rows `top` through `bottom` (1-indexed, DECSTBM).
+    #[must_use]
+    pub fn scroll_region(top: u16, bottom: u16) -> String {
+        format!("\x1b[{top};{bottom}r")
+    }
+
     /// Move cursor to specific column (1-indexed).
     #[must_use]
     pub fn column(col: u16) -> String {
@@ -166,16 +438,22 @@ pub mod prelude {
     pub use crate::color::Color;
     pub use crate::modifier::Modifier;
     pub use crate::style::{style, Style, Styled};
+    pub use crate::stylize::Stylize;
     pub use crate::{cursor, sequences, RESET};
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_style_basic() {
-        let s = style("test").fg(Color::Red).to_string();
+        let s = style("test")
+            .fg(Color::Red)
+            .color_level(ColorLevel::Ansi16)
+            .to_string();
         assert!(s.contains("\x1b["));
         assert!(s.contains("test"));
         assert!(s.ends_with(RESET));
@@ -186,4 +464,25 @@ mod tests {
         assert_eq!(cursor::up(5), "\x1b[5A");
         assert_eq!(cursor::goto(10, 20), "\x1b[10;20H");
     }
+
+    #[test]
+    fn test_line_size_sequences() {
+        let banner = sequences::double_height("Hi");
+        let kinds: Vec<EscapeDetail> = parse(&banner)
+            .iter()
+            .filter_map(|s| s.as_escape().map(Escape::detail))
+            .collect();
+        assert_eq!(kinds, [EscapeDetail::LineSize, EscapeDetail::LineSize]);
+        assert_eq!(strip_ansi(&sequences::double_width("Hi")), "Hi");
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let cleanup = sequences::cleanup();
+        assert_eq!(strip_ansi(&cleanup), "");
+        assert!(parse(&cleanup)
+            .iter()
+            .all(|s| s.as_escape().is_some_and(|e| e.kind != EscapeKind::Unknown)));
+        assert!(cleanup.ends_with(RESET));
+    }
 }
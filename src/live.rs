@@ -0,0 +1,165 @@
+//! Rewriting recently printed lines in place, for progress output.
+//!
+//! These helpers only build escape sequences; write them to the terminal
+//! without a trailing newline so the cursor stays on the last line. Lines
+//! wider than the terminal wrap and take more rows than counted, so keep
+//! live content shorter than the terminal width.
+
+use crate::sequences::{CLEAR_LINE_TO_END, CLEAR_TO_END};
+use alloc::string::String;
+
+/// Replace the line the cursor is on with `content`.
+///
+/// Returns to the first column, writes `content`, and erases whatever the
+/// old line had beyond it.
+///
+/// ```rust
+/// use glyphs::rewrite_line;
+///
+/// assert_eq!(rewrite_line("50%"), "\r50%\x1b[0K");
+/// ```
+#[must_use]
+pub fn rewrite_line(content: &str) -> String {
+    let mut out = String::with_capacity(content.len() + 5);
+    out.push('\r');
+    out.push_str(content);
+    out.push_str(CLEAR_LINE_TO_END);
+    out
+}
+
+/// Replace the last `n` printed lines, ending on the cursor's line, with
+/// `contents`.
+///
+/// Old lines beyond the new ones are erased. With `n` of 0 the contents
+/// are written from the cursor's line.
+///
+/// ```rust
+/// use glyphs::rewrite_lines;
+///
+/// assert_eq!(
+///     rewrite_lines(2, &["a: done", "b: 40%"]),
+///     "\r\x1b[1Aa: done\x1b[0K\nb: 40%\x1b[0K\x1b[0J"
+/// );
+/// ```
+#[must_use]
+pub fn rewrite_lines<S: AsRef<str>>(n: usize, contents: &[S]) -> String {
+    let mut out = String::from("\r");
+    if n > 1 {
+        let up = u16::try_from(n - 1).unwrap_or(u16::MAX);
+        out.push_str(&crate::cursor::up(up));
+    }
+    for (i, line) in contents.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(line.as_ref());
+        out.push_str(CLEAR_LINE_TO_END);
+    }
+    out.push_str(CLEAR_TO_END);
+    out
+}
+
+/// A block of lines that is redrawn in place, such as a set of progress
+/// bars.
+///
+/// The region remembers how many lines it last printed, so each update
+/// replaces exactly those.
+///
+/// ```rust
+/// use glyphs::LiveRegion;
+///
+/// let mut region = LiveRegion::new();
+/// print!("{}", region.update("downloading\n 10%"));
+/// print!("{}", region.update("downloading\n 95%"));
+/// print!("{}", region.finish());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LiveRegion {
+    lines: usize,
+}
+
+impl LiveRegion {
+    /// A region that hasn't printed anything yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { lines: 0 }
+    }
+
+    /// How many lines the region currently covers.
+    #[must_use]
+    pub const fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Replace the region with `content`, which may span several lines.
+    #[must_use]
+    pub fn update(&mut self, content: &str) -> String {
+        let lines: alloc::vec::Vec<&str> = content.split('\n').collect();
+        let out = rewrite_lines(self.lines, &lines);
+        self.lines = lines.len();
+        out
+    }
+
+    /// Erase the region, leaving the cursor where it started.
+    #[must_use]
+    pub fn clear(&mut self) -> String {
+        let out = rewrite_lines::<&str>(self.lines, &[]);
+        self.lines = 0;
+        out
+    }
+
+    /// Keep the region's content on screen and move below it, so the
+    /// next update starts a new region.
+    #[must_use]
+    pub fn finish(&mut self) -> String {
+        let out = if self.lines > 0 {
+            String::from("\n")
+        } else {
+            String::new()
+        };
+        self.lines = 0;
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulate::TerminalState;
+
+    fn screen(output: &[String]) -> alloc::vec::Vec<String> {
+        let mut term = TerminalState::new(20, 5);
+        for chunk in output {
+            term.feed(chunk);
+        }
+        term.plain_lines()
+    }
+
+    #[test]
+    fn test_rewrite_line() {
+        let out = [
+            String::from("log\n"),
+            rewrite_line("loading....."),
+            rewrite_line("done"),
+        ];
+        assert_eq!(screen(&out)[..2], ["log", "done"]);
+    }
+
+    #[test]
+    fn test_live_region() {
+        let mut region = LiveRegion::new();
+        let mut out = alloc::vec![String::from("start\n")];
+        out.push(region.update("a: 10%\nb: 20%\nc: 30%"));
+        out.push(region.update("a: done\nb: 90%"));
+        assert_eq!(region.lines(), 2);
+        assert_eq!(screen(&out), ["start", "a: done", "b: 90%", "", ""]);
+
+        out.push(region.finish());
+        out.push(region.update("next"));
+        assert_eq!(screen(&out), ["start", "a: done", "b: 90%", "next", ""]);
+
+        out.push(region.clear());
+        out.push(String::from("end"));
+        assert_eq!(screen(&out), ["start", "a: done", "b: 90%", "end", ""]);
+    }
+}
@@ -0,0 +1,167 @@
+//! A colored logger for the `log` crate (requires the `log` feature).
+//!
+//! [`SigilLogger`] writes `LEVEL target: message` lines to stderr, styling
+//! the level with [`Theme::for_log_level`] and dimming the target. Colors
+//! follow the [`Console`] for stderr, so they are dropped when stderr is
+//! redirected or `NO_COLOR` is set.
+//!
+//! ```rust,no_run
+//! use glyphs::SigilLogger;
+//!
+//! SigilLogger::new().init()?;
+//! log::warn!("disk almost full");
+//! # Ok::<(), log::SetLoggerError>(())
+//! ```
+//!
+//! The same formatting can be plugged into other loggers through
+//! [`SigilLogger::format`]:
+//!
+//! ```rust,ignore
+//! let sigil = glyphs::SigilLogger::new();
+//! env_logger::Builder::from_default_env()
+//!     .format(move |buf, record| writeln!(buf, "{}", sigil.format(record)))
+//!     .init();
+//! ```
+
+use crate::caps::ColorLevel;
+use crate::console::Console;
+use crate::style::Style;
+use crate::theme::{LogLevel, Theme};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::io::Write;
+
+/// A [`log::Log`] implementation that writes styled lines to stderr.
+#[derive(Debug, Clone, Copy)]
+pub struct SigilLogger {
+    theme: Theme,
+    level: LevelFilter,
+    console: Console,
+}
+
+impl SigilLogger {
+    /// A logger for info and above, using [`Theme::current`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::current(),
+            level: LevelFilter::Info,
+            console: Console::stderr(),
+        }
+    }
+
+    /// Use `theme` for the level labels and target.
+    #[must_use]
+    pub const fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Only log messages at `level` or more severe.
+    #[must_use]
+    pub const fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Decide colors with `console` instead of the stderr console.
+    #[must_use]
+    pub const fn console(mut self, console: Console) -> Self {
+        self.console = console;
+        self
+    }
+
+    /// Format a record as `LEVEL target: message`, without a newline.
+    ///
+    /// Debug and trace messages are shown in the muted style.
+    #[must_use]
+    pub fn format(&self, record: &Record<'_>) -> String {
+        let level = LogLevel::from(record.level());
+        let label = format!("{:<5}", level.as_str());
+        let message = record.args().to_string();
+        let message_style = match level {
+            LogLevel::Debug | LogLevel::Trace => self.theme.muted,
+            _ => Style::new(),
+        };
+        format!(
+            "{} {}: {}",
+            self.paint(&label, self.theme.for_log_level(level)),
+            self.paint(record.target(), self.theme.muted),
+            self.paint(&message, message_style),
+        )
+    }
+
+    fn paint(&self, text: &str, style: Style) -> String {
+        match self.console.color_level() {
+            ColorLevel::None => text.to_string(),
+            level => style.downgrade(level).apply(text),
+        }
+    }
+
+    /// Install this logger as the global logger and set the maximum level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a logger was already installed.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        log::set_max_level(self.level);
+        log::set_boxed_logger(Box::new(self))
+    }
+}
+
+impl Default for SigilLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Log for SigilLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let _ = writeln!(std::io::stderr().lock(), "{}", self.format(record));
+        }
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ColorChoice;
+    use log::Level;
+
+    fn format(logger: &SigilLogger, level: Level, message: &str) -> String {
+        logger.format(
+            &Record::builder()
+                .level(level)
+                .target("app::db")
+                .args(format_args!("{message}"))
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_format_plain() {
+        let logger =
+            SigilLogger::new().console(Console::stderr().with_color_choice(ColorChoice::Never));
+        assert_eq!(
+            format(&logger, Level::Warn, "slow query"),
+            "WARN  app::db: slow query"
+        );
+        assert_eq!(format(&logger, Level::Error, "down"), "ERROR app::db: down");
+    }
+
+    #[test]
+    fn test_enabled() {
+        let logger = SigilLogger::new().level(LevelFilter::Warn);
+        let metadata = |level| Metadata::builder().level(level).build();
+        assert!(logger.enabled(&metadata(Level::Error)));
+        assert!(!logger.enabled(&metadata(Level::Info)));
+    }
+}
@@ -0,0 +1,320 @@
+//! File name colors from `LS_COLORS`, as written by GNU `dircolors`.
+//!
+//! `LS_COLORS` is a `:`-separated list of `key=SGR` entries. Two-letter
+//! keys such as `di` color a [`FileKind`]; `*suffix` keys color regular
+//! files whose names end with the suffix:
+//!
+//! ```rust
+//! use glyphs::{strip_ansi, Color, FileKind, LsColors, Modifier, Style};
+//!
+//! let colors = LsColors::parse("di=01;34:ex=01;32:*.tar=01;31");
+//! assert_eq!(
+//!     colors.style_for("src", FileKind::Directory),
+//!     Some(Style::new().fg(Color::Blue).modifier(Modifier::Bold))
+//! );
+//! let archive = colors.style_for("backup.TAR", FileKind::File);
+//! assert_eq!(archive.and_then(|s| s.foreground), Some(Color::Red));
+//! assert_eq!(colors.style_for("notes.txt", FileKind::File), None);
+//! assert_eq!(strip_ansi(&colors.paint("build.sh", FileKind::Executable)), "build.sh");
+//! ```
+
+use crate::style::{parse_sgr_value, Style};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The kinds of file `LS_COLORS` has keys for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// Anything without a more specific kind (`no`).
+    Normal,
+    /// A regular file (`fi`).
+    File,
+    /// A directory (`di`).
+    Directory,
+    /// A symbolic link (`ln`).
+    Symlink,
+    /// A named pipe (`pi`).
+    Fifo,
+    /// A socket (`so`).
+    Socket,
+    /// A door, on Solaris (`do`).
+    Door,
+    /// A block device (`bd`).
+    BlockDevice,
+    /// A character device (`cd`).
+    CharDevice,
+    /// A symbolic link to a missing file (`or`).
+    Orphan,
+    /// The missing target of an orphaned link (`mi`).
+    Missing,
+    /// A set-user-ID file (`su`).
+    Setuid,
+    /// A set-group-ID file (`sg`).
+    Setgid,
+    /// A file with capabilities (`ca`).
+    Capability,
+    /// A sticky, other-writable directory (`tw`).
+    StickyOtherWritable,
+    /// An other-writable directory (`ow`).
+    OtherWritable,
+    /// A sticky directory (`st`).
+    Sticky,
+    /// An executable file (`ex`).
+    Executable,
+    /// A regular file with several hard links (`mh`).
+    MultiHardlink,
+}
+
+impl FileKind {
+    /// Every kind, in `dircolors` order.
+    const ALL: [Self; 19] = [
+        Self::Normal,
+        Self::File,
+        Self::Directory,
+        Self::Symlink,
+        Self::Fifo,
+        Self::Socket,
+        Self::Door,
+        Self::BlockDevice,
+        Self::CharDevice,
+        Self::Orphan,
+        Self::Missing,
+        Self::Setuid,
+        Self::Setgid,
+        Self::Capability,
+        Self::StickyOtherWritable,
+        Self::OtherWritable,
+        Self::Sticky,
+        Self::Executable,
+        Self::MultiHardlink,
+    ];
+
+    /// The `LS_COLORS` key, such as `di`.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Normal => "no",
+            Self::File => "fi",
+            Self::Directory => "di",
+            Self::Symlink => "ln",
+            Self::Fifo => "pi",
+            Self::Socket => "so",
+            Self::Door => "do",
+            Self::BlockDevice => "bd",
+            Self::CharDevice => "cd",
+            Self::Orphan => "or",
+            Self::Missing => "mi",
+            Self::Setuid => "su",
+            Self::Setgid => "sg",
+            Self::Capability => "ca",
+            Self::StickyOtherWritable => "tw",
+            Self::OtherWritable => "ow",
+            Self::Sticky => "st",
+            Self::Executable => "ex",
+            Self::MultiHardlink => "mh",
+        }
+    }
+
+    /// The kind for an `LS_COLORS` key.
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+
+    /// The broader kind to use when this one has no color.
+    const fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Normal | Self::Missing => None,
+            Self::Orphan => Some(Self::Symlink),
+            Self::StickyOtherWritable | Self::OtherWritable | Self::Sticky => Some(Self::Directory),
+            Self::Setuid
+            | Self::Setgid
+            | Self::Capability
+            | Self::Executable
+            | Self::MultiHardlink => Some(Self::File),
+            _ => Some(Self::Normal),
+        }
+    }
+
+    /// Classify a file from its metadata, as `ls` would.
+    ///
+    /// Use [`std::fs::symlink_metadata`] so links are reported as links.
+    /// File capabilities and hard link counts aren't checked, and outside
+    /// Unix only links, directories, and files are told apart.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            return Self::Symlink;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+            let mode = metadata.permissions().mode();
+            if file_type.is_dir() {
+                return match (mode & 0o1000 != 0, mode & 0o002 != 0) {
+                    (true, true) => Self::StickyOtherWritable,
+                    (false, true) => Self::OtherWritable,
+                    (true, false) => Self::Sticky,
+                    (false, false) => Self::Directory,
+                };
+            }
+            if file_type.is_fifo() {
+                return Self::Fifo;
+            }
+            if file_type.is_socket() {
+                return Self::Socket;
+            }
+            if file_type.is_block_device() {
+                return Self::BlockDevice;
+            }
+            if file_type.is_char_device() {
+                return Self::CharDevice;
+            }
+            if mode & 0o4000 != 0 {
+                return Self::Setuid;
+            }
+            if mode & 0o2000 != 0 {
+                return Self::Setgid;
+            }
+            if mode & 0o111 != 0 {
+                return Self::Executable;
+            }
+        }
+        if file_type.is_dir() {
+            Self::Directory
+        } else {
+            Self::File
+        }
+    }
+}
+
+/// Parsed `LS_COLORS` entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LsColors {
+    kinds: Vec<(FileKind, Style)>,
+    suffixes: Vec<(String, Style)>,
+}
+
+impl LsColors {
+    /// Parse an `LS_COLORS` value.
+    ///
+    /// Entries that can't be parsed are skipped, as `ls` does, including
+    /// `ln=target`, which needs the link's target to resolve.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let mut colors = Self::default();
+        for entry in value.split(':') {
+            let Some((key, sgr)) = entry.split_once('=') else {
+                continue;
+            };
+            let Some(style) = parse_sgr_value(sgr) else {
+                continue;
+            };
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors.suffixes.push((String::from(suffix), style));
+            } else if let Some(kind) = FileKind::from_key(key) {
+                colors.kinds.retain(|(k, _)| *k != kind);
+                colors.kinds.push((kind, style));
+            }
+        }
+        colors
+    }
+
+    /// Parse the `LS_COLORS` environment variable, if it is set.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        std::env::var("LS_COLORS")
+            .ok()
+            .map(|value| Self::parse(&value))
+    }
+
+    /// The style set for `kind` itself, without falling back.
+    #[must_use]
+    pub fn style_for_kind(&self, kind: FileKind) -> Option<Style> {
+        self.kinds.iter().find(|(k, _)| *k == kind).map(|(_, s)| *s)
+    }
+
+    /// The style of the last `*suffix` entry matching `name`.
+    ///
+    /// A match with the same case wins; otherwise case is ignored, as in
+    /// recent GNU `ls`.
+    #[must_use]
+    pub fn style_for_name(&self, name: &str) -> Option<Style> {
+        let ends_with_ignore_case = |suffix: &str| {
+            name.len() >= suffix.len()
+                && name.is_char_boundary(name.len() - suffix.len())
+                && name[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+        };
+        let entries = || self.suffixes.iter().rev();
+        entries()
+            .find(|(suffix, _)| name.ends_with(suffix.as_str()))
+            .or_else(|| entries().find(|(suffix, _)| ends_with_ignore_case(suffix)))
+            .map(|(_, s)| *s)
+    }
+
+    /// The style `ls` would give a file called `name` of `kind`.
+    ///
+    /// Regular files are matched against the `*suffix` entries first.
+    /// Kinds without a color of their own fall back to a broader one:
+    /// executables and other special files to `fi`, special directories
+    /// to `di`, orphans to `ln`, and everything to `no`.
+    #[must_use]
+    pub fn style_for(&self, name: &str, kind: FileKind) -> Option<Style> {
+        let mut kind = kind;
+        loop {
+            if kind == FileKind::File {
+                if let Some(style) = self.style_for_name(name) {
+                    return Some(style);
+                }
+            }
+            if let Some(style) = self.style_for_kind(kind) {
+                return Some(style);
+            }
+            kind = kind.fallback()?;
+        }
+    }
+
+    /// `name` in the style from [`LsColors::style_for`], or unstyled.
+    #[must_use]
+    pub fn paint(&self, name: &str, kind: FileKind) -> String {
+        match self.style_for(name, kind) {
+            Some(style) => style.apply(name),
+            None => String::from(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    #[test]
+    fn test_kind_keys() {
+        for kind in FileKind::ALL {
+            assert_eq!(FileKind::from_key(kind.key()), Some(kind));
+        }
+        assert_eq!(FileKind::from_key("rs"), None);
+    }
+
+    #[test]
+    fn test_style_for() {
+        let colors =
+            LsColors::parse("rs=0:no=33:di=34:ln=target:*.gz=31:*.GZ=32:bad:fi=1;x:*.tar.gz=35");
+        let fg = |name, kind| colors.style_for(name, kind).and_then(|s| s.foreground);
+        assert_eq!(fg("a.tar.gz", FileKind::File), Some(Color::Magenta));
+        assert_eq!(fg("a.gz", FileKind::File), Some(Color::Red));
+        assert_eq!(fg("a.GZ", FileKind::File), Some(Color::Green));
+        assert_eq!(fg("a.Gz", FileKind::File), Some(Color::Green));
+        assert_eq!(fg("a.txt", FileKind::File), Some(Color::Yellow));
+        assert_eq!(fg("a.gz", FileKind::Executable), Some(Color::Red));
+        assert_eq!(fg("tmp", FileKind::StickyOtherWritable), Some(Color::Blue));
+        assert_eq!(fg("link", FileKind::Orphan), Some(Color::Yellow));
+        assert_eq!(colors.style_for("gone", FileKind::Missing), None);
+        assert_eq!(colors.paint("gone", FileKind::Missing), "gone");
+    }
+}
@@ -1,6 +1,7 @@
 //! Text modifiers (bold, italic, underline, etc.).
 
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 /// Text style modifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -27,9 +28,39 @@ pub enum Modifier {
     DoubleUnderline,
     /// Overlined text.
     Overline,
+    /// Fraktur (Gothic) font.
+    Fraktur,
+    /// Framed text.
+    Framed,
+    /// Encircled text.
+    Encircled,
+    /// Superscript text.
+    Superscript,
+    /// Subscript text.
+    Subscript,
 }
 
 impl Modifier {
+    /// Every modifier, in the order their codes are emitted.
+    pub(crate) const ALL: [Self; 16] = [
+        Self::Bold,
+        Self::Dim,
+        Self::Italic,
+        Self::Underline,
+        Self::Blink,
+        Self::RapidBlink,
+        Self::Reverse,
+        Self::Hidden,
+        Self::Strikethrough,
+        Self::Fraktur,
+        Self::DoubleUnderline,
+        Self::Framed,
+        Self::Encircled,
+        Self::Overline,
+        Self::Superscript,
+        Self::Subscript,
+    ];
+
     /// Get the ANSI SGR code to enable this modifier.
     #[must_use]
     pub const fn on_code(&self) -> u8 {
@@ -43,8 +74,37 @@ impl Modifier {
             Self::Reverse => 7,
             Self::Hidden => 8,
             Self::Strikethrough => 9,
+            Self::Fraktur => 20,
             Self::DoubleUnderline => 21,
+            Self::Framed => 51,
+            Self::Encircled => 52,
             Self::Overline => 53,
+            Self::Superscript => 73,
+            Self::Subscript => 74,
+        }
+    }
+
+    /// The modifier enabled by an SGR code; the inverse of [`Modifier::on_code`].
+    #[must_use]
+    pub const fn from_on_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::Bold),
+            2 => Some(Self::Dim),
+            3 => Some(Self::Italic),
+            4 => Some(Self::Underline),
+            5 => Some(Self::Blink),
+            6 => Some(Self::RapidBlink),
+            7 => Some(Self::Reverse),
+            8 => Some(Self::Hidden),
+            9 => Some(Self::Strikethrough),
+            20 => Some(Self::Fraktur),
+            21 => Some(Self::DoubleUnderline),
+            51 => Some(Self::Framed),
+            52 => Some(Self::Encircled),
+            53 => Some(Self::Overline),
+            73 => Some(Self::Superscript),
+            74 => Some(Self::Subscript),
+            _ => None,
         }
     }
 
@@ -53,13 +113,15 @@ impl Modifier {
     pub const fn off_code(&self) -> u8 {
         match self {
             Self::Bold | Self::Dim => 22,
-            Self::Italic => 23,
+            Self::Italic | Self::Fraktur => 23,
             Self::Underline | Self::DoubleUnderline => 24,
             Self::Blink | Self::RapidBlink => 25,
             Self::Reverse => 27,
             Self::Hidden => 28,
             Self::Strikethrough => 29,
+            Self::Framed | Self::Encircled => 54,
             Self::Overline => 55,
+            Self::Superscript | Self::Subscript => 75,
         }
     }
 
@@ -78,6 +140,11 @@ impl Modifier {
             Self::Strikethrough => "strikethrough",
             Self::DoubleUnderline => "double underline",
             Self::Overline => "overline",
+            Self::Fraktur => "fraktur",
+            Self::Framed => "framed",
+            Self::Encircled => "encircled",
+            Self::Superscript => "superscript",
+            Self::Subscript => "subscript",
         }
     }
 }
@@ -89,13 +156,13 @@ impl fmt::Display for Modifier {
 }
 
 /// A set of modifiers.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct ModifierSet {
     bits: u16,
 }
 
 impl ModifierSet {
-    // Bit positions for each modifier (0-10, fitting in u16)
+    // Bit positions for each modifier (0-15, fitting in u16)
     const BOLD_BIT: u16 = 0;
     const DIM_BIT: u16 = 1;
     const ITALIC_BIT: u16 = 2;
@@ -107,6 +174,11 @@ impl ModifierSet {
     const STRIKETHROUGH_BIT: u16 = 8;
     const DOUBLE_UNDERLINE_BIT: u16 = 9;
     const OVERLINE_BIT: u16 = 10;
+    const FRAKTUR_BIT: u16 = 11;
+    const FRAMED_BIT: u16 = 12;
+    const ENCIRCLED_BIT: u16 = 13;
+    const SUPERSCRIPT_BIT: u16 = 14;
+    const SUBSCRIPT_BIT: u16 = 15;
 
     const fn bit_for(modifier: Modifier) -> u16 {
         match modifier {
@@ -121,6 +193,11 @@ impl ModifierSet {
             Modifier::Strikethrough => Self::STRIKETHROUGH_BIT,
             Modifier::DoubleUnderline => Self::DOUBLE_UNDERLINE_BIT,
             Modifier::Overline => Self::OVERLINE_BIT,
+            Modifier::Fraktur => Self::FRAKTUR_BIT,
+            Modifier::Framed => Self::FRAMED_BIT,
+            Modifier::Encircled => Self::ENCIRCLED_BIT,
+            Modifier::Superscript => Self::SUPERSCRIPT_BIT,
+            Modifier::Subscript => Self::SUBSCRIPT_BIT,
         }
     }
 
@@ -138,6 +215,22 @@ impl ModifierSet {
         }
     }
 
+    /// Remove a modifier from the set.
+    #[must_use]
+    pub const fn without(self, modifier: Modifier) -> Self {
+        Self {
+            bits: self.bits & !(1 << Self::bit_for(modifier)),
+        }
+    }
+
+    /// All modifiers in either set.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
+
     /// Check if a modifier is in the set.
     #[must_use]
     pub const fn contains(self, modifier: Modifier) -> bool {
@@ -147,20 +240,63 @@ impl ModifierSet {
     /// Get all enabled modifiers.
     #[must_use]
     pub fn modifiers(self) -> Vec<Modifier> {
-        let all = [
-            Modifier::Bold,
-            Modifier::Dim,
-            Modifier::Italic,
-            Modifier::Underline,
-            Modifier::Blink,
-            Modifier::RapidBlink,
-            Modifier::Reverse,
-            Modifier::Hidden,
-            Modifier::Strikethrough,
-            Modifier::DoubleUnderline,
-            Modifier::Overline,
-        ];
-        all.into_iter().filter(|m| self.contains(*m)).collect()
+        self.iter().collect()
+    }
+
+    /// Iterate over the enabled modifiers without allocating.
+    pub fn iter(self) -> impl Iterator<Item = Modifier> {
+        Modifier::ALL.into_iter().filter(move |m| self.contains(*m))
+    }
+
+    /// Build the set left enabled by a sequence of SGR parameters.
+    ///
+    /// Off-codes remove the modifiers they cancel, `0` clears the set, and
+    /// the arguments of extended colors (`38;5;n`, `48;2;r;g;b`) are skipped.
+    #[must_use]
+    pub fn from_sgr_params(params: &[u32]) -> Self {
+        let mut set = Self::empty();
+        let mut i = 0;
+        while i < params.len() {
+            let code = params[i];
+            i += match (code, params.get(i + 1)) {
+                (38 | 48 | 58, Some(5)) => 3,
+                (38 | 48 | 58, Some(2)) => 5,
+                _ => 1,
+            };
+            set = set.apply_code(code);
+        }
+        set
+    }
+
+    /// Update the set for one SGR code; codes that aren't about modifiers
+    /// are ignored.
+    pub(crate) fn apply_code(self, code: u32) -> Self {
+        if code == 0 {
+            return Self::empty();
+        }
+        let Ok(code) = u8::try_from(code) else {
+            return self;
+        };
+        match Modifier::from_on_code(code) {
+            // Superscript and subscript replace each other.
+            Some(Modifier::Superscript) => self
+                .without(Modifier::Subscript)
+                .with(Modifier::Superscript),
+            Some(Modifier::Subscript) => self
+                .without(Modifier::Superscript)
+                .with(Modifier::Subscript),
+            Some(modifier) => self.with(modifier),
+            None => Modifier::ALL
+                .into_iter()
+                .filter(|m| m.off_code() == code)
+                .fold(self, Self::without),
+        }
+    }
+
+    /// Check if no modifiers are set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
     }
 
     /// Get the ANSI codes for all enabled modifiers.
@@ -180,6 +316,8 @@ mod tests {
         assert_eq!(Modifier::Bold.on_code(), 1);
         assert_eq!(Modifier::Bold.off_code(), 22);
         assert_eq!(Modifier::Underline.on_code(), 4);
+        assert_eq!(Modifier::Superscript.on_code(), 73);
+        assert_eq!(Modifier::Subscript.off_code(), 75);
     }
 
     #[test]
@@ -187,9 +325,21 @@ mod tests {
         let set = ModifierSet::empty()
             .with(Modifier::Bold)
             .with(Modifier::Italic);
-        
+
         assert!(set.contains(Modifier::Bold));
         assert!(set.contains(Modifier::Italic));
         assert!(!set.contains(Modifier::Underline));
     }
+
+    #[test]
+    fn test_from_sgr() {
+        for modifier in Modifier::ALL {
+            assert_eq!(Modifier::from_on_code(modifier.on_code()), Some(modifier));
+        }
+        assert_eq!(Modifier::from_on_code(31), None);
+
+        let set = ModifierSet::from_sgr_params(&[1, 2, 3, 38, 5, 4, 22, 48, 2, 1, 7, 9]);
+        assert_eq!(set, ModifierSet::empty().with(Modifier::Italic));
+        assert!(ModifierSet::from_sgr_params(&[1, 0]).is_empty());
+    }
 }
@@ -0,0 +1,262 @@
+//! Typed operations decoded from escape sequences.
+
+use crate::color::{self, Color};
+use crate::escape::{Escape, EscapeDetail, EscapeKind};
+use crate::modifier::Modifier;
+use crate::style::extended_color;
+use alloc::vec::Vec;
+
+/// A cursor operation, from [`Escape::cursor_op`].
+///
+/// Counts and positions are 1-based, and a zero parameter reads as `1`
+/// like it does on a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorOp {
+    /// Move up `n` rows (`CSI n A`).
+    Up(u32),
+    /// Move down `n` rows (`CSI n B`).
+    Down(u32),
+    /// Move right `n` columns (`CSI n C`).
+    Forward(u32),
+    /// Move left `n` columns (`CSI n D`).
+    Back(u32),
+    /// Move to a column of the current row (`CSI n G`).
+    Column(u32),
+    /// Move to a row and column (`CSI row ; col H`).
+    Goto(u32, u32),
+    /// Save the cursor position (`ESC 7`, `CSI s`).
+    Save,
+    /// Restore the saved cursor position (`ESC 8`, `CSI u`).
+    Restore,
+}
+
+/// An erase operation, from [`Escape::erase_op`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EraseOp {
+    /// From the cursor to the end of the screen (`CSI 0 J`).
+    ToScreenEnd,
+    /// From the start of the screen to the cursor (`CSI 1 J`).
+    ToScreenStart,
+    /// The whole screen (`CSI 2 J`).
+    Screen,
+    /// The whole screen and the scrollback (`CSI 3 J`).
+    ScreenAndScrollback,
+    /// From the cursor to the end of the line (`CSI 0 K`).
+    ToLineEnd,
+    /// From the start of the line to the cursor (`CSI 1 K`).
+    ToLineStart,
+    /// The whole line (`CSI 2 K`).
+    Line,
+}
+
+/// One attribute change in an SGR sequence, from [`Escape::sgr_ops`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SgrOp {
+    /// Reset all attributes (`0`).
+    Reset,
+    /// Turn a modifier on (`1` for bold, ...).
+    Add(Modifier),
+    /// Turn a modifier off (`22` removes both bold and dim).
+    Remove(Modifier),
+    /// Set the foreground color.
+    Foreground(Color),
+    /// Go back to the default foreground color (`39`).
+    DefaultForeground,
+    /// Set the background color.
+    Background(Color),
+    /// Go back to the default background color (`49`).
+    DefaultBackground,
+    /// Set the underline color (`58;…`).
+    UnderlineColor(Color),
+    /// Go back to the default underline color (`59`).
+    DefaultUnderlineColor,
+    /// A code without a typed form, such as a font (`10`–`19`) or a
+    /// truncated extended color.
+    Other(u32),
+}
+
+impl Escape {
+    /// The cursor operation this sequence performs, if it moves, saves, or
+    /// restores the cursor.
+    ///
+    /// ```rust
+    /// use glyphs::{parse, CursorOp};
+    ///
+    /// let escape = parse("\x1b[5;10H")[0].as_escape().cloned().unwrap();
+    /// assert_eq!(escape.cursor_op(), Some(CursorOp::Goto(5, 10)));
+    /// ```
+    #[must_use]
+    pub fn cursor_op(&self) -> Option<CursorOp> {
+        let count = |i: usize| self.params.get(i).map_or(1, |&p| p.max(1));
+        match self.detail() {
+            EscapeDetail::CursorSave => return Some(CursorOp::Save),
+            EscapeDetail::CursorRestore => return Some(CursorOp::Restore),
+            EscapeDetail::CursorMove => {}
+            _ => return None,
+        }
+        let op = match self.raw.chars().last()? {
+            'A' => CursorOp::Up(count(0)),
+            'B' => CursorOp::Down(count(0)),
+            'C' => CursorOp::Forward(count(0)),
+            'D' => CursorOp::Back(count(0)),
+            'G' => CursorOp::Column(count(0)),
+            'H' | 'f' => CursorOp::Goto(count(0), count(1)),
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// The erase operation this sequence performs, if it is a line or
+    /// screen erase with a known mode.
+    ///
+    /// ```rust
+    /// use glyphs::{parse, EraseOp};
+    ///
+    /// let escape = parse("\x1b[2K")[0].as_escape().cloned().unwrap();
+    /// assert_eq!(escape.erase_op(), Some(EraseOp::Line));
+    /// ```
+    #[must_use]
+    pub fn erase_op(&self) -> Option<EraseOp> {
+        let mode = self.params.first().copied().unwrap_or(0);
+        match (self.detail(), mode) {
+            (EscapeDetail::EraseScreen, 0) => Some(EraseOp::ToScreenEnd),
+            (EscapeDetail::EraseScreen, 1) => Some(EraseOp::ToScreenStart),
+            (EscapeDetail::EraseScreen, 2) => Some(EraseOp::Screen),
+            (EscapeDetail::EraseScreen, 3) => Some(EraseOp::ScreenAndScrollback),
+            (EscapeDetail::EraseLine, 0) => Some(EraseOp::ToLineEnd),
+            (EscapeDetail::EraseLine, 1) => Some(EraseOp::ToLineStart),
+            (EscapeDetail::EraseLine, 2) => Some(EraseOp::Line),
+            _ => None,
+        }
+    }
+
+    /// The attribute changes of an SGR sequence, in order, or `None` for
+    /// other sequences.
+    ///
+    /// An empty parameter list is a reset. Parameters kept in
+    /// [`Escape::unparsed`] are not included.
+    ///
+    /// ```rust
+    /// use glyphs::{parse, Color, Modifier, SgrOp};
+    ///
+    /// let escape = parse("\x1b[1;38;5;208m")[0].as_escape().cloned().unwrap();
+    /// assert_eq!(
+    ///     escape.sgr_ops(),
+    ///     Some(vec![SgrOp::Add(Modifier::Bold), SgrOp::Foreground(Color::Ansi256(208))])
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn sgr_ops(&self) -> Option<Vec<SgrOp>> {
+        if self.kind != EscapeKind::Sgr {
+            return None;
+        }
+        if self.params.is_empty() {
+            return Some(alloc::vec![SgrOp::Reset]);
+        }
+
+        let mut ops = Vec::new();
+        let mut i = 0;
+        while i < self.params.len() {
+            let code = self.params[i];
+            i += 1;
+            match code {
+                0 => ops.push(SgrOp::Reset),
+                30..=37 => ops.push(SgrOp::Foreground(color::basic((code - 30) as u8))),
+                90..=97 => ops.push(SgrOp::Foreground(color::basic((code - 82) as u8))),
+                39 => ops.push(SgrOp::DefaultForeground),
+                40..=47 => ops.push(SgrOp::Background(color::basic((code - 40) as u8))),
+                100..=107 => ops.push(SgrOp::Background(color::basic((code - 92) as u8))),
+                49 => ops.push(SgrOp::DefaultBackground),
+                59 => ops.push(SgrOp::DefaultUnderlineColor),
+                38 | 48 | 58 => {
+                    let (color, used) = extended_color(&self.params[i..]);
+                    i += used;
+                    ops.push(match (code, color) {
+                        (38, Some(color)) => SgrOp::Foreground(color),
+                        (48, Some(color)) => SgrOp::Background(color),
+                        (_, Some(color)) => SgrOp::UnderlineColor(color),
+                        (_, None) => SgrOp::Other(code),
+                    });
+                }
+                _ => push_modifier_ops(&mut ops, code),
+            }
+        }
+        Some(ops)
+    }
+}
+
+fn push_modifier_ops(ops: &mut Vec<SgrOp>, code: u32) {
+    let Ok(byte) = u8::try_from(code) else {
+        ops.push(SgrOp::Other(code));
+        return;
+    };
+    if let Some(modifier) = Modifier::from_on_code(byte) {
+        ops.push(SgrOp::Add(modifier));
+        return;
+    }
+    let before = ops.len();
+    ops.extend(
+        Modifier::ALL
+            .into_iter()
+            .filter(|m| m.off_code() == byte)
+            .map(SgrOp::Remove),
+    );
+    if ops.len() == before {
+        ops.push(SgrOp::Other(code));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use alloc::vec;
+
+    fn escape(input: &str) -> Escape {
+        parse(input)[0].as_escape().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_cursor_op() {
+        assert_eq!(escape("\x1b[A").cursor_op(), Some(CursorOp::Up(1)));
+        assert_eq!(escape("\x1b[0C").cursor_op(), Some(CursorOp::Forward(1)));
+        assert_eq!(escape("\x1b[3D").cursor_op(), Some(CursorOp::Back(3)));
+        assert_eq!(escape("\x1b[H").cursor_op(), Some(CursorOp::Goto(1, 1)));
+        assert_eq!(escape("\x1b[7G").cursor_op(), Some(CursorOp::Column(7)));
+        assert_eq!(escape("\x1b7").cursor_op(), Some(CursorOp::Save));
+        assert_eq!(escape("\x1b[u").cursor_op(), Some(CursorOp::Restore));
+        assert_eq!(escape("\x1b[2 q").cursor_op(), None);
+        assert_eq!(escape("\x1b[2J").cursor_op(), None);
+    }
+
+    #[test]
+    fn test_erase_op() {
+        assert_eq!(escape("\x1b[J").erase_op(), Some(EraseOp::ToScreenEnd));
+        assert_eq!(
+            escape("\x1b[3J").erase_op(),
+            Some(EraseOp::ScreenAndScrollback)
+        );
+        assert_eq!(escape("\x1b[1K").erase_op(), Some(EraseOp::ToLineStart));
+        assert_eq!(escape("\x1b[9K").erase_op(), None);
+        assert_eq!(escape("\x1b[31m").erase_op(), None);
+    }
+
+    #[test]
+    fn test_sgr_ops() {
+        assert_eq!(escape("\x1b[m").sgr_ops(), Some(vec![SgrOp::Reset]));
+        assert_eq!(
+            escape("\x1b[22;91;49;58;2;1;2;3;10m").sgr_ops(),
+            Some(vec![
+                SgrOp::Remove(Modifier::Bold),
+                SgrOp::Remove(Modifier::Dim),
+                SgrOp::Foreground(Color::BrightRed),
+                SgrOp::DefaultBackground,
+                SgrOp::UnderlineColor(Color::rgb(1, 2, 3)),
+                SgrOp::Other(10),
+            ])
+        );
+        assert_eq!(escape("\x1b[38;5m").sgr_ops(), Some(vec![SgrOp::Other(38)]));
+        assert_eq!(escape("\x1b[2J").sgr_ops(), None);
+    }
+}
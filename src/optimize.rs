@@ -0,0 +1,315 @@
+//! Removing redundant SGR sequences from rendered output.
+
+use crate::config;
+use crate::escape::{Escape, EscapeKind};
+use crate::modifier::Modifier;
+use crate::parser::{parse, ParsedSequence};
+use crate::policy;
+use crate::style::Style;
+use crate::{CSI, RESET, SGR_SUFFIX};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Remove redundant SGR sequences from `input` without changing how it
+/// looks.
+///
+/// Runs of SGR sequences are merged into the shortest sequence that
+/// reaches the same style, and nothing is sent when the style is already
+/// active: repeated resets, re-setting the current color, and styles
+/// overridden before any text are all dropped. The style in effect at the
+/// end of the input is kept.
+///
+/// SGR codes the crate doesn't track (underline colors, fonts, colon
+/// sub-parameters) are passed through untouched, as is everything that
+/// isn't an SGR sequence.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::optimize;
+///
+/// let input = "\x1b[0m\x1b[0m\x1b[31m\x1b[32mok\x1b[32m!\x1b[0m\x1b[0m";
+/// assert_eq!(optimize(input), "\x1b[32mok!\x1b[0m");
+/// ```
+#[must_use]
+pub fn optimize(input: &str) -> String {
+    let mut optimizer = Optimizer::default();
+    let mut out = String::with_capacity(input.len());
+    for segment in parse(input) {
+        optimizer.push(&segment, &mut out);
+    }
+    optimizer.flush(&mut out);
+    out
+}
+
+/// Render styled spans one after another into `out`.
+///
+/// Between spans only the codes that change the previous style into the
+/// next are sent, or a reset followed by the new style when that is
+/// shorter, and nothing at all when two spans share a style. Empty spans
+/// are skipped. The output starts from and, with a final reset, returns
+/// to the default style.
+///
+/// Like [`Style::apply`], only the text is written when colors are
+/// disabled (see [`crate::config`]), and the accessibility
+/// [`Policy`](crate::Policy) is applied to every style.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{render_spans, Color, Modifier, Style};
+/// # glyphs::set_color_choice(glyphs::ColorChoice::Always);
+///
+/// let red = Style::new().fg(Color::Red);
+/// let spans = [
+///     (red.modifier(Modifier::Bold), "error"),
+///     (red, ": "),
+///     (red, "disk full"),
+///     (Style::new(), " (retrying)"),
+/// ];
+/// let mut out = String::new();
+/// render_spans(&spans, &mut out);
+/// assert_eq!(out, "\x1b[1;31merror\x1b[22m: disk full\x1b[0m (retrying)");
+/// ```
+pub fn render_spans(spans: &[(Style, &str)], out: &mut String) {
+    if !config::colors_enabled() {
+        for (_, text) in spans {
+            out.push_str(text);
+        }
+        return;
+    }
+
+    let policy = policy::policy();
+    let mut optimizer = Optimizer::default();
+    for (style, text) in spans.iter().filter(|(_, text)| !text.is_empty()) {
+        optimizer.wanted = policy.enforce(style);
+        optimizer.flush(out);
+        out.push_str(text);
+    }
+    optimizer.wanted = Style::new();
+    optimizer.flush(out);
+}
+
+#[derive(Debug, Default)]
+struct Optimizer {
+    /// The style the input asks for.
+    wanted: Style,
+    /// The style the terminal has after the output so far.
+    emitted: Style,
+    /// Style saved by a cursor save (`ESC 7`, `CSI s`).
+    saved: Style,
+    /// Untracked attributes may be set, so resets can't be dropped.
+    untracked: bool,
+    /// A reset must be sent to clear untracked attributes.
+    force_reset: bool,
+}
+
+impl Optimizer {
+    fn push(&mut self, segment: &ParsedSequence, out: &mut String) {
+        let escape = match segment {
+            ParsedSequence::Escape(escape) => escape,
+            ParsedSequence::Text(text) => {
+                self.flush(out);
+                out.push_str(text);
+                return;
+            }
+            ParsedSequence::Control(c) => {
+                self.flush(out);
+                out.push(c.as_char());
+                return;
+            }
+            ParsedSequence::Malformed { raw, .. } => {
+                self.flush(out);
+                out.push_str(raw);
+                return;
+            }
+        };
+
+        if escape.kind == EscapeKind::Sgr
+            && escape.unparsed.is_empty()
+            && is_tracked(&escape.params)
+        {
+            if self.untracked && (escape.params.is_empty() || escape.params.contains(&0)) {
+                self.untracked = false;
+                self.force_reset = true;
+            }
+            self.wanted.apply_sgr(&escape.params);
+            return;
+        }
+
+        self.flush(out);
+        out.push_str(&escape.raw);
+        if escape.kind == EscapeKind::Sgr {
+            // An SGR with only unparsed parameters is not a reset.
+            if !escape.params.is_empty() || escape.unparsed.is_empty() {
+                self.wanted.apply_sgr(&escape.params);
+            }
+            self.untracked = true;
+        } else if is_save(escape) {
+            self.saved = self.emitted;
+        } else if is_restore(escape) {
+            self.wanted = self.saved;
+        } else if escape.raw == "\x1bc" || escape.raw == "\x1b[!p" {
+            self.wanted = Style::new();
+            self.untracked = false;
+        }
+        self.emitted = self.wanted;
+    }
+
+    /// Send whatever it takes to go from the emitted style to the wanted one.
+    fn flush(&mut self, out: &mut String) {
+        if !self.force_reset && self.wanted == self.emitted {
+            return;
+        }
+        if self.wanted.is_plain() {
+            out.push_str(RESET);
+        } else {
+            let mut full = String::from("0;");
+            let _ = self.wanted.write_codes(&mut full);
+            let codes = match transition(&self.emitted, &self.wanted) {
+                Some(codes) if !self.force_reset && codes.len() < full.len() => codes,
+                _ => full,
+            };
+            out.push_str(CSI);
+            out.push_str(&codes);
+            out.push_str(SGR_SUFFIX);
+        }
+        self.emitted = self.wanted;
+        self.force_reset = false;
+    }
+}
+
+/// SGR codes that change `from` into `to` without a reset, if any.
+fn transition(from: &Style, to: &Style) -> Option<String> {
+    let mut off = Vec::new();
+    for modifier in from.modifiers.iter().filter(|&m| !to.modifiers.contains(m)) {
+        if !off.contains(&modifier.off_code()) {
+            off.push(modifier.off_code());
+        }
+    }
+
+    let mut codes = String::new();
+    for code in &off {
+        let _ = write!(codes, "{code};");
+    }
+    // An off-code can clear more than one modifier (22 ends bold and dim).
+    for modifier in to.modifiers.iter() {
+        if !from.modifiers.contains(modifier) || off.contains(&modifier.off_code()) {
+            let _ = write!(codes, "{};", modifier.on_code());
+        }
+    }
+    for (from, to, base) in [
+        (from.foreground, to.foreground, 30),
+        (from.background, to.background, 40),
+    ] {
+        if from != to {
+            let _ = match to {
+                Some(color) => color.write_code(&mut codes, base),
+                None => write!(codes, "{}", base + 9),
+            };
+            codes.push(';');
+        }
+    }
+    codes.pop()?;
+    Some(codes)
+}
+
+/// Whether every code in an SGR sequence is one [`Style`] keeps track of.
+fn is_tracked(params: &[u32]) -> bool {
+    let mut i = 0;
+    while i < params.len() {
+        i += match (params[i], &params[i + 1..]) {
+            (38 | 48, [5, n, ..]) if *n <= 255 => 3,
+            (38 | 48, [2, r, g, b, ..]) if *r <= 255 && *g <= 255 && *b <= 255 => 5,
+            (38 | 48 | 58 | 59, _) => return false,
+            (0 | 30..=37 | 39..=47 | 49 | 90..=97 | 100..=107, _) => 1,
+            (code, _) => {
+                let Ok(code) = u8::try_from(code) else {
+                    return false;
+                };
+                let known = Modifier::from_on_code(code).is_some()
+                    || Modifier::ALL.iter().any(|m| m.off_code() == code);
+                if !known {
+                    return false;
+                }
+                1
+            }
+        };
+    }
+    true
+}
+
+fn is_save(escape: &Escape) -> bool {
+    escape.raw == "\x1b7" || escape.raw == "\x1b[s"
+}
+
+fn is_restore(escape: &Escape) -> bool {
+    escape.raw == "\x1b8" || escape.raw == "\x1b[u"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{set_color_choice, ColorChoice};
+
+    #[test]
+    fn test_optimize_merges_and_drops() {
+        assert_eq!(optimize("\x1b[1m\x1b[31mx"), "\x1b[1;31mx");
+        assert_eq!(optimize("\x1b[31mx\x1b[31my"), "\x1b[31mxy");
+        assert_eq!(optimize("\x1b[0m\x1b[mplain"), "plain");
+        assert_eq!(
+            optimize("\x1b[1;31mx\x1b[0m\x1b[1mx"),
+            "\x1b[1;31mx\x1b[39mx"
+        );
+        assert_eq!(optimize("\x1b[1;2mx\x1b[22;2mx"), "\x1b[1;2mx\x1b[0;2mx");
+        assert_eq!(
+            optimize("\x1b[1;2;31mx\x1b[0;2;31mx"),
+            "\x1b[1;2;31mx\x1b[22;2mx"
+        );
+        assert_eq!(optimize("\x1b[31mx\x1b[0m\n"), "\x1b[31mx\x1b[0m\n");
+        assert_eq!(optimize("\x1b[44mx\x1b[0m\x1b[44m"), "\x1b[44mx");
+    }
+
+    #[test]
+    fn test_optimize_passes_through_untracked() {
+        // Underline color isn't tracked, so the reset clearing it is kept.
+        let input = "\x1b[4;58;5;1mx\x1b[0m\x1b[4my";
+        assert_eq!(optimize(input), "\x1b[4;58;5;1mx\x1b[0;4my");
+        assert_eq!(optimize("\x1b[4:3mx\x1b[0m"), "\x1b[4:3mx\x1b[0m");
+        assert_eq!(optimize("\x1b[31m\x1b[2Jx"), "\x1b[31m\x1b[2Jx");
+    }
+
+    #[test]
+    fn test_render_spans() {
+        use crate::color::Color;
+
+        set_color_choice(ColorChoice::Always);
+
+        let bold = Style::new().modifier(Modifier::Bold);
+        let blue = Style::new().fg(Color::Blue);
+        let mut out = String::new();
+        render_spans(
+            &[
+                (bold, "a"),
+                (bold, ""),
+                (bold.patch(blue), "b"),
+                (blue, "c"),
+            ],
+            &mut out,
+        );
+        assert_eq!(out, "\x1b[1ma\x1b[34mb\x1b[22mc\x1b[0m");
+
+        let mut out = String::from("> ");
+        render_spans(&[(Style::new(), "plain"), (blue, "")], &mut out);
+        assert_eq!(out, "> plain");
+    }
+
+    #[test]
+    fn test_optimize_cursor_save_restore() {
+        let input = "\x1b7\x1b[31mx\x1b8\x1b[31my";
+        assert_eq!(optimize(input), "\x1b7\x1b[31mx\x1b8\x1b[31my");
+        let input = "\x1b[32m\x1b7x\x1b[0m\x1b8\x1b[32my";
+        assert_eq!(optimize(input), "\x1b[32m\x1b7x\x1b[0m\x1b8y");
+    }
+}
@@ -1,6 +1,15 @@
 //! ANSI sequence parser.
 
-use crate::escape::{describe_sgr, Escape, EscapeKind};
+use crate::charset::Charset;
+use crate::control::ControlChar;
+use crate::describe::{Describer, EnglishDescriber as English};
+use crate::escape::{describe_sgr, Escape, EscapeKind, Params};
+use crate::op::{CursorOp, EraseOp};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use memchr::{memchr2, memchr3};
 
 /// A parsed segment of text (either plain text or an escape sequence).
 #[derive(Debug, Clone)]
@@ -9,6 +18,17 @@ pub enum ParsedSequence {
     Text(String),
     /// An escape sequence.
     Escape(Escape),
+    /// A control character such as `\r`, `\n`, `\t`, or BEL.
+    Control(ControlChar),
+    /// A truncated or otherwise invalid escape sequence.
+    ///
+    /// The raw bytes are kept so nothing is silently lost.
+    Malformed {
+        /// The raw (partial) sequence.
+        raw: String,
+        /// Why the sequence could not be parsed.
+        reason: MalformedReason,
+    },
 }
 
 impl ParsedSequence {
@@ -17,7 +37,7 @@ impl ParsedSequence {
     pub fn as_text(&self) -> Option<&str> {
         match self {
             Self::Text(t) => Some(t),
-            Self::Escape(_) => None,
+            Self::Escape(_) | Self::Control(_) | Self::Malformed { .. } => None,
         }
     }
 
@@ -25,15 +45,85 @@ impl ParsedSequence {
     #[must_use]
     pub const fn as_escape(&self) -> Option<&Escape> {
         match self {
-            Self::Text(_) => None,
             Self::Escape(e) => Some(e),
+            Self::Text(_) | Self::Control(_) | Self::Malformed { .. } => None,
         }
     }
+
+    /// Get as control character, or None otherwise.
+    #[must_use]
+    pub const fn as_control(&self) -> Option<ControlChar> {
+        match self {
+            Self::Control(c) => Some(*c),
+            Self::Text(_) | Self::Escape(_) | Self::Malformed { .. } => None,
+        }
+    }
+
+    /// Whether this segment is a malformed sequence.
+    #[must_use]
+    pub const fn is_malformed(&self) -> bool {
+        matches!(self, Self::Malformed { .. })
+    }
+}
+
+/// Why an escape sequence could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MalformedReason {
+    /// An ESC byte at the end of the input, or directly followed by another ESC.
+    TruncatedEscape,
+    /// A CSI sequence that ended before its final byte.
+    TruncatedCsi,
+    /// A CSI sequence interrupted by a byte that is not valid inside it.
+    InvalidCsiByte(char),
+    /// An OSC sequence without a BEL or ST terminator.
+    UnterminatedOsc,
+    /// A CSI parameter too large to represent.
+    ParamOverflow,
+}
+
+impl fmt::Display for MalformedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncatedEscape => write!(f, "truncated escape sequence"),
+            Self::TruncatedCsi => write!(f, "CSI sequence without a final byte"),
+            Self::InvalidCsiByte(c) => write!(f, "invalid byte {c:?} in CSI sequence"),
+            Self::UnterminatedOsc => write!(f, "unterminated OSC sequence"),
+            Self::ParamOverflow => write!(f, "CSI parameter out of range"),
+        }
+    }
+}
+
+/// Error returned by [`parse_strict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset of the malformed sequence in the input.
+    pub position: usize,
+    /// The raw (partial) sequence.
+    pub raw: String,
+    /// Why the sequence could not be parsed.
+    pub reason: MalformedReason,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at byte {}: {:?}",
+            self.reason, self.position, self.raw
+        )
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
 /// Parse a string containing ANSI escape sequences.
 ///
-/// Returns a vector of parsed segments, alternating between plain text and escapes.
+/// Returns a vector of parsed segments: plain text, escapes, and control
+/// characters (which are split out of the text so `\r` redraws and the like
+/// can be interpreted).
+/// Invalid sequences are reported as [`ParsedSequence::Malformed`]; use
+/// [`parse_strict`] to turn them into an error instead.
 ///
 /// # Example
 ///
@@ -45,122 +135,842 @@ impl ParsedSequence {
 ///     match segment {
 ///         glyphs::ParsedSequence::Text(t) => println!("Text: {}", t),
 ///         glyphs::ParsedSequence::Escape(e) => println!("Escape: {}", e),
+///         glyphs::ParsedSequence::Control(c) => println!("Control: {}", c),
+///         glyphs::ParsedSequence::Malformed { raw, reason } => println!("Bad: {raw:?} ({reason})"),
 ///     }
 /// }
 /// ```
 #[must_use]
-#[allow(clippy::missing_panics_doc)]
 pub fn parse(input: &str) -> Vec<ParsedSequence> {
     let mut result = Vec::new();
-    let mut current_text = String::new();
-    let mut chars = input.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '\x1b' {
-            // Flush any accumulated text
-            if !current_text.is_empty() {
-                result.push(ParsedSequence::Text(std::mem::take(&mut current_text)));
-            }
-
-            // Parse escape sequence
-            let mut seq = String::from('\x1b');
-            
-            if let Some(&next) = chars.peek() {
-                seq.push(chars.next().unwrap());
-
-                match next {
-                    '[' => {
-                        // CSI sequence
-                        let mut params = String::new();
-                        while let Some(&c) = chars.peek() {
-                            if c.is_ascii_digit() || c == ';' {
-                                params.push(chars.next().unwrap());
-                                seq.push(c);
-                            } else {
-                                break;
-                            }
-                        }
-
-                        // Get final character
-                        if let Some(final_char) = chars.next() {
-                            seq.push(final_char);
-                            let escape = parse_csi(&params, final_char, &seq);
-                            result.push(ParsedSequence::Escape(escape));
-                        }
-                    }
-                    ']' => {
-                        // OSC sequence
-                        while let Some(&c) = chars.peek() {
-                            seq.push(chars.next().unwrap());
-                            if c == '\x07' || (c == '\\' && seq.ends_with('\x1b')) {
-                                break;
-                            }
-                        }
-                        let escape = Escape::new(
-                            seq,
-                            EscapeKind::Osc,
-                            "operating system command".to_string(),
-                        );
-                        result.push(ParsedSequence::Escape(escape));
-                    }
-                    _ => {
-                        // Unknown escape
-                        let escape = Escape::new(seq, EscapeKind::Unknown, "unknown".to_string());
-                        result.push(ParsedSequence::Escape(escape));
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let (segment, end) = next_segment(input, pos);
+        result.push(segment);
+        pos = end;
+    }
+
+    result
+}
+
+/// Parse a string, failing on the first malformed escape sequence.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] with the byte position of the first truncated or
+/// invalid sequence.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::parse_strict;
+///
+/// assert!(parse_strict("\x1b[31mok\x1b[0m").is_ok());
+///
+/// let err = parse_strict("ok\x1b[31").unwrap_err();
+/// assert_eq!(err.position, 2);
+/// ```
+pub fn parse_strict(input: &str) -> Result<Vec<ParsedSequence>, ParseError> {
+    let mut result = Vec::new();
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let (segment, end) = next_segment(input, pos);
+        if let ParsedSequence::Malformed { raw, reason } = segment {
+            return Err(ParseError {
+                position: pos,
+                raw,
+                reason,
+            });
+        }
+        result.push(segment);
+        pos = end;
+    }
+
+    Ok(result)
+}
+
+/// Callbacks for [`parse_visit`], one per kind of segment.
+///
+/// Every method does nothing by default, so a visitor only implements the
+/// ones it cares about. Text and OSC bodies are borrowed from the input.
+pub trait Visitor {
+    /// A run of plain text.
+    fn on_text(&mut self, _text: &str) {}
+
+    /// A control character such as `\n` or BEL.
+    fn on_control(&mut self, _control: ControlChar) {}
+
+    /// An SGR sequence, with its parameters as [`Escape::params`] would
+    /// hold them. An empty list is a reset.
+    fn on_sgr(&mut self, _params: &[u32]) {}
+
+    /// A cursor movement, save, or restore.
+    fn on_cursor(&mut self, _op: CursorOp) {}
+
+    /// A line or screen erase.
+    fn on_erase(&mut self, _op: EraseOp) {}
+
+    /// An operating system command, without its introducer and terminator
+    /// (`8;;https://example.com` for a hyperlink).
+    fn on_osc(&mut self, _body: &str) {}
+
+    /// Any other escape sequence.
+    fn on_escape(&mut self, _escape: &Escape) {}
+
+    /// A truncated or otherwise invalid escape sequence.
+    fn on_malformed(&mut self, _raw: &str, _reason: MalformedReason) {}
+}
+
+/// Parse a string in one pass, calling `visitor` for each segment.
+///
+/// Segments are the same as [`parse`] returns, but no `Vec` is built and
+/// text, SGR, cursor, erase, and OSC segments are handed over without
+/// allocating. Only the remaining escape sequences are built into an
+/// [`Escape`] for [`Visitor::on_escape`].
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{parse_visit, Visitor};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     chars: usize,
+///     styles: usize,
+/// }
+///
+/// impl Visitor for Counter {
+///     fn on_text(&mut self, text: &str) {
+///         self.chars += text.chars().count();
+///     }
+///
+///     fn on_sgr(&mut self, _params: &[u32]) {
+///         self.styles += 1;
+///     }
+/// }
+///
+/// let mut counter = Counter::default();
+/// parse_visit("\x1b[1;31mError:\x1b[0m disk full", &mut counter);
+/// assert_eq!((counter.chars, counter.styles), (16, 2));
+/// ```
+pub fn parse_visit<V: Visitor + ?Sized>(input: &str, visitor: &mut V) {
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let (span, end) = next_span(input, pos);
+        let raw = &input[pos..end];
+        let handled = match span {
+            Span::Text => {
+                visitor.on_text(raw);
+                true
+            }
+            Span::Control(b) => {
+                visitor.on_control(
+                    ControlChar::from_char(char::from(b)).unwrap_or(ControlChar::Other(b)),
+                );
+                true
+            }
+            Span::Osc => {
+                visitor.on_osc(osc_body(raw));
+                true
+            }
+            Span::Csi { params_end }
+                if params_end == end - 1 && !overflows(&input[pos + 2..params_end]) =>
+            {
+                visit_csi(
+                    &input[pos + 2..params_end],
+                    input.as_bytes()[end - 1],
+                    visitor,
+                )
+            }
+            _ => false,
+        };
+
+        if !handled {
+            match next_segment(input, pos).0 {
+                ParsedSequence::Escape(escape) => {
+                    if escape.kind == EscapeKind::Sgr {
+                        visitor.on_sgr(&escape.params);
+                    } else if let Some(op) = escape.cursor_op() {
+                        visitor.on_cursor(op);
+                    } else if let Some(op) = escape.erase_op() {
+                        visitor.on_erase(op);
+                    } else {
+                        visitor.on_escape(&escape);
                     }
                 }
+                ParsedSequence::Malformed { reason, .. } => visitor.on_malformed(raw, reason),
+                ParsedSequence::Text(_) | ParsedSequence::Control(_) => {}
             }
-        } else {
-            current_text.push(c);
         }
+        pos = end;
     }
+}
 
-    // Flush remaining text
-    if !current_text.is_empty() {
-        result.push(ParsedSequence::Text(current_text));
+/// Hand the common CSI sequences to `visitor` without building an
+/// [`Escape`], returning whether the sequence was one of them.
+fn visit_csi<V: Visitor + ?Sized>(params: &str, final_byte: u8, visitor: &mut V) -> bool {
+    if !matches!(
+        final_byte,
+        b'm' | b'A'..=b'D' | b'G' | b'H' | b'f' | b'J' | b'K'
+    ) {
+        return false;
     }
+    let (values, _) = split_params(params);
+    let count = |i: usize| values.get(i).map_or(1, |&p| p.max(1));
+    let mode = values.first().copied().unwrap_or(0);
 
-    result
+    match (final_byte, mode) {
+        (b'm', _) => visitor.on_sgr(&values),
+        (b'A', _) => visitor.on_cursor(CursorOp::Up(count(0))),
+        (b'B', _) => visitor.on_cursor(CursorOp::Down(count(0))),
+        (b'C', _) => visitor.on_cursor(CursorOp::Forward(count(0))),
+        (b'D', _) => visitor.on_cursor(CursorOp::Back(count(0))),
+        (b'G', _) => visitor.on_cursor(CursorOp::Column(count(0))),
+        (b'H' | b'f', _) => visitor.on_cursor(CursorOp::Goto(count(0), count(1))),
+        (b'J', 0) => visitor.on_erase(EraseOp::ToScreenEnd),
+        (b'J', 1) => visitor.on_erase(EraseOp::ToScreenStart),
+        (b'J', 2) => visitor.on_erase(EraseOp::Screen),
+        (b'J', 3) => visitor.on_erase(EraseOp::ScreenAndScrollback),
+        (b'K', 0) => visitor.on_erase(EraseOp::ToLineEnd),
+        (b'K', 1) => visitor.on_erase(EraseOp::ToLineStart),
+        (b'K', 2) => visitor.on_erase(EraseOp::Line),
+        _ => return false,
+    }
+    true
+}
+
+/// The body of a complete OSC sequence, between introducer and terminator.
+pub(crate) fn osc_body(raw: &str) -> &str {
+    // ESC ] and the C1 introducer U+009D are both two bytes.
+    let body = &raw[2..];
+    body.strip_suffix('\x07')
+        .or_else(|| body.strip_suffix("\x1b\\"))
+        .or_else(|| body.strip_suffix('\u{9c}'))
+        .unwrap_or(body)
+}
+
+/// How byte input that isn't valid UTF-8 is turned into text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvalidUtf8 {
+    /// Replace each invalid sequence with U+FFFD, except that a lone byte
+    /// from `0x80` to `0x9F` is read as the C1 control of the same value.
+    #[default]
+    Replace,
+    /// Read every invalid byte as the Latin-1 character of the same value,
+    /// so no byte is dropped. Suits captures from Latin-1 systems; C1
+    /// bytes are still controls.
+    Latin1,
+}
+
+/// Parse raw bytes, such as output captured from a PTY or serial line.
+///
+/// Valid UTF-8 is parsed as by [`parse`]. A lone byte from `0x80` to
+/// `0x9F` is read as the 8-bit C1 control of the same value, so `0x9B`
+/// introduces a CSI sequence and `0x9D` an OSC; any other invalid byte
+/// sequence becomes U+FFFD. See [`parse_bytes_with`] to keep those bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{parse_bytes, ParsedSequence};
+///
+/// let segments = parse_bytes(b"\x9b1mbold");
+/// assert!(matches!(&segments[0], ParsedSequence::Escape(e) if e.description == "bold"));
+/// ```
+#[must_use]
+pub fn parse_bytes(input: &[u8]) -> Vec<ParsedSequence> {
+    parse_bytes_with(input, InvalidUtf8::Replace)
+}
+
+/// Parse raw bytes, choosing how invalid UTF-8 is handled.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{parse_bytes_with, InvalidUtf8, ParsedSequence};
+///
+/// let segments = parse_bytes_with(b"caf\xe9", InvalidUtf8::Latin1);
+/// assert!(matches!(&segments[0], ParsedSequence::Text(t) if t == "caf\u{e9}"));
+/// ```
+#[must_use]
+pub fn parse_bytes_with(input: &[u8], invalid: InvalidUtf8) -> Vec<ParsedSequence> {
+    let mut text = String::with_capacity(input.len());
+    let used = decode(input, invalid, &mut text);
+    push_invalid(&input[used..], invalid, &mut text);
+    parse(&text)
+}
+
+/// Decode `input` into `out`, returning how many bytes were used.
+///
+/// Stops before a UTF-8 sequence cut off by the end of the input, so
+/// streaming callers can complete it with the next chunk.
+fn decode(mut input: &[u8], invalid: InvalidUtf8, out: &mut String) -> usize {
+    let total = input.len();
+    loop {
+        match core::str::from_utf8(input) {
+            Ok(valid) => {
+                out.push_str(valid);
+                return total;
+            }
+            Err(err) => {
+                let (valid, rest) = input.split_at(err.valid_up_to());
+                out.push_str(core::str::from_utf8(valid).unwrap_or_default());
+                let Some(len) = err.error_len() else {
+                    return total - rest.len();
+                };
+                push_invalid(&rest[..len], invalid, out);
+                input = &rest[len..];
+            }
+        }
+    }
+}
+
+/// Append one invalid byte sequence as text.
+fn push_invalid(bytes: &[u8], invalid: InvalidUtf8, out: &mut String) {
+    match (invalid, bytes) {
+        (_, []) => {}
+        (InvalidUtf8::Latin1, _) => out.extend(bytes.iter().map(|&b| char::from(b))),
+        (InvalidUtf8::Replace, [byte @ 0x80..=0x9f]) => out.push(char::from(*byte)),
+        (InvalidUtf8::Replace, _) => out.push(char::REPLACEMENT_CHARACTER),
+    }
+}
+
+/// Incremental parser for output that arrives in chunks.
+///
+/// Escape sequences split across chunk boundaries are buffered until they
+/// are complete, so each sequence is reported exactly once.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{ParsedSequence, StreamParser};
+///
+/// let mut parser = StreamParser::new();
+/// assert!(parser.push("\x1b[3").is_empty());
+/// let segments = parser.push("1mred");
+/// assert!(matches!(&segments[0], ParsedSequence::Escape(e) if e.description == "red fg"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamParser {
+    pending: String,
+    /// The start of a UTF-8 sequence split by [`StreamParser::push_bytes`].
+    partial: Vec<u8>,
+}
+
+impl StreamParser {
+    /// Incomplete sequences longer than this are reported as malformed
+    /// instead of being buffered forever.
+    const MAX_PENDING: usize = 64 * 1024;
+
+    /// Create a new stream parser.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk of output, returning every segment that is complete.
+    pub fn push(&mut self, chunk: &str) -> Vec<ParsedSequence> {
+        self.pending.push_str(chunk);
+        let mut result = Vec::new();
+        let mut pos = 0;
+
+        while pos < self.pending.len() {
+            if self.pending.len() - pos < Self::MAX_PENDING && is_incomplete(&self.pending, pos) {
+                break;
+            }
+            let (segment, end) = next_segment(&self.pending, pos);
+            result.push(segment);
+            pos = end;
+        }
+
+        self.pending.drain(..pos);
+        result
+    }
+
+    /// Feed a chunk of raw bytes, returning every segment that is complete.
+    ///
+    /// UTF-8 characters split across chunks are reassembled; other invalid
+    /// bytes are handled as by [`parse_bytes`].
+    pub fn push_bytes(&mut self, chunk: &[u8]) -> Vec<ParsedSequence> {
+        self.partial.extend_from_slice(chunk);
+        let mut text = String::with_capacity(self.partial.len());
+        let used = decode(&self.partial, InvalidUtf8::Replace, &mut text);
+        self.partial.drain(..used);
+        self.push(&text)
+    }
+
+    /// Flush any buffered partial sequence (reported as malformed).
+    pub fn finish(&mut self) -> Vec<ParsedSequence> {
+        let partial = core::mem::take(&mut self.partial);
+        push_invalid(&partial, InvalidUtf8::Replace, &mut self.pending);
+        let rest = core::mem::take(&mut self.pending);
+        parse(&rest)
+    }
+
+    /// Bytes currently buffered waiting for the rest of a sequence.
+    #[must_use]
+    pub fn pending(&self) -> &str {
+        &self.pending
+    }
+}
+
+/// Whether the escape sequence starting at `start` needs more input.
+fn is_incomplete(input: &str, start: usize) -> bool {
+    let bytes = input.as_bytes();
+    let introducer = match c1_introducer(bytes, start) {
+        Some(c) => Some(c),
+        None if bytes[start] == ESC => bytes.get(start + 1).copied(),
+        None => return false,
+    };
+    match introducer {
+        None => true,
+        Some(b'[') => bytes[start + 2..]
+            .iter()
+            .all(|&b| (0x20..=0x3f).contains(&b)),
+        // Unterminated, or ending in an ESC that may begin `ESC \`.
+        Some(b']') => {
+            matches!(osc_span(bytes, start), (Span::Malformed(_), end) if end + 1 >= bytes.len())
+        }
+        Some(0x20..=0x2f) => bytes[start + 2..]
+            .iter()
+            .all(|&b| (0x20..=0x2f).contains(&b)),
+        Some(_) => false,
+    }
+}
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+/// First UTF-8 byte of the C1 controls U+0080 to U+00BF.
+const C1_LEAD: u8 = 0xc2;
+
+/// The 7-bit equivalent (`[` or `]`) of a UTF-8 encoded C1 CSI (U+009B)
+/// or OSC (U+009D) at `at`.
+fn c1_introducer(bytes: &[u8], at: usize) -> Option<u8> {
+    if bytes[at] != C1_LEAD {
+        return None;
+    }
+    match bytes.get(at + 1) {
+        Some(0x9b) => Some(b'['),
+        Some(0x9d) => Some(b']'),
+        _ => None,
+    }
+}
+
+/// Boundaries of one segment, found without building descriptions.
+#[derive(Debug, Clone, Copy)]
+enum Span {
+    Text,
+    Control(u8),
+    /// A CSI sequence whose parameters end at `params_end`.
+    Csi {
+        params_end: usize,
+    },
+    Osc,
+    /// A two-character escape (ESC 7, ESC c, ...).
+    Short,
+    Malformed(MalformedReason),
+}
+
+/// Find the segment starting at byte `start`, returning its kind and end offset.
+fn next_span(input: &str, start: usize) -> (Span, usize) {
+    let bytes = input.as_bytes();
+    let first = bytes[start];
+
+    match c1_introducer(bytes, start) {
+        Some(b'[') => return csi_span(input, start),
+        Some(_) => return osc_span(bytes, start),
+        None => {}
+    }
+
+    if first != ESC {
+        if ControlChar::is_control_byte(first) {
+            return (Span::Control(first), start + 1);
+        }
+        // Find the next ESC or C1 introducer with memchr, then only look
+        // for control bytes before it.
+        let rest = &bytes[start..];
+        let mut esc = 0;
+        loop {
+            match memchr2(ESC, C1_LEAD, &rest[esc..]) {
+                Some(i) if rest[esc + i] == C1_LEAD && c1_introducer(rest, esc + i).is_none() => {
+                    esc += i + 1;
+                }
+                Some(i) => {
+                    esc += i;
+                    break;
+                }
+                None => {
+                    esc = rest.len();
+                    break;
+                }
+            }
+        }
+        let end = rest[..esc]
+            .iter()
+            .position(|&b| ControlChar::is_control_byte(b))
+            .unwrap_or(esc);
+        return (Span::Text, start + end);
+    }
+
+    match bytes.get(start + 1) {
+        None | Some(&ESC) => (Span::Malformed(MalformedReason::TruncatedEscape), start + 1),
+        Some(b'[') => csi_span(input, start),
+        Some(b']') => osc_span(bytes, start),
+        Some(0x20..=0x2f) => intermediate_span(bytes, start),
+        Some(_) => {
+            let next = input[start + 1..].chars().next().map_or(1, char::len_utf8);
+            (Span::Short, start + 1 + next)
+        }
+    }
+}
+
+fn csi_span(input: &str, start: usize) -> (Span, usize) {
+    let bytes = input.as_bytes();
+
+    // Parameter bytes (0x30-0x3F), then intermediate bytes (0x20-0x2F)
+    let mut i = start + 2;
+    while i < bytes.len() && (0x30..=0x3f).contains(&bytes[i]) {
+        i += 1;
+    }
+    let params_end = i;
+    while i < bytes.len() && (0x20..=0x2f).contains(&bytes[i]) {
+        i += 1;
+    }
+
+    match bytes.get(i) {
+        None => (Span::Malformed(MalformedReason::TruncatedCsi), i),
+        Some(&b) if (0x40..=0x7e).contains(&b) => (Span::Csi { params_end }, i + 1),
+        Some(_) => {
+            // Leave the offending character in the input so it isn't lost.
+            let c = input[i..].chars().next().unwrap_or('\u{fffd}');
+            (Span::Malformed(MalformedReason::InvalidCsiByte(c)), i)
+        }
+    }
+}
+
+/// An escape with intermediate bytes, such as the charset designation
+/// `ESC ( 0`.
+fn intermediate_span(bytes: &[u8], start: usize) -> (Span, usize) {
+    let mut i = start + 1;
+    while i < bytes.len() && (0x20..=0x2f).contains(&bytes[i]) {
+        i += 1;
+    }
+    match bytes.get(i) {
+        Some(0x30..=0x7e) => (Span::Short, i + 1),
+        _ => (Span::Malformed(MalformedReason::TruncatedEscape), i),
+    }
+}
+
+fn osc_span(bytes: &[u8], start: usize) -> (Span, usize) {
+    let mut body = start + 2;
+    // Skip C1 controls other than ST (U+009C), which ends the command.
+    let terminator = loop {
+        match memchr3(BEL, ESC, C1_LEAD, &bytes[body..]).map(|offset| body + offset) {
+            Some(at) if bytes[at] == C1_LEAD && bytes.get(at + 1) != Some(&0x9c) => body = at + 1,
+            found => break found,
+        }
+    };
+    match terminator {
+        Some(at) if bytes[at] == BEL => (Span::Osc, at + 1),
+        Some(at) if bytes[at] == C1_LEAD => (Span::Osc, at + 2),
+        Some(at) if bytes.get(at + 1) == Some(&b'\\') => (Span::Osc, at + 2),
+        // Any other ESC begins a new sequence.
+        Some(at) => (Span::Malformed(MalformedReason::UnterminatedOsc), at),
+        None => (
+            Span::Malformed(MalformedReason::UnterminatedOsc),
+            bytes.len(),
+        ),
+    }
+}
+
+/// Parse one segment starting at byte `start`, returning it and the end offset.
+fn next_segment(input: &str, start: usize) -> (ParsedSequence, usize) {
+    let (span, end) = next_span(input, start);
+    let raw = &input[start..end];
+
+    let segment = match span {
+        Span::Text => ParsedSequence::Text(raw.to_string()),
+        Span::Control(b) => ParsedSequence::Control(
+            ControlChar::from_char(char::from(b)).unwrap_or(ControlChar::Other(b)),
+        ),
+        Span::Csi { params_end } if overflows(&input[start + 2..params_end]) => {
+            ParsedSequence::Malformed {
+                raw: raw.to_string(),
+                reason: MalformedReason::ParamOverflow,
+            }
+        }
+        Span::Csi { params_end } => {
+            let params = &input[start + 2..params_end];
+            let intermediates = &input[params_end..end - 1];
+            let final_char = char::from(input.as_bytes()[end - 1]);
+            let escape = if intermediates.is_empty() {
+                parse_csi(params, final_char, raw)
+            } else if intermediates == " " && final_char == 'q' {
+                describe_cursor_style(params, raw)
+            } else if intermediates == "$" && matches!(final_char, 'v' | 'x' | 'z' | 'r') {
+                describe_rectangle(params, final_char, raw)
+            } else if intermediates == "!" && final_char == 'p' && params.is_empty() {
+                Escape::new(
+                    raw.to_string(),
+                    EscapeKind::Mode,
+                    "soft terminal reset".to_string(),
+                )
+            } else {
+                Escape::new(
+                    raw.to_string(),
+                    EscapeKind::Unknown,
+                    format!("CSI sequence ending with '{intermediates}{final_char}'"),
+                )
+            };
+            ParsedSequence::Escape(escape)
+        }
+        Span::Osc => ParsedSequence::Escape(Escape::new(
+            raw.to_string(),
+            EscapeKind::Osc,
+            "operating system command".to_string(),
+        )),
+        Span::Short => {
+            let (kind, description) = describe_short(raw);
+            ParsedSequence::Escape(Escape::new(raw.to_string(), kind, description))
+        }
+        Span::Malformed(reason) => ParsedSequence::Malformed {
+            raw: raw.to_string(),
+            reason,
+        },
+    };
+
+    (segment, end)
+}
+
+/// Kind and description of a two-character or intermediate escape.
+fn describe_short(raw: &str) -> (EscapeKind, String) {
+    let mut chars = raw.chars().skip(1);
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(set @ ('(' | ')' | '*' | '+')), Some(c), None) => {
+            let slot = match set {
+                '(' => 0,
+                ')' => 1,
+                '*' => 2,
+                _ => 3,
+            };
+            let charset = Charset::from_designator(c);
+            let name = match charset {
+                Charset::Other(c) => format!("'{c}'"),
+                known => known.name().to_string(),
+            };
+            let description = format!("designate G{slot} charset: {name}");
+            (EscapeKind::Charset, description)
+        }
+        (Some('7'), None, None) => (EscapeKind::Cursor, English.cursor(CursorOp::Save)),
+        (Some('8'), None, None) => (EscapeKind::Cursor, English.cursor(CursorOp::Restore)),
+        (Some('='), None, None) => (EscapeKind::Mode, "keypad application mode".to_string()),
+        (Some('>'), None, None) => (EscapeKind::Mode, "keypad numeric mode".to_string()),
+        (Some('c'), None, None) => (EscapeKind::Mode, "full terminal reset".to_string()),
+        (Some('#'), Some(size @ '3'..='6'), None) => {
+            let description = match size {
+                '3' => "double-height line, top half",
+                '4' => "double-height line, bottom half",
+                '5' => "single-width line",
+                _ => "double-width line",
+            };
+            (EscapeKind::Mode, description.to_string())
+        }
+        _ => (EscapeKind::Unknown, "unknown".to_string()),
+    }
+}
+
+/// Describe a device status request or report (`CSI ... n`, `c`, `R`)
+/// or a focus event (`CSI I`, `CSI O`).
+fn describe_device(params: &str, final_char: char, values: &[u32]) -> (EscapeKind, String) {
+    let count = |i: usize| values.get(i).map_or(1, |&p| p.max(1));
+    let desc = match final_char {
+        'n' => match params {
+            "5" => "request device status".to_string(),
+            "6" | "?6" => "request cursor position".to_string(),
+            _ => format!("device status report {params}"),
+        },
+        'c' => match params.chars().next() {
+            Some('>') => "request secondary device attributes".to_string(),
+            Some('?') => "device attributes report".to_string(),
+            _ => "request primary device attributes".to_string(),
+        },
+        'R' => format!("cursor position report ({}, {})", count(0), count(1)),
+        // Sent by the terminal when focus reporting (`?1004`) is on.
+        'I' if params.is_empty() => "focus in event".to_string(),
+        'O' if params.is_empty() => "focus out event".to_string(),
+        _ => {
+            let desc = format!("CSI sequence ending with '{final_char}'");
+            return (EscapeKind::Unknown, desc);
+        }
+    };
+    (EscapeKind::Device, desc)
+}
+
+/// Describe a DECSCUSR cursor shape change (`CSI Ps SP q`).
+fn describe_cursor_style(params: &str, raw: &str) -> Escape {
+    let shape = match params {
+        "" | "0" | "1" => "blinking block",
+        "2" => "steady block",
+        "3" => "blinking underline",
+        "4" => "steady underline",
+        "5" => "blinking bar",
+        "6" => "steady bar",
+        _ => "unknown shape",
+    };
+    let (values, _) = split_params(params);
+    let description = format!("cursor style: {shape}");
+    Escape::new(raw.to_string(), EscapeKind::Cursor, description).with_params(values)
+}
+
+/// Describe a VT420 rectangular area operation (`CSI ... $ v`, `x`, `z`,
+/// or `r`).
+fn describe_rectangle(params: &str, final_char: char, raw: &str) -> Escape {
+    let (values, _) = split_params(params);
+    // DECFRA puts the fill character before the rectangle.
+    let offset = usize::from(final_char == 'x');
+    let area = describe_area(values.get(offset..).unwrap_or_default());
+    let description = match final_char {
+        'v' => {
+            let to = |i: usize| values.get(i).map_or(1, |&p| p.max(1));
+            format!("copy rectangle {area} to row {}, column {}", to(5), to(6))
+        }
+        'x' => {
+            let fill = values
+                .first()
+                .filter(|&&c| c >= 0x20)
+                .and_then(|&c| char::from_u32(c))
+                .unwrap_or(' ');
+            format!("fill rectangle {area} with {fill:?}")
+        }
+        'z' => format!("erase rectangle {area}"),
+        _ => {
+            let attributes = describe_sgr(values.get(4..).unwrap_or_default());
+            format!("change attributes in rectangle {area}: {attributes}")
+        }
+    };
+    Escape::new(raw.to_string(), EscapeKind::Rectangle, description).with_params(values)
+}
+
+/// Describe the `top ; left ; bottom ; right` corners of a rectangle,
+/// where a missing or zero bottom or right edge means the last row or
+/// column.
+fn describe_area(params: &[u32]) -> String {
+    let corner = |i: usize| params.get(i).copied().filter(|&n| n > 0);
+    let edge = |i: usize| corner(i).map_or_else(|| "last".to_string(), |n| n.to_string());
+    format!(
+        "({}, {}) to ({}, {})",
+        corner(0).unwrap_or(1),
+        corner(1).unwrap_or(1),
+        edge(2),
+        edge(3)
+    )
+}
+
+/// Whether a numeric CSI parameter doesn't fit in a `u32`.
+fn overflows(params: &str) -> bool {
+    params.split([';', ':']).any(|p| {
+        !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) && p.parse::<u32>().is_err()
+    })
+}
+
+/// Split CSI parameters into numbers and the pieces that aren't numbers.
+///
+/// Empty parameters read as `0`, and colon-separated extended colors
+/// (`38:2::r:g:b`, `38:5:n`) are expanded to their `;` form. Anything else
+/// is returned as raw text so descriptions can still show it.
+fn split_params(params: &str) -> (Params, Vec<String>) {
+    let mut values = Params::new();
+    let mut unparsed = Vec::new();
+    if params.is_empty() {
+        return (values, unparsed);
+    }
+
+    for param in params.split(';') {
+        if param.is_empty() {
+            values.push(0);
+        } else if let Ok(value) = param.parse() {
+            values.push(value);
+        } else if let Some(color) = colon_color(param) {
+            values.extend(color);
+        } else {
+            unparsed.push(param.to_string());
+        }
+    }
+    (values, unparsed)
+}
+
+/// Expand an ITU T.416 colon-form color (`38:5:n`, `48:2:cs:r:g:b`).
+fn colon_color(param: &str) -> Option<Params> {
+    let parts = param
+        .split(':')
+        .map(|p| {
+            if p.is_empty() {
+                Some(0)
+            } else {
+                p.parse::<u32>().ok()
+            }
+        })
+        .collect::<Option<Params>>()?;
+    match parts.as_slice() {
+        [base @ (38 | 48 | 58), 5, n] => Some(Params::from_slice(&[*base, 5, *n])),
+        [base @ (38 | 48 | 58), 2, r, g, b] | [base @ (38 | 48 | 58), 2, _, r, g, b] => {
+            Some(Params::from_slice(&[*base, 2, *r, *g, *b]))
+        }
+        _ => None,
+    }
 }
 
 fn parse_csi(params: &str, final_char: char, raw: &str) -> Escape {
-    let param_values: Vec<u16> = params
-        .split(';')
-        .filter(|s| !s.is_empty())
-        .filter_map(|s| s.parse().ok())
-        .collect();
+    let (param_values, unparsed) = split_params(params);
+    // A zero count or position means the same as the default of one.
+    let count = |i: usize| param_values.get(i).map_or(1, |&p| p.max(1));
 
     let (kind, description) = match final_char {
         'm' => (EscapeKind::Sgr, describe_sgr(&param_values)),
-        'A' => (EscapeKind::Cursor, format!("cursor up {}", param_values.first().unwrap_or(&1))),
-        'B' => (EscapeKind::Cursor, format!("cursor down {}", param_values.first().unwrap_or(&1))),
-        'C' => (EscapeKind::Cursor, format!("cursor right {}", param_values.first().unwrap_or(&1))),
-        'D' => (EscapeKind::Cursor, format!("cursor left {}", param_values.first().unwrap_or(&1))),
-        'H' | 'f' => {
-            let row = param_values.first().unwrap_or(&1);
-            let col = param_values.get(1).unwrap_or(&1);
-            (EscapeKind::Cursor, format!("cursor to ({row}, {col})"))
-        }
+        'A' => (EscapeKind::Cursor, English.cursor(CursorOp::Up(count(0)))),
+        'B' => (EscapeKind::Cursor, English.cursor(CursorOp::Down(count(0)))),
+        'C' => (
+            EscapeKind::Cursor,
+            English.cursor(CursorOp::Forward(count(0))),
+        ),
+        'D' => (EscapeKind::Cursor, English.cursor(CursorOp::Back(count(0)))),
+        'G' => (
+            EscapeKind::Cursor,
+            English.cursor(CursorOp::Column(count(0))),
+        ),
+        'H' | 'f' => (
+            EscapeKind::Cursor,
+            English.cursor(CursorOp::Goto(count(0), count(1))),
+        ),
         'J' => {
-            let mode = param_values.first().unwrap_or(&0);
-            let desc = match mode {
-                0 => "clear to end of screen",
-                1 => "clear to start of screen",
-                2 => "clear entire screen",
-                3 => "clear screen and scrollback",
-                _ => "clear screen (unknown mode)",
+            let op = match param_values.first().unwrap_or(&0) {
+                0 => Some(EraseOp::ToScreenEnd),
+                1 => Some(EraseOp::ToScreenStart),
+                2 => Some(EraseOp::Screen),
+                3 => Some(EraseOp::ScreenAndScrollback),
+                _ => None,
             };
-            (EscapeKind::Erase, desc.to_string())
+            let desc = op.map_or_else(
+                || "clear screen (unknown mode)".to_string(),
+                |op| English.erase(op),
+            );
+            (EscapeKind::Erase, desc)
         }
         'K' => {
-            let mode = param_values.first().unwrap_or(&0);
-            let desc = match mode {
-                0 => "clear to end of line",
-                1 => "clear to start of line",
-                2 => "clear entire line",
-                _ => "clear line (unknown mode)",
+            let op = match param_values.first().unwrap_or(&0) {
+                0 => Some(EraseOp::ToLineEnd),
+                1 => Some(EraseOp::ToLineStart),
+                2 => Some(EraseOp::Line),
+                _ => None,
             };
-            (EscapeKind::Erase, desc.to_string())
+            let desc = op.map_or_else(
+                || "clear line (unknown mode)".to_string(),
+                |op| English.erase(op),
+            );
+            (EscapeKind::Erase, desc)
         }
         'h' | 'l' => {
             let enabled = final_char == 'h';
@@ -172,6 +982,8 @@ fn parse_csi(params: &str, final_char: char, raw: &str) -> Escape {
                     "1049" => format!("{action} alternate screen"),
                     "1000" => format!("{action} mouse tracking"),
                     "2004" => format!("{action} bracketed paste"),
+                    "1007" => format!("{action} alternate scroll"),
+                    "1004" => format!("{action} focus reporting"),
                     _ => format!("{action} mode {mode}"),
                 };
                 (EscapeKind::Mode, desc)
@@ -179,43 +991,329 @@ fn parse_csi(params: &str, final_char: char, raw: &str) -> Escape {
                 (EscapeKind::Mode, format!("{action} mode {params}"))
             }
         }
-        's' => (EscapeKind::Cursor, "save cursor position".to_string()),
-        'u' => (EscapeKind::Cursor, "restore cursor position".to_string()),
-        _ => (EscapeKind::Unknown, format!("CSI sequence ending with '{final_char}'")),
+        'r' if !params.starts_with('?') => {
+            let desc = match (param_values.first(), param_values.get(1)) {
+                (None | Some(0), None | Some(0)) => "reset scroll region".to_string(),
+                (_, None | Some(0)) => format!("set scroll region (lines {} to bottom)", count(0)),
+                (_, Some(bottom)) => format!("set scroll region (lines {} to {bottom})", count(0)),
+            };
+            (EscapeKind::Mode, desc)
+        }
+        'n' | 'c' | 'R' | 'I' | 'O' => describe_device(params, final_char, &param_values),
+        's' => (EscapeKind::Cursor, English.cursor(CursorOp::Save)),
+        'u' => (EscapeKind::Cursor, English.cursor(CursorOp::Restore)),
+        _ => (
+            EscapeKind::Unknown,
+            format!("CSI sequence ending with '{final_char}'"),
+        ),
+    };
+
+    // Mode and device descriptions already include the raw parameters.
+    let description = if unparsed.is_empty() || matches!(final_char, 'h' | 'l' | 'n' | 'c') {
+        description
+    } else {
+        let list = unparsed
+            .iter()
+            .map(|p| format!("'{p}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if kind == EscapeKind::Sgr && param_values.is_empty() {
+            format!("unparsed {list}")
+        } else {
+            format!("{description}, unparsed {list}")
+        }
     };
 
-    Escape::new(raw.to_string(), kind, description).with_params(param_values)
+    let mut escape = Escape::new(raw.to_string(), kind, description).with_params(param_values);
+    escape.unparsed = unparsed;
+    escape
 }
 
 /// Strip all ANSI escape sequences from a string.
+///
+/// Control characters are kept as-is.
 #[must_use]
 pub fn strip_ansi(input: &str) -> String {
-    parse(input)
-        .iter()
-        .filter_map(|seg| seg.as_text())
-        .collect()
+    let mut out = String::with_capacity(input.len());
+    let mut pos = 0;
+    while pos < input.len() {
+        let (span, end) = next_span(input, pos);
+        if matches!(span, Span::Text | Span::Control(_)) {
+            out.push_str(&input[pos..end]);
+        }
+        pos = end;
+    }
+    out
 }
 
 /// Get the visible length of a string (excluding ANSI codes).
 #[must_use]
 pub fn visible_len(input: &str) -> usize {
-    strip_ansi(input).chars().count()
+    let mut len = 0;
+    let mut pos = 0;
+    while pos < input.len() {
+        let (span, end) = next_span(input, pos);
+        match span {
+            Span::Text => len += input[pos..end].chars().count(),
+            Span::Control(_) => len += 1,
+            _ => {}
+        }
+        pos = end;
+    }
+    len
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_parse_simple() {
         let segments = parse("\x1b[31mRed\x1b[0m");
         assert_eq!(segments.len(), 3);
-        
+
         assert!(matches!(&segments[0], ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr));
         assert!(matches!(&segments[1], ParsedSequence::Text(t) if t == "Red"));
         assert!(matches!(&segments[2], ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr));
     }
 
+    #[test]
+    fn test_parse_private_mode() {
+        let segments = parse("\x1b[?25lhidden");
+        assert_eq!(segments.len(), 2);
+        assert!(
+            matches!(&segments[0], ParsedSequence::Escape(e) if e.description == "disable cursor visibility")
+        );
+        assert!(matches!(&segments[1], ParsedSequence::Text(t) if t == "hidden"));
+    }
+
+    #[test]
+    fn test_parse_scroll_region() {
+        let describe = |input: &str| parse(input)[0].as_escape().unwrap().description.clone();
+        assert_eq!(describe("\x1b[2;20r"), "set scroll region (lines 2 to 20)");
+        assert_eq!(describe("\x1b[5r"), "set scroll region (lines 5 to bottom)");
+        assert_eq!(describe("\x1b[r"), "reset scroll region");
+        assert_eq!(describe("\x1b[?1007h"), "enable alternate scroll");
+        assert_eq!(describe("\x1b[?1004l"), "disable focus reporting");
+        assert_eq!(describe("\x1b[I"), "focus in event");
+        assert_eq!(describe("\x1b[O"), "focus out event");
+    }
+
+    #[test]
+    fn test_parse_device_and_cursor_style() {
+        let parsed = |input: &str| parse(input)[0].as_escape().unwrap().clone();
+        assert_eq!(parsed("\x1b[6n").kind, EscapeKind::Device);
+        assert_eq!(parsed("\x1b[6n").description, "request cursor position");
+        assert_eq!(
+            parsed("\x1b[>c").description,
+            "request secondary device attributes"
+        );
+        assert_eq!(
+            parsed("\x1b[?62;22c").description,
+            "device attributes report"
+        );
+        assert_eq!(
+            parsed("\x1b[12;40R").description,
+            "cursor position report (12, 40)"
+        );
+        assert_eq!(parsed("\x1b[5 q").description, "cursor style: blinking bar");
+        assert_eq!(parsed("\x1b[5 q").kind, EscapeKind::Cursor);
+        assert_eq!(parsed("\x1b7").description, "save cursor position");
+        assert_eq!(parsed("\x1b=").kind, EscapeKind::Mode);
+        assert_eq!(parsed("\x1b[3G").description, "cursor to column 3");
+        assert_eq!(parsed("\x1b#3").description, "double-height line, top half");
+        assert_eq!(parsed("\x1b#6").kind, EscapeKind::Mode);
+        assert_eq!(parsed("\x1b#8").kind, EscapeKind::Unknown);
+        assert_eq!(parsed("\x1bc").description, "full terminal reset");
+        assert_eq!(parsed("\x1b[!p").description, "soft terminal reset");
+        assert_eq!(parsed("\x1b[!p").kind, EscapeKind::Mode);
+    }
+
+    #[test]
+    fn test_parse_rectangle_operations() {
+        let parsed = |input: &str| parse(input)[0].as_escape().unwrap().clone();
+        let copy = parsed("\x1b[2;3;4;10;1;8;3;1$v");
+        assert_eq!(copy.kind, EscapeKind::Rectangle);
+        assert_eq!(
+            copy.description,
+            "copy rectangle (2, 3) to (4, 10) to row 8, column 3"
+        );
+        assert_eq!(copy.params.len(), 8);
+        assert_eq!(
+            parsed("\x1b[$z").description,
+            "erase rectangle (1, 1) to (last, last)"
+        );
+        assert_eq!(
+            parsed("\x1b[35;5;1;5;80$x").description,
+            "fill rectangle (5, 1) to (5, 80) with '#'"
+        );
+        assert_eq!(
+            parsed("\x1b[1;1;;;1;4$r").description,
+            "change attributes in rectangle (1, 1) to (last, last): bold, underline"
+        );
+        assert_eq!(parsed("\x1b[1$w").kind, EscapeKind::Unknown);
+    }
+
+    #[test]
+    fn test_parse_charset_designation() {
+        let segments = parse("\x1b(0lqk\x1b(B\x1b)%6");
+        let g0 = segments[0].as_escape().unwrap();
+        assert_eq!(g0.kind, EscapeKind::Charset);
+        assert_eq!(g0.description, "designate G0 charset: DEC line drawing");
+        assert!(matches!(&segments[1], ParsedSequence::Text(t) if t == "lqk"));
+        let g0 = segments[2].as_escape().unwrap();
+        assert_eq!(g0.description, "designate G0 charset: US ASCII");
+        assert_eq!(segments[3].as_escape().unwrap().kind, EscapeKind::Unknown);
+        assert_eq!(strip_ansi("\x1b(0q\x1b(B"), "q");
+
+        let mut parser = StreamParser::new();
+        assert_eq!(parser.push("a\x1b(").len(), 1);
+        assert_eq!(parser.push("0").len(), 1);
+    }
+
+    #[test]
+    fn test_parse_c1_introducers() {
+        let segments = parse("a\u{9b}31mred\u{9d}0;title\u{9c}\u{85}b");
+        assert!(matches!(&segments[1], ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr));
+        assert!(matches!(&segments[2], ParsedSequence::Text(t) if t == "red"));
+        assert!(matches!(&segments[3], ParsedSequence::Escape(e) if e.kind == EscapeKind::Osc));
+        assert!(matches!(&segments[4], ParsedSequence::Text(t) if t == "\u{85}b"));
+        assert_eq!(strip_ansi("\u{9b}1mx\u{9d}8;;u\x07y"), "xy");
+
+        let bytes = parse_bytes(b"\x9b32mok\xff\x9d0;t\x1b\\\xc3\xa9");
+        assert!(matches!(&bytes[0], ParsedSequence::Escape(e) if e.description == "green fg"));
+        assert!(matches!(&bytes[1], ParsedSequence::Text(t) if t == "ok\u{fffd}"));
+        assert!(matches!(&bytes[2], ParsedSequence::Escape(e) if e.kind == EscapeKind::Osc));
+        assert!(matches!(&bytes[3], ParsedSequence::Text(t) if t == "\u{e9}"));
+
+        let mut parser = StreamParser::new();
+        assert!(parser.push("\u{9b}3").is_empty());
+        assert_eq!(parser.push("1m").len(), 1);
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid_utf8() {
+        let text = |segments: &[ParsedSequence]| segments[0].as_text().map(String::from);
+        let input = b"\xe9t\xe9 \xc3";
+        assert_eq!(
+            text(&parse_bytes(input)).as_deref(),
+            Some("\u{fffd}t\u{fffd} \u{fffd}")
+        );
+        assert_eq!(
+            text(&parse_bytes_with(input, InvalidUtf8::Latin1)).as_deref(),
+            Some("\u{e9}t\u{e9} \u{c3}")
+        );
+
+        let mut parser = StreamParser::new();
+        let mut segments = parser.push_bytes(b"\x1b[1mcaf\xc3");
+        segments.extend(parser.push_bytes(b"\xa9\xe2\x94"));
+        segments.extend(parser.finish());
+        let texts: Vec<_> = segments
+            .iter()
+            .filter_map(ParsedSequence::as_text)
+            .collect();
+        assert_eq!(texts, ["caf", "\u{e9}", "\u{fffd}"]);
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let segments = parse("a\x1b[31\nb\x1b");
+        assert!(
+            matches!(&segments[1], ParsedSequence::Malformed { raw, reason: MalformedReason::InvalidCsiByte('\n') } if raw == "\x1b[31")
+        );
+        assert!(matches!(
+            &segments[2],
+            ParsedSequence::Control(ControlChar::LineFeed)
+        ));
+        assert!(matches!(&segments[3], ParsedSequence::Text(t) if t == "b"));
+        assert!(matches!(
+            &segments[4],
+            ParsedSequence::Malformed {
+                reason: MalformedReason::TruncatedEscape,
+                ..
+            }
+        ));
+
+        let segments = parse("\x1b]0;title\x1b[1m");
+        assert!(matches!(
+            &segments[0],
+            ParsedSequence::Malformed {
+                reason: MalformedReason::UnterminatedOsc,
+                ..
+            }
+        ));
+        assert!(matches!(&segments[1], ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr));
+
+        let segments = parse("\x1b]0;title\x1b\\x");
+        assert!(matches!(&segments[0], ParsedSequence::Escape(e) if e.kind == EscapeKind::Osc));
+        assert_eq!(segments.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_controls() {
+        let segments = parse("50%\r\x1b[2K100%\x07\n");
+        assert!(matches!(
+            &segments[1],
+            ParsedSequence::Control(ControlChar::CarriageReturn)
+        ));
+        assert!(matches!(&segments[3], ParsedSequence::Text(t) if t == "100%"));
+        assert_eq!(segments[4].as_control(), Some(ControlChar::Bell));
+        assert_eq!(segments[5].as_control(), Some(ControlChar::LineFeed));
+        assert_eq!(strip_ansi("a\tb\x1b[1m\r\n"), "a\tb\r\n");
+    }
+
+    #[test]
+    fn test_stream_parser() {
+        let mut parser = StreamParser::new();
+        let mut segments = Vec::new();
+        for chunk in ["a\x1b", "[1", ";31mb\x1b]0;t", "itle\x1b", "\\c"] {
+            segments.extend(parser.push(chunk));
+        }
+        segments.extend(parser.finish());
+
+        let kinds: Vec<_> = segments
+            .iter()
+            .map(|s| s.as_escape().map(|e| e.kind.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                None,
+                Some(EscapeKind::Sgr),
+                None,
+                Some(EscapeKind::Osc),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_wide_and_unparsed_params() {
+        let segments = parse("\x1b[1;4:3;38:2::255:0:0;;70000m\x1b[;5H");
+        let sgr = segments[0].as_escape().unwrap();
+        assert_eq!(sgr.params.as_slice(), &[1, 38, 2, 255, 0, 0, 0, 70000]);
+        assert_eq!(sgr.unparsed, vec!["4:3".to_string()]);
+        assert!(sgr.description.ends_with("unparsed '4:3'"));
+
+        let cup = segments[1].as_escape().unwrap();
+        assert_eq!(cup.description, "cursor to (1, 5)");
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let err = parse_strict("ok\x1b]8;;http://x").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert_eq!(err.reason, MalformedReason::UnterminatedOsc);
+
+        let err = parse_strict("\x1b[38;5;5000000000m").unwrap_err();
+        assert_eq!(err.reason, MalformedReason::ParamOverflow);
+        assert!(matches!(
+            crate::Error::from(err),
+            crate::Error::MalformedSequence(_)
+        ));
+    }
+
     #[test]
     fn test_strip_ansi() {
         let stripped = strip_ansi("\x1b[1;31mBold Red\x1b[0m Text");
@@ -226,5 +1324,64 @@ mod tests {
     fn test_visible_len() {
         let len = visible_len("\x1b[31mHello\x1b[0m");
         assert_eq!(len, 5);
+
+        let mixed = "caf\u{e9}\t\x1b]8;;url\x1b\\x\x1b[3";
+        assert_eq!(strip_ansi(mixed), "caf\u{e9}\tx");
+        assert_eq!(visible_len(mixed), 6);
+    }
+
+    #[test]
+    fn test_parse_visit() {
+        #[derive(Default)]
+        struct Log(Vec<String>);
+
+        impl Visitor for Log {
+            fn on_text(&mut self, text: &str) {
+                self.0.push(format!("text {text}"));
+            }
+            fn on_control(&mut self, control: ControlChar) {
+                self.0.push(format!("control {control:?}"));
+            }
+            fn on_sgr(&mut self, params: &[u32]) {
+                self.0.push(format!("sgr {params:?}"));
+            }
+            fn on_cursor(&mut self, op: CursorOp) {
+                self.0.push(format!("cursor {op:?}"));
+            }
+            fn on_erase(&mut self, op: EraseOp) {
+                self.0.push(format!("erase {op:?}"));
+            }
+            fn on_osc(&mut self, body: &str) {
+                self.0.push(format!("osc {body}"));
+            }
+            fn on_escape(&mut self, escape: &Escape) {
+                self.0.push(format!("escape {:?}", escape.raw));
+            }
+            fn on_malformed(&mut self, raw: &str, reason: MalformedReason) {
+                self.0.push(format!("malformed {raw:?} {reason:?}"));
+            }
+        }
+
+        let input =
+            "\x1b[1;31mhi\x1b[m\r\n\x1b[2;5H\x1b[2K\x1b7\x1b]0;title\x07\x1b[?25l\x1b[4J\x1b[";
+        let mut log = Log::default();
+        parse_visit(input, &mut log);
+        assert_eq!(
+            log.0,
+            [
+                "sgr [1, 31]",
+                "text hi",
+                "sgr []",
+                "control CarriageReturn",
+                "control LineFeed",
+                "cursor Goto(2, 5)",
+                "erase Line",
+                "cursor Save",
+                "osc 0;title",
+                "escape \"\\u{1b}[?25l\"",
+                "escape \"\\u{1b}[4J\"",
+                "malformed \"\\u{1b}[\" TruncatedCsi",
+            ]
+        );
     }
 }
@@ -0,0 +1,176 @@
+//! Crate-wide accessibility policy enforced at render time.
+
+use crate::color::Color;
+use crate::float;
+use crate::modifier::Modifier;
+use crate::style::Style;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// Accessibility rules applied by [`Styled::render`](crate::Styled::render)
+/// and [`Style::apply`](crate::Style::apply).
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// use glyphs::{set_policy, Policy};
+///
+/// set_policy(Policy {
+///     no_blink: true,
+///     min_contrast: 4.5,
+///     ..Policy::default()
+/// });
+/// # set_policy(Policy::default());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Policy {
+    /// Drop blinking attributes.
+    pub no_blink: bool,
+    /// Render dim text at normal intensity.
+    pub no_dim: bool,
+    /// Minimum WCAG contrast ratio between foreground and background.
+    ///
+    /// Only enforced when both colors are set and have a known RGB value.
+    /// `1.0` (the default) disables the check; WCAG AA asks for `4.5`.
+    pub min_contrast: f32,
+}
+
+impl Policy {
+    /// A policy that leaves styles untouched.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            no_blink: false,
+            no_dim: false,
+            min_contrast: 1.0,
+        }
+    }
+
+    /// Apply this policy to a style.
+    #[must_use]
+    pub fn enforce(&self, style: &Style) -> Style {
+        let mut style = *style;
+
+        if self.no_blink {
+            style.modifiers = style
+                .modifiers
+                .without(Modifier::Blink)
+                .without(Modifier::RapidBlink);
+        }
+        if self.no_dim {
+            style.modifiers = style.modifiers.without(Modifier::Dim);
+        }
+        if self.min_contrast > 1.0 {
+            if let (Some(fg), Some(bg)) = (style.foreground, style.background) {
+                style.foreground = Some(ensure_contrast(fg, bg, self.min_contrast));
+            }
+        }
+
+        style
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Move `fg` towards black or white until it reaches `min` contrast against `bg`.
+fn ensure_contrast(fg: Color, bg: Color, min: f32) -> Color {
+    let (Some(ratio), Some(bg_lum)) = (fg.contrast_ratio(&bg), bg.luminance()) else {
+        return fg;
+    };
+    if ratio >= min {
+        return fg;
+    }
+    let Some((r, g, b)) = fg.to_rgb() else {
+        return fg;
+    };
+
+    let target = if bg_lum > 0.179 { 0.0 } else { 255.0 };
+    let mix = |v: u8, t: f32| {
+        let v = f32::from(v);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let out = float::round(float::mul_add(target - v, t, v)).clamp(0.0, 255.0) as u8;
+        out
+    };
+
+    let mut candidate = fg;
+    for step in 1..=10u8 {
+        let t = f32::from(step) / 10.0;
+        candidate = Color::rgb(mix(r, t), mix(g, t), mix(b, t));
+        if candidate.contrast_ratio(&bg).is_some_and(|c| c >= min) {
+            break;
+        }
+    }
+    candidate
+}
+
+#[cfg(feature = "std")]
+static POLICY: RwLock<Policy> = RwLock::new(Policy::new());
+
+/// Set the accessibility policy for the whole process.
+#[cfg(feature = "std")]
+pub fn set_policy(policy: Policy) {
+    *POLICY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = policy;
+}
+
+/// The accessibility policy currently in effect.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn policy() -> Policy {
+    *POLICY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// The accessibility policy currently in effect.
+///
+/// Without `std` this is always the default policy.
+#[cfg(not(feature = "std"))]
+#[must_use]
+pub const fn policy() -> Policy {
+    Policy::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_drops_blink_and_dim() {
+        let style = Style::new()
+            .modifier(Modifier::Blink)
+            .modifier(Modifier::Dim)
+            .modifier(Modifier::Bold);
+        let policy = Policy {
+            no_blink: true,
+            no_dim: true,
+            ..Policy::default()
+        };
+        let enforced = policy.enforce(&style);
+        assert!(!enforced.modifiers.contains(Modifier::Blink));
+        assert!(!enforced.modifiers.contains(Modifier::Dim));
+        assert!(enforced.modifiers.contains(Modifier::Bold));
+    }
+
+    #[test]
+    fn test_policy_fixes_low_contrast() {
+        let style = Style::new()
+            .fg(Color::rgb(120, 120, 120))
+            .bg(Color::rgb(100, 100, 100));
+        let policy = Policy {
+            min_contrast: 4.5,
+            ..Policy::default()
+        };
+        let enforced = policy.enforce(&style);
+        let fg = enforced.foreground.unwrap();
+        assert!(fg.contrast_ratio(&Color::rgb(100, 100, 100)).unwrap() >= 4.5);
+    }
+}
@@ -0,0 +1,120 @@
+//! Syntax-colored JSON (requires the `pretty` feature).
+
+use crate::theme::Theme;
+use serde_json::Value;
+
+/// Pretty-print `value` with two-space indentation, colored with `theme`.
+///
+/// Object keys use [`Theme::info`], strings [`Theme::success`], numbers
+/// [`Theme::accent`], `true` and `false` [`Theme::warning`], and `null`
+/// [`Theme::muted`]; brackets and punctuation are left plain. Without the
+/// escape sequences the output is the same as
+/// `serde_json::to_string_pretty`.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{pretty_json, strip_ansi, Theme};
+///
+/// let config = serde_json::json!({ "name": "demo", "threads": 4, "debug": false });
+/// let colored = pretty_json(&config, &Theme::dark());
+/// println!("{colored}");
+/// assert_eq!(strip_ansi(&colored), serde_json::to_string_pretty(&config).unwrap());
+/// ```
+#[must_use]
+pub fn pretty_json(value: &Value, theme: &Theme) -> String {
+    let mut out = String::new();
+    write_value(value, theme, 0, &mut out);
+    out
+}
+
+fn write_value(value: &Value, theme: &Theme, depth: usize, out: &mut String) {
+    match value {
+        Value::Null => out.push_str(&theme.muted.apply("null")),
+        Value::Bool(b) => out.push_str(&theme.warning.apply(if *b { "true" } else { "false" })),
+        Value::Number(n) => out.push_str(&theme.accent.apply(&n.to_string())),
+        Value::String(s) => out.push_str(&theme.success.apply(&quote(s))),
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Object(entries) if entries.is_empty() => out.push_str("{}"),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                separate(i, depth + 1, out);
+                write_value(item, theme, depth + 1, out);
+            }
+            newline(depth, out);
+            out.push(']');
+        }
+        Value::Object(entries) => {
+            out.push('{');
+            for (i, (key, item)) in entries.iter().enumerate() {
+                separate(i, depth + 1, out);
+                out.push_str(&theme.info.apply(&quote(key)));
+                out.push_str(": ");
+                write_value(item, theme, depth + 1, out);
+            }
+            newline(depth, out);
+            out.push('}');
+        }
+    }
+}
+
+/// A JSON string literal with quotes and escapes.
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+/// Start the `i`th element of an array or object.
+fn separate(i: usize, depth: usize, out: &mut String) {
+    if i > 0 {
+        out.push(',');
+    }
+    newline(depth, out);
+}
+
+fn newline(depth: usize, out: &mut String) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::strip_ansi;
+    use serde_json::json;
+
+    #[test]
+    fn test_pretty_json_layout() {
+        let value = json!({
+            "name": "a \"quoted\"\nname",
+            "ports": [80, 443],
+            "empty": [],
+            "nested": { "ok": true, "none": null, "map": {} },
+            "ratio": 0.5,
+        });
+        let colored = pretty_json(&value, &Theme::dark());
+        assert_eq!(
+            strip_ansi(&colored),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert_eq!(pretty_json(&json!("x"), &Theme::new()), "\"x\"");
+    }
+
+    #[test]
+    fn test_pretty_json_colors() {
+        let theme = Theme::dark();
+        let colored = pretty_json(&json!({ "n": 1, "s": "v" }), &theme);
+        assert_eq!(
+            colored,
+            format!(
+                "{{\n  {}: {},\n  {}: {}\n}}",
+                theme.info.apply("\"n\""),
+                theme.accent.apply("1"),
+                theme.info.apply("\"s\""),
+                theme.success.apply("\"v\""),
+            )
+        );
+    }
+}
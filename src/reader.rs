@@ -0,0 +1,124 @@
+//! Async adapter that parses output as it is read.
+
+use crate::parser::{ParsedSequence, StreamParser};
+use alloc::collections::VecDeque;
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::io;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Reads from an [`AsyncRead`] and yields parsed segments as they become
+/// complete (requires the `tokio` feature).
+///
+/// Sequences split across reads are buffered by a [`StreamParser`], and
+/// invalid UTF-8 is handled as by [`parse_bytes`](crate::parse_bytes).
+/// The reader is also a [`Stream`] of `io::Result<ParsedSequence>`.
+///
+/// ```rust,no_run
+/// use glyphs::{AnsiReader, ParsedSequence};
+/// use tokio::io::AsyncRead;
+///
+/// async fn print_text(output: impl AsyncRead + Unpin) -> std::io::Result<()> {
+///     let mut reader = AnsiReader::new(output);
+///     while let Some(segment) = reader.next_segment().await {
+///         if let ParsedSequence::Text(text) = segment? {
+///             print!("{text}");
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AnsiReader<R> {
+    reader: R,
+    parser: StreamParser,
+    ready: VecDeque<ParsedSequence>,
+    buf: Box<[u8]>,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin> AnsiReader<R> {
+    /// Size of the read buffer.
+    const BUF_SIZE: usize = 8 * 1024;
+
+    /// Wrap `reader`.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: StreamParser::new(),
+            ready: VecDeque::new(),
+            buf: vec![0; Self::BUF_SIZE].into_boxed_slice(),
+            done: false,
+        }
+    }
+
+    /// The next complete segment, or `None` once the reader is exhausted.
+    ///
+    /// A sequence still incomplete at the end of input is reported as
+    /// [`ParsedSequence::Malformed`].
+    pub async fn next_segment(&mut self) -> Option<io::Result<ParsedSequence>> {
+        poll_fn(|cx| self.poll_segment(cx)).await
+    }
+
+    /// Poll for the next complete segment.
+    pub fn poll_segment(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<ParsedSequence>>> {
+        loop {
+            if let Some(segment) = self.ready.pop_front() {
+                return Poll::Ready(Some(Ok(segment)));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            let mut buf = ReadBuf::new(&mut self.buf);
+            match Pin::new(&mut self.reader).poll_read(cx, &mut buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(err)) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                    self.done = true;
+                    self.ready.extend(self.parser.finish());
+                }
+                Poll::Ready(Ok(())) => self.ready.extend(self.parser.push_bytes(buf.filled())),
+            }
+        }
+    }
+
+    /// Unwrap the inner reader, dropping any buffered input.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for AnsiReader<R> {
+    type Item = io::Result<ParsedSequence>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_segment(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::escape::EscapeKind;
+
+    #[tokio::test]
+    async fn test_reads_segments_until_eof() {
+        let mut reader = AnsiReader::new(&b"\x1b[1mhi\xc3\xa9\x1b[3"[..]);
+        let mut segments = Vec::new();
+        while let Some(segment) = reader.next_segment().await {
+            segments.push(segment.unwrap());
+        }
+        assert!(matches!(&segments[0], ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr));
+        assert!(matches!(&segments[1], ParsedSequence::Text(t) if t == "hi\u{e9}"));
+        assert!(matches!(&segments[2], ParsedSequence::Malformed { .. }));
+        assert_eq!(segments.len(), 3);
+    }
+}
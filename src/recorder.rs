@@ -0,0 +1,139 @@
+//! A writer that tees output while logging the escape sequences it contains.
+
+use crate::parser::{ParsedSequence, StreamParser};
+use crate::replay::take_utf8;
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// Passes bytes through to an inner writer while logging a timestamped,
+/// human-readable description of every sequence to a side channel.
+///
+/// Useful for finding out why a TUI flickers: wrap stdout in a recorder
+/// and log to a file.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::Recorder;
+/// use std::io::Write;
+///
+/// let mut log = Vec::new();
+/// let mut out = Recorder::new(Vec::new(), &mut log);
+/// write!(out, "\x1b[2J\x1b[1;1Hhello")?;
+/// let (_, log) = out.finish()?;
+///
+/// let log = String::from_utf8_lossy(log);
+/// assert!(log.contains("[erase] clear entire screen"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Recorder<W: Write, L: Write> {
+    inner: W,
+    log: L,
+    parser: StreamParser,
+    carry: Vec<u8>,
+    start: Instant,
+    log_text: bool,
+}
+
+impl<W: Write, L: Write> Recorder<W, L> {
+    /// Wrap `inner`, writing descriptions to `log`.
+    pub fn new(inner: W, log: L) -> Self {
+        Self {
+            inner,
+            log,
+            parser: StreamParser::new(),
+            carry: Vec::new(),
+            start: Instant::now(),
+            log_text: true,
+        }
+    }
+
+    /// Whether plain text segments are logged too (default `true`).
+    #[must_use]
+    pub fn log_text(mut self, enabled: bool) -> Self {
+        self.log_text = enabled;
+        self
+    }
+
+    /// Get a reference to the inner writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Log any buffered partial sequence and return the inner writer and the log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the log fails.
+    pub fn finish(mut self) -> io::Result<(W, L)> {
+        let segments = self.parser.finish();
+        self.record(segments)?;
+        self.log.flush()?;
+        Ok((self.inner, self.log))
+    }
+
+    fn record(&mut self, segments: Vec<ParsedSequence>) -> io::Result<()> {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        for segment in segments {
+            match segment {
+                ParsedSequence::Text(text) if self.log_text => {
+                    writeln!(self.log, "[{elapsed:>10.6}s] text {text:?}")?;
+                }
+                ParsedSequence::Text(_) => {}
+                ParsedSequence::Escape(e) => {
+                    writeln!(
+                        self.log,
+                        "[{elapsed:>10.6}s] {} {:?}",
+                        e.human_readable(),
+                        e.raw
+                    )?;
+                }
+                ParsedSequence::Control(c) => {
+                    writeln!(self.log, "[{elapsed:>10.6}s] [control] {c}")?;
+                }
+                ParsedSequence::Malformed { raw, reason } => {
+                    writeln!(self.log, "[{elapsed:>10.6}s] [malformed] {reason} {raw:?}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write, L: Write> Write for Recorder<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.carry.extend_from_slice(&buf[..written]);
+        let text = take_utf8(&mut self.carry);
+        let segments = self.parser.push(&text);
+        self.record(segments)?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        self.log.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_passes_through() {
+        let mut log = Vec::new();
+        let mut recorder = Recorder::new(Vec::new(), &mut log).log_text(false);
+        recorder.write_all(b"\x1b[1").unwrap();
+        recorder.write_all(b";31mred\r").unwrap();
+        let (out, _) = recorder.finish().unwrap();
+        assert_eq!(out, b"\x1b[1;31mred\r");
+
+        let log = String::from_utf8(log).unwrap();
+        let lines: Vec<_> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[SGR (style)] bold, red fg \"\\u{1b}[1;31m\""));
+        assert!(lines[1].ends_with("[control] carriage return"));
+    }
+}
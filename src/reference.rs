@@ -0,0 +1,354 @@
+//! Reference metadata for recognized escape sequences.
+
+use crate::escape::{Escape, EscapeDetail, EscapeKind};
+
+/// Where a sequence is specified and how well it is supported.
+///
+/// Returned by [`Escape::reference`] for tools that explain sequences to
+/// their users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeqInfo {
+    /// The standard mnemonic, such as `CUP`.
+    pub mnemonic: &'static str,
+    /// The full name, such as `Cursor Position`.
+    pub name: &'static str,
+    /// The standard or terminal that defines it (`ECMA-48`, `DEC VT100`,
+    /// `xterm`, ...).
+    pub standard: &'static str,
+    /// A short note on terminal support.
+    pub support: &'static str,
+}
+
+macro_rules! seq_info {
+    ($id:ident, $mnemonic:expr, $name:expr, $standard:expr, $support:expr) => {
+        static $id: SeqInfo = SeqInfo {
+            mnemonic: $mnemonic,
+            name: $name,
+            standard: $standard,
+            support: $support,
+        };
+    };
+}
+
+const UNIVERSAL: &str = "supported by all common terminals";
+const RECTANGLES: &str = "xterm, Windows Terminal, and a few others; ignored by most terminals";
+
+seq_info!(
+    SGR,
+    "SGR",
+    "Select Graphic Rendition",
+    "ECMA-48",
+    "universal for basic colors and bold; italic, strikethrough, and truecolor vary"
+);
+seq_info!(CUU, "CUU", "Cursor Up", "ECMA-48", UNIVERSAL);
+seq_info!(CUD, "CUD", "Cursor Down", "ECMA-48", UNIVERSAL);
+seq_info!(CUF, "CUF", "Cursor Forward", "ECMA-48", UNIVERSAL);
+seq_info!(CUB, "CUB", "Cursor Backward", "ECMA-48", UNIVERSAL);
+seq_info!(
+    CHA,
+    "CHA",
+    "Cursor Character Absolute",
+    "ECMA-48",
+    UNIVERSAL
+);
+seq_info!(CUP, "CUP", "Cursor Position", "ECMA-48", UNIVERSAL);
+seq_info!(
+    HVP,
+    "HVP",
+    "Character and Line Position",
+    "ECMA-48",
+    UNIVERSAL
+);
+seq_info!(
+    ED,
+    "ED",
+    "Erase in Page",
+    "ECMA-48",
+    "universal; mode 3 (scrollback) is an xterm extension"
+);
+seq_info!(EL, "EL", "Erase in Line", "ECMA-48", UNIVERSAL);
+seq_info!(
+    SM,
+    "SM",
+    "Set Mode",
+    "ECMA-48",
+    "few ANSI modes are implemented by modern terminals"
+);
+seq_info!(
+    RM,
+    "RM",
+    "Reset Mode",
+    "ECMA-48",
+    "few ANSI modes are implemented by modern terminals"
+);
+seq_info!(
+    DECSET,
+    "DECSET",
+    "DEC Private Mode Set",
+    "DEC VT100 / xterm",
+    "widely supported; individual modes vary"
+);
+seq_info!(
+    DECRST,
+    "DECRST",
+    "DEC Private Mode Reset",
+    "DEC VT100 / xterm",
+    "widely supported; individual modes vary"
+);
+seq_info!(
+    DECSTBM,
+    "DECSTBM",
+    "Set Top and Bottom Margins",
+    "DEC VT100",
+    UNIVERSAL
+);
+seq_info!(DECSC, "DECSC", "Save Cursor", "DEC VT100", UNIVERSAL);
+seq_info!(DECRC, "DECRC", "Restore Cursor", "DEC VT100", UNIVERSAL);
+seq_info!(
+    SCOSC,
+    "SCOSC",
+    "Save Current Cursor Position",
+    "SCO console / xterm",
+    "widely supported; prefer DECSC"
+);
+seq_info!(
+    SCORC,
+    "SCORC",
+    "Restore Saved Cursor Position",
+    "SCO console / xterm",
+    "widely supported; prefer DECRC"
+);
+seq_info!(
+    DECSCUSR,
+    "DECSCUSR",
+    "Set Cursor Style",
+    "DEC VT520 / xterm",
+    "supported by most modern terminals; ignored by the Linux console"
+);
+seq_info!(
+    DECKPAM,
+    "DECKPAM",
+    "Keypad Application Mode",
+    "DEC VT100",
+    UNIVERSAL
+);
+seq_info!(
+    DECKPNM,
+    "DECKPNM",
+    "Keypad Numeric Mode",
+    "DEC VT100",
+    UNIVERSAL
+);
+seq_info!(
+    DECDHL,
+    "DECDHL",
+    "Double-Height Line",
+    "DEC VT100",
+    "xterm, VTE, Konsole, and Windows Terminal; ignored by most others"
+);
+seq_info!(
+    DECSWL,
+    "DECSWL",
+    "Single-Width Line",
+    "DEC VT100",
+    "xterm, VTE, Konsole, and Windows Terminal; ignored by most others"
+);
+seq_info!(
+    DECDWL,
+    "DECDWL",
+    "Double-Width Line",
+    "DEC VT100",
+    "xterm, VTE, Konsole, and Windows Terminal; ignored by most others"
+);
+seq_info!(
+    RIS,
+    "RIS",
+    "Reset to Initial State",
+    "ECMA-48",
+    "universal; most terminals also clear the screen and scrollback"
+);
+seq_info!(
+    DECSTR,
+    "DECSTR",
+    "Soft Terminal Reset",
+    "DEC VT220",
+    "supported by xterm-compatible terminals; leaves the screen contents alone"
+);
+seq_info!(
+    DECCRA,
+    "DECCRA",
+    "Copy Rectangular Area",
+    "DEC VT420",
+    RECTANGLES
+);
+seq_info!(
+    DECERA,
+    "DECERA",
+    "Erase Rectangular Area",
+    "DEC VT420",
+    RECTANGLES
+);
+seq_info!(
+    DECFRA,
+    "DECFRA",
+    "Fill Rectangular Area",
+    "DEC VT420",
+    RECTANGLES
+);
+seq_info!(
+    DECCARA,
+    "DECCARA",
+    "Change Attributes in Rectangular Area",
+    "DEC VT420",
+    RECTANGLES
+);
+seq_info!(
+    DSR,
+    "DSR",
+    "Device Status Report",
+    "ECMA-48",
+    "universal; the reply arrives on the terminal's input"
+);
+seq_info!(
+    DA,
+    "DA",
+    "Device Attributes",
+    "ECMA-48",
+    "universal; the reply arrives on the terminal's input"
+);
+seq_info!(
+    DA2,
+    "DA2",
+    "Secondary Device Attributes",
+    "DEC VT220 / xterm",
+    "widely supported; the reply arrives on the terminal's input"
+);
+seq_info!(
+    CPR,
+    "CPR",
+    "Active Position Report",
+    "ECMA-48",
+    "sent by the terminal in reply to DSR 6"
+);
+seq_info!(
+    OSC,
+    "OSC",
+    "Operating System Command",
+    "ECMA-48 / xterm",
+    "command numbers are terminal-specific; unknown ones are ignored"
+);
+seq_info!(
+    SCS,
+    "SCS",
+    "Select Character Set",
+    "ECMA-35 / DEC VT100",
+    "DEC line drawing is universal; other sets vary"
+);
+
+impl Escape {
+    /// Reference metadata for this sequence, or `None` if it isn't one the
+    /// parser recognizes.
+    ///
+    /// ```rust
+    /// use glyphs::parse;
+    ///
+    /// let escape = parse("\x1b[5;10H")[0].as_escape().cloned().unwrap();
+    /// let info = escape.reference().unwrap();
+    /// assert_eq!(info.mnemonic, "CUP");
+    /// assert_eq!(info.standard, "ECMA-48");
+    /// ```
+    #[must_use]
+    pub fn reference(&self) -> Option<&'static SeqInfo> {
+        let final_char = self.raw.chars().last().unwrap_or_default();
+        let private = self.raw.contains('?');
+        let info = match (&self.kind, self.detail()) {
+            (EscapeKind::Sgr, _) => &SGR,
+            (EscapeKind::Osc, _) => &OSC,
+            (EscapeKind::Charset, _) => &SCS,
+            (_, EscapeDetail::CursorMove) => match final_char {
+                'A' => &CUU,
+                'B' => &CUD,
+                'C' => &CUF,
+                'D' => &CUB,
+                'G' => &CHA,
+                'H' => &CUP,
+                'f' => &HVP,
+                _ => return None,
+            },
+            (_, EscapeDetail::CursorSave) if final_char == 's' => &SCOSC,
+            (_, EscapeDetail::CursorSave) => &DECSC,
+            (_, EscapeDetail::CursorRestore) if final_char == 'u' => &SCORC,
+            (_, EscapeDetail::CursorRestore) => &DECRC,
+            (_, EscapeDetail::CursorStyle) => &DECSCUSR,
+            (_, EscapeDetail::EraseScreen) => &ED,
+            (_, EscapeDetail::EraseLine) => &EL,
+            (_, EscapeDetail::ModeSet) if private => &DECSET,
+            (_, EscapeDetail::ModeSet) => &SM,
+            (_, EscapeDetail::ModeReset) if private => &DECRST,
+            (_, EscapeDetail::ModeReset) => &RM,
+            (_, EscapeDetail::ScrollRegion) => &DECSTBM,
+            (_, EscapeDetail::KeypadApplication) => &DECKPAM,
+            (_, EscapeDetail::KeypadNumeric) => &DECKPNM,
+            (_, EscapeDetail::LineSize) => match final_char {
+                '3' | '4' => &DECDHL,
+                '5' => &DECSWL,
+                _ => &DECDWL,
+            },
+            (_, EscapeDetail::TerminalReset) if final_char == 'p' => &DECSTR,
+            (_, EscapeDetail::TerminalReset) => &RIS,
+            (_, EscapeDetail::DeviceStatus) => &DSR,
+            (_, EscapeDetail::DeviceAttributes) if self.raw.contains('>') => &DA2,
+            (_, EscapeDetail::DeviceAttributes) => &DA,
+            (_, EscapeDetail::CursorPositionReport) => &CPR,
+            (_, EscapeDetail::CopyRectangle) => &DECCRA,
+            (_, EscapeDetail::EraseRectangle) => &DECERA,
+            (_, EscapeDetail::FillRectangle) => &DECFRA,
+            (_, EscapeDetail::ChangeRectangleAttributes) => &DECCARA,
+            (_, EscapeDetail::FocusEvent | EscapeDetail::Other) => return None,
+        };
+        Some(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_reference() {
+        let mnemonics: Vec<Option<&str>> = parse(
+            "\x1b[1m\x1b[3A\x1b[2;3f\x1b7\x1b[u\x1b[2 q\x1b[2J\x1b[?25l\x1b[4h\x1b[1;9r\x1b=\x1b#4\x1b#6\x1bc\x1b[!p\x1b[$z\x1b[1;1;9;9;7$r\x1b[6n\x1b[>c\x1b]0;t\x07\x1b(0\x1b[9z",
+        )
+        .iter()
+        .filter_map(|s| s.as_escape().map(|e| e.reference().map(|info| info.mnemonic)))
+        .collect();
+        assert_eq!(
+            mnemonics,
+            [
+                Some("SGR"),
+                Some("CUU"),
+                Some("HVP"),
+                Some("DECSC"),
+                Some("SCORC"),
+                Some("DECSCUSR"),
+                Some("ED"),
+                Some("DECRST"),
+                Some("SM"),
+                Some("DECSTBM"),
+                Some("DECKPAM"),
+                Some("DECDHL"),
+                Some("DECDWL"),
+                Some("RIS"),
+                Some("DECSTR"),
+                Some("DECERA"),
+                Some("DECCARA"),
+                Some("DSR"),
+                Some("DA2"),
+                Some("OSC"),
+                Some("SCS"),
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Interning styles for render loops that reuse a few styles many times.
+
+use crate::style::Style;
+use crate::{CSI, SGR_SUFFIX};
+use std::collections::HashMap;
+
+/// A handle to a style interned in a [`StyleRegistry`].
+///
+/// Ids are small and `Copy`, so grids of cells can store them instead of
+/// whole styles. They are only meaningful for the registry that made them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleId(u32);
+
+impl StyleId {
+    /// Position of the style in its registry, in order of interning.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Lookup counts of a [`StyleRegistry`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    /// Interned styles that were already in the registry.
+    pub hits: u64,
+    /// Interned styles that had to be added.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that were hits, or 0.0 before any lookup.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    style: Style,
+    prefix: String,
+    off: String,
+}
+
+/// Interns styles and keeps their escape sequences ready to write.
+///
+/// Interning a style returns a [`StyleId`]; the SGR sequences turning it
+/// on and off are built once, the first time the style is seen, instead
+/// of on every cell. Like [`Style::prefix`], the sequences don't consult
+/// the color [`config`](crate::config) or the accessibility policy;
+/// enforce those on styles before interning them.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{Color, Style, StyleRegistry, RESET};
+///
+/// let mut registry = StyleRegistry::new();
+/// let mut out = String::new();
+/// for (i, cell) in ["a", "b", "c", "d"].iter().enumerate() {
+///     let color = if i % 2 == 0 { Color::Red } else { Color::Blue };
+///     let id = registry.intern(Style::new().fg(color));
+///     out.push_str(registry.prefix(id));
+///     out.push_str(cell);
+/// }
+/// out.push_str(RESET);
+///
+/// assert_eq!(registry.len(), 2);
+/// assert_eq!(registry.stats().hits, 2);
+/// assert!(out.starts_with("\x1b[31ma\x1b[34mb"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleRegistry {
+    entries: Vec<Entry>,
+    ids: HashMap<Style, StyleId>,
+    stats: CacheStats,
+}
+
+impl StyleRegistry {
+    /// An empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `style`, adding it to the registry if it isn't there yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct styles are interned.
+    pub fn intern(&mut self, style: Style) -> StyleId {
+        if let Some(&id) = self.ids.get(&style) {
+            self.stats.hits += 1;
+            return id;
+        }
+        self.stats.misses += 1;
+
+        let id = StyleId(u32::try_from(self.entries.len()).expect("too many styles"));
+        let prefix = if style.is_plain() {
+            String::new()
+        } else {
+            let mut prefix = String::from(CSI);
+            let _ = style.write_codes(&mut prefix);
+            prefix.push_str(SGR_SUFFIX);
+            prefix
+        };
+        self.entries.push(Entry {
+            style,
+            prefix,
+            off: style.off_sequence(),
+        });
+        self.ids.insert(style, id);
+        id
+    }
+
+    /// The id of `style` if it has been interned.
+    #[must_use]
+    pub fn get(&self, style: &Style) -> Option<StyleId> {
+        self.ids.get(style).copied()
+    }
+
+    /// The style behind `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` came from another registry with more styles.
+    #[must_use]
+    pub fn style(&self, id: StyleId) -> Style {
+        self.entries[id.index()].style
+    }
+
+    /// The SGR sequence turning the style on, empty for a plain style.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` came from another registry with more styles.
+    #[must_use]
+    pub fn prefix(&self, id: StyleId) -> &str {
+        &self.entries[id.index()].prefix
+    }
+
+    /// The SGR sequence turning off only what the style sets (`22` for
+    /// bold, `39` for a foreground color, ...), empty for a plain style.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` came from another registry with more styles.
+    #[must_use]
+    pub fn off(&self, id: StyleId) -> &str {
+        &self.entries[id.index()].off
+    }
+
+    /// Number of distinct styles interned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no style has been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hit and miss counts since the registry was created or the stats
+    /// were last reset.
+    #[must_use]
+    pub const fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Start counting hits and misses from zero, keeping the styles.
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::modifier::Modifier;
+
+    #[test]
+    fn test_intern() {
+        let mut registry = StyleRegistry::new();
+        let bold_red = Style::new().fg(Color::Red).modifier(Modifier::Bold);
+        let a = registry.intern(bold_red);
+        let b = registry.intern(Style::new());
+        assert_eq!(registry.intern(bold_red), a);
+        assert_ne!(a, b);
+
+        assert_eq!(registry.prefix(a), "\x1b[1;31m");
+        assert_eq!(registry.off(a), "\x1b[22;39m");
+        assert_eq!(registry.prefix(b), "");
+        assert_eq!(registry.style(a), bold_red);
+        assert_eq!(registry.get(&Style::new()), Some(b));
+        assert_eq!(registry.get(&Style::new().fg(Color::Blue)), None);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let mut registry = StyleRegistry::new();
+        assert!(registry.stats().hit_rate().abs() < f64::EPSILON);
+        for _ in 0..4 {
+            registry.intern(Style::new().fg(Color::Green));
+        }
+        assert_eq!(registry.stats(), CacheStats { hits: 3, misses: 1 });
+        assert!((registry.stats().hit_rate() - 0.75).abs() < f64::EPSILON);
+
+        registry.reset_stats();
+        assert_eq!(registry.stats(), CacheStats::default());
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+//! Timed replay of recorded terminal sessions.
+//!
+//! [`Replay`] holds output chunks with the delay before each one. It can be
+//! built from any source, or from the typescript and timing files written
+//! by util-linux `script --timing`.
+//!
+//! ```rust,no_run
+//! use glyphs::replay::Replay;
+//!
+//! let typescript = std::fs::read("typescript")?;
+//! let timing = std::fs::read_to_string("timing")?;
+//! let replay = Replay::from_script(&typescript, &timing)?;
+//! replay.play(std::io::stdout(), 2.0)?; // double speed
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::parser::{ParsedSequence, StreamParser};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// A chunk of output and the delay before it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedChunk {
+    /// Time since the previous chunk.
+    pub delay: Duration,
+    /// The output data.
+    pub data: String,
+}
+
+impl TimedChunk {
+    /// Create a timed chunk.
+    #[must_use]
+    pub fn new(delay: Duration, data: impl Into<String>) -> Self {
+        Self {
+            delay,
+            data: data.into(),
+        }
+    }
+}
+
+/// Error returned when a timing file cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingError {
+    /// One-based line number in the timing file.
+    pub line: usize,
+    /// What was wrong with the line.
+    pub message: String,
+}
+
+impl fmt::Display for TimingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid timing data on line {}: {}",
+            self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for TimingError {}
+
+/// A recorded session that can be replayed with its original timing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Replay {
+    chunks: Vec<TimedChunk>,
+}
+
+impl Replay {
+    /// Create a replay from timed chunks.
+    #[must_use]
+    pub fn new(chunks: Vec<TimedChunk>) -> Self {
+        Self { chunks }
+    }
+
+    /// Build a replay from a `script` typescript and its timing file.
+    ///
+    /// Both the classic (`delay bytes`) and advanced (`O delay bytes`)
+    /// timing formats are supported; only output (`O`) entries of the
+    /// advanced format are replayed. A leading `Script started on ...`
+    /// header line in the typescript is skipped. Invalid UTF-8 is replaced
+    /// with U+FFFD, without splitting characters across chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a timing line cannot be parsed.
+    pub fn from_script(typescript: &[u8], timing: &str) -> Result<Self, TimingError> {
+        let mut data = typescript;
+        if data.starts_with(b"Script started") {
+            let header_end = data
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(data.len(), |i| i + 1);
+            data = &data[header_end..];
+        }
+
+        let mut chunks = Vec::new();
+        let mut carry: Vec<u8> = Vec::new();
+        let mut pending_delay = Duration::ZERO;
+
+        for (index, line) in timing.lines().enumerate() {
+            let error = |message: &str| TimingError {
+                line: index + 1,
+                message: message.to_string(),
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (kind, delay, len) = match fields.as_slice() {
+                [] => continue,
+                [delay, len] => ("O", *delay, *len),
+                [kind, delay, len, ..] => (*kind, *delay, *len),
+                [_] => return Err(error("expected `delay bytes`")),
+            };
+            let delay = delay
+                .parse::<f64>()
+                .ok()
+                .filter(|d| d.is_finite() && *d >= 0.0)
+                .map(Duration::from_secs_f64)
+                .ok_or_else(|| error("invalid delay"))?;
+            pending_delay += delay;
+
+            match kind {
+                "O" => {
+                    let len: usize = len.parse().map_err(|_| error("invalid byte count"))?;
+                    let take = len.min(data.len());
+                    carry.extend_from_slice(&data[..take]);
+                    data = &data[take..];
+                    chunks.push(TimedChunk::new(pending_delay, take_utf8(&mut carry)));
+                    pending_delay = Duration::ZERO;
+                }
+                // Input, signal, and header entries only contribute their delay.
+                "I" | "S" | "H" => {}
+                _ => return Err(error("unknown entry type")),
+            }
+        }
+
+        if !carry.is_empty() {
+            chunks.push(TimedChunk::new(
+                pending_delay,
+                String::from_utf8_lossy(&carry).into_owned(),
+            ));
+        }
+        Ok(Self { chunks })
+    }
+
+    /// The chunks of this replay.
+    #[must_use]
+    pub fn chunks(&self) -> &[TimedChunk] {
+        &self.chunks
+    }
+
+    /// Total duration of the recording at normal speed.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.chunks.iter().map(|c| c.delay).sum()
+    }
+
+    /// Parse the recording, pairing each chunk's delay with the segments
+    /// it completes.
+    ///
+    /// Sequences split across chunks are reported with the chunk that
+    /// completes them.
+    #[must_use]
+    pub fn segments(&self) -> Vec<(Duration, Vec<ParsedSequence>)> {
+        let mut parser = StreamParser::new();
+        let mut result: Vec<_> = self
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.delay, parser.push(&chunk.data)))
+            .collect();
+        let rest = parser.finish();
+        if !rest.is_empty() {
+            result.push((Duration::ZERO, rest));
+        }
+        result
+    }
+
+    /// Write the recording to `writer`, sleeping between chunks.
+    ///
+    /// `speed` scales playback: `2.0` plays twice as fast, and values that
+    /// are zero, negative, or not finite write everything without delay.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn play<W: Write>(&self, mut writer: W, speed: f64) -> io::Result<()> {
+        for chunk in &self.chunks {
+            if speed.is_finite() && speed > 0.0 && !chunk.delay.is_zero() {
+                std::thread::sleep(chunk.delay.div_f64(speed));
+            }
+            writer.write_all(chunk.data.as_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode the complete UTF-8 prefix of `buf`, leaving a trailing partial
+/// character in place and replacing invalid bytes.
+pub(crate) fn take_utf8(buf: &mut Vec<u8>) -> String {
+    let mut out = String::new();
+    let mut rest = buf.as_slice();
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(s) => {
+                out.push_str(s);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                out.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                let Some(len) = e.error_len() else {
+                    rest = after;
+                    break;
+                };
+                out.push(char::REPLACEMENT_CHARACTER);
+                rest = &after[len..];
+            }
+        }
+    }
+    *buf = rest.to_vec();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_script_classic() {
+        let typescript = "Script started on 2024-01-01\nab\x1b[31mc\u{e9}".as_bytes();
+        // "ab\x1b[3" is 5 bytes, then "1mc" + the first byte of é, then the rest.
+        let timing = "0.5 5\n0.25 4\n1.0 1\n";
+        let replay = Replay::from_script(typescript, timing).unwrap();
+        let data: Vec<_> = replay.chunks().iter().map(|c| c.data.as_str()).collect();
+        assert_eq!(data, vec!["ab\x1b[3", "1mc", "\u{e9}"]);
+        assert_eq!(replay.duration(), Duration::from_millis(1750));
+
+        let segments = replay.segments();
+        assert_eq!(segments[0].1.len(), 1);
+        assert_eq!(segments[1].1.len(), 2);
+    }
+
+    #[test]
+    fn test_from_script_advanced() {
+        let timing = "H 0 START_TIME 1\nO 0.1 2\nI 0.2 1\nO 0.3 1\n";
+        let replay = Replay::from_script(b"abc", timing).unwrap();
+        assert_eq!(
+            replay.chunks()[1],
+            TimedChunk::new(Duration::from_millis(500), "c")
+        );
+        assert!(Replay::from_script(b"", "x y").is_err());
+    }
+
+    #[test]
+    fn test_play() {
+        let replay = Replay::new(vec![
+            TimedChunk::new(Duration::ZERO, "a"),
+            TimedChunk::new(Duration::from_millis(1), "b"),
+        ]);
+        let mut out = Vec::new();
+        replay.play(&mut out, 0.0).unwrap();
+        assert_eq!(out, b"ab");
+    }
+}
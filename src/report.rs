@@ -0,0 +1,278 @@
+//! Source snippets with an underlined span, for error messages.
+//!
+//! [`Report`] lays out a message, a `--> path:line:col` location, and the
+//! affected source lines with line numbers in a gutter and the span marked
+//! by carets. It only draws; finding the span is up to the caller.
+//!
+//! ```rust
+//! use glyphs::{strip_ansi, LogLevel, Report};
+//!
+//! let source = "[package]\nname = = \"demo\"\n";
+//! let report = Report::new(LogLevel::Error, "unexpected `=`", source, 17..18)
+//!     .path("Cargo.toml")
+//!     .label("expected a value");
+//! assert_eq!(
+//!     strip_ansi(&report.to_string()),
+//!     "error: unexpected `=`\n  --> Cargo.toml:2:8\n  |\n2 | name = = \"demo\"\n  |        ^ expected a value"
+//! );
+//! ```
+
+use crate::modifier::Modifier;
+use crate::style::Style;
+use crate::theme::{LogLevel, Theme};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// Columns a tab is expanded to in snippets.
+const TAB_WIDTH: usize = 4;
+
+/// A message pointing at a span of source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<'a> {
+    level: LogLevel,
+    message: String,
+    source: &'a str,
+    span: Range<usize>,
+    label: Option<String>,
+    path: Option<String>,
+    context: usize,
+    theme: Theme,
+}
+
+impl<'a> Report<'a> {
+    /// Report `message` about the bytes `span` of `source`.
+    ///
+    /// The span is clamped to the source and widened to character
+    /// boundaries; an empty span marks a single position.
+    #[must_use]
+    pub fn new(
+        level: LogLevel,
+        message: impl Into<String>,
+        source: &'a str,
+        span: Range<usize>,
+    ) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            source,
+            span,
+            label: None,
+            path: None,
+            context: 0,
+            theme: Theme::current(),
+        }
+    }
+
+    /// Text shown after the carets.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The file name shown in the location line.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Also show up to `lines` lines before and after the span.
+    #[must_use]
+    pub const fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+
+    /// Style the report with `theme` instead of [`Theme::current`].
+    #[must_use]
+    pub const fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The span clamped to the source and to character boundaries.
+    fn clamped_span(&self) -> Range<usize> {
+        let mut start = self.span.start.min(self.source.len());
+        while !self.source.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = self.span.end.clamp(start, self.source.len());
+        while !self.source.is_char_boundary(end) {
+            end += 1;
+        }
+        start..end
+    }
+}
+
+/// One source line with its byte range.
+struct Line<'s> {
+    number: usize,
+    start: usize,
+    text: &'s str,
+}
+
+fn lines(source: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    let mut lines: Vec<Line<'_>> = source
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, raw)| {
+            let line = Line {
+                number: i + 1,
+                start,
+                text: raw.trim_end_matches(['\n', '\r']),
+            };
+            start += raw.len();
+            line
+        })
+        .collect();
+    // A position just after the final newline is on an empty last line.
+    if lines.is_empty() || source.ends_with('\n') {
+        lines.push(Line {
+            number: lines.len() + 1,
+            start: source.len(),
+            text: "",
+        });
+    }
+    lines
+}
+
+/// Display columns of `text`, with tabs expanded.
+fn columns(text: &str) -> usize {
+    text.chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+fn paint(text: &str, style: Style) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        style.apply(text)
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.clamped_span();
+        let lines = lines(self.source);
+        let first = lines
+            .iter()
+            .rposition(|l| l.start <= span.start)
+            .unwrap_or(0);
+        let last = lines
+            .iter()
+            .rposition(|l| l.start < span.end.max(span.start + 1))
+            .unwrap_or(first)
+            .max(first);
+        let end = (last + 1 + self.context).min(lines.len());
+        let shown = first.saturating_sub(self.context)..end;
+
+        let marker = self.theme.for_log_level(self.level);
+        let gutter = self.theme.muted;
+        let width = lines[shown.end - 1].number.to_string().len();
+        let pad = " ".repeat(width);
+        let bar = paint("|", gutter);
+
+        let name = match self.level {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warning",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        };
+        write!(
+            f,
+            "{}{} {}",
+            paint(name, marker),
+            paint(":", Style::new().modifier(Modifier::Bold)),
+            paint(&self.message, Style::new().modifier(Modifier::Bold)),
+        )?;
+
+        let start_line = &lines[first];
+        let column = self.source[start_line.start..span.start].chars().count() + 1;
+        let location = match &self.path {
+            Some(path) => format!("{path}:{}:{column}", start_line.number),
+            None => format!("{}:{column}", start_line.number),
+        };
+        write!(f, "\n{pad} {} {location}", paint("-->", gutter))?;
+        write!(f, "\n{pad} {bar}")?;
+
+        for (index, line) in lines[shown.clone()].iter().enumerate() {
+            let index = shown.start + index;
+            let number = format!("{:>width$}", line.number);
+            let text = line.text.replace('\t', &" ".repeat(TAB_WIDTH));
+            write!(f, "\n{} {bar}", paint(&number, gutter))?;
+            if !text.is_empty() {
+                write!(f, " {text}")?;
+            }
+            if !(first..=last).contains(&index) {
+                continue;
+            }
+
+            let line_end = line.start + line.text.len();
+            let from = span.start.clamp(line.start, line_end) - line.start;
+            let to = span.end.clamp(line.start, line_end) - line.start;
+            let offset = columns(&line.text[..from]);
+            let carets = columns(&line.text[from..to]).max(1);
+            let mut underline = "^".repeat(carets);
+            if index == last {
+                if let Some(label) = &self.label {
+                    underline.push(' ');
+                    underline.push_str(label);
+                }
+            }
+            write!(
+                f,
+                "\n{pad} {bar} {}{}",
+                " ".repeat(offset),
+                paint(&underline, marker)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::strip_ansi;
+
+    fn plain(report: &Report<'_>) -> String {
+        strip_ansi(&report.to_string())
+    }
+
+    #[test]
+    fn test_multiline_span_and_context() {
+        let source = "a\nlet x = foo(\n  1,\n);\nb\nc";
+        let start = source.find("foo").unwrap();
+        let end = source.find(");").unwrap() + 1;
+        let report = Report::new(LogLevel::Warn, "long call", source, start..end)
+            .label("here")
+            .context(1);
+        assert_eq!(
+            plain(&report),
+            "warning: long call\n  --> 2:9\n  |\n1 | a\n2 | let x = foo(\n  |         ^^^^\n3 |   1,\n  | ^^^^\n4 | );\n  | ^ here\n5 | b"
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let report = Report::new(LogLevel::Error, "eof", "ab\n", 3..3);
+        assert_eq!(plain(&report), "error: eof\n  --> 2:1\n  |\n2 |\n  | ^");
+
+        let report = Report::new(LogLevel::Error, "tab", "\tx", 1..2).label("x");
+        assert_eq!(
+            plain(&report),
+            "error: tab\n  --> 1:2\n  |\n1 |     x\n  |     ^ x"
+        );
+
+        // Spans are clamped to the source and to character boundaries.
+        let report = Report::new(LogLevel::Info, "wide", "é!", 1..99);
+        assert_eq!(plain(&report), "info: wide\n  --> 1:1\n  |\n1 | é!\n  | ^^");
+    }
+}
@@ -0,0 +1,51 @@
+//! Panic hook that puts the terminal back into a usable state.
+
+use crate::sequences::{ALT_SCREEN_EXIT, BRACKETED_PASTE_DISABLE, CURSOR_SHOW, MOUSE_DISABLE};
+use crate::RESET;
+use std::io::Write;
+
+/// The sequence written by [`install_restore_hook`] when a panic occurs.
+///
+/// Shows the cursor, leaves the alternate screen, disables mouse tracking
+/// and bracketed paste, and resets all SGR attributes.
+#[must_use]
+pub fn restore_sequence() -> String {
+    format!("{CURSOR_SHOW}{ALT_SCREEN_EXIT}{MOUSE_DISABLE}{BRACKETED_PASTE_DISABLE}{RESET}")
+}
+
+/// Install a panic hook that restores the terminal before the panic message
+/// is printed.
+///
+/// The previously installed hook (usually the default one that prints the
+/// panic message) still runs afterwards, so the message ends up on the
+/// normal screen instead of being swallowed by the alternate screen.
+///
+/// # Example
+///
+/// ```rust
+/// glyphs::install_restore_hook();
+/// ```
+pub fn install_restore_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(restore_sequence().as_bytes());
+        let _ = stdout.flush();
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_sequence() {
+        let seq = restore_sequence();
+        assert!(seq.starts_with(CURSOR_SHOW));
+        assert!(seq.contains(ALT_SCREEN_EXIT));
+        assert!(seq.contains(MOUSE_DISABLE));
+        assert!(seq.contains(BRACKETED_PASTE_DISABLE));
+        assert!(seq.ends_with(RESET));
+    }
+}
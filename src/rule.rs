@@ -0,0 +1,79 @@
+//! Horizontal dividers.
+
+use crate::style::Style;
+use crate::width::WidthPolicy;
+use alloc::string::String;
+
+/// The character used by [`titled_rule`].
+pub const RULE_CHAR: char = '─';
+
+/// Render `ch` repeated `width` times in `style`.
+///
+/// ```rust
+/// use glyphs::{rule, Style};
+///
+/// assert_eq!(rule(5, '=', Style::new()), "=====");
+/// ```
+#[must_use]
+pub fn rule(width: usize, ch: char, style: Style) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let line: String = core::iter::repeat(ch).take(width).collect();
+    style.apply(&line)
+}
+
+/// Render a [`RULE_CHAR`] divider `width` columns wide with `title`
+/// centered in it.
+///
+/// `title` may already be styled; its escape sequences don't count
+/// towards the width and it keeps its own colors. It is measured in
+/// terminal columns as by [`WidthPolicy::visible_width`], so wide
+/// characters count twice. When the title doesn't
+/// fit, it is returned padded by one space on each side.
+///
+/// ```rust
+/// use glyphs::{strip_ansi, titled_rule, Style};
+///
+/// let line = titled_rule("Tests", 15, Style::new());
+/// assert_eq!(strip_ansi(&line), "──── Tests ────");
+/// ```
+#[must_use]
+pub fn titled_rule(title: &str, width: usize, style: Style) -> String {
+    let remaining = width.saturating_sub(WidthPolicy::new().visible_width(title) + 2);
+    let left = remaining / 2;
+
+    let mut out = rule(left, RULE_CHAR, style);
+    out.push(' ');
+    out.push_str(title);
+    out.push(' ');
+    out.push_str(&rule(remaining - left, RULE_CHAR, style));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caps::ColorLevel;
+    use crate::color::Color;
+    use crate::parser::visible_len;
+    use crate::style::style;
+
+    #[test]
+    fn test_titled_rule_width() {
+        let title = style("Build")
+            .fg(Color::Green)
+            .color_level(ColorLevel::Ansi16)
+            .render();
+        let line = titled_rule(&title, 12, Style::new());
+        assert_eq!(line, "── \x1b[32mBuild\x1b[0m ───");
+        assert_eq!(visible_len(&line), 12);
+
+        assert_eq!(titled_rule("too long", 4, Style::new()), " too long ");
+        assert_eq!(
+            titled_rule("\u{6e2c}\u{8a66}", 10, Style::new()),
+            "── \u{6e2c}\u{8a66} ──"
+        );
+        assert_eq!(rule(0, '-', Style::new().fg(Color::Red)), "");
+    }
+}
@@ -1,11 +1,64 @@
 //! Sequence building utilities.
 
-use std::fmt;
+use crate::caps::TermCaps;
+use crate::style::Style;
+use crate::width::WidthPolicy;
+use crate::RESET;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// A rectangle of screen cells, with 1-indexed coordinates like
+/// [`cursor::goto`](crate::cursor::goto).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// Row of the top edge.
+    pub top: u16,
+    /// Column of the left edge.
+    pub left: u16,
+    /// Number of columns.
+    pub width: u16,
+    /// Number of rows.
+    pub height: u16,
+}
+
+impl Rect {
+    /// Create a rectangle from its top-left corner and size.
+    #[must_use]
+    pub const fn new(top: u16, left: u16, width: u16, height: u16) -> Self {
+        Self {
+            top,
+            left,
+            width,
+            height,
+        }
+    }
+
+    /// Row of the bottom edge.
+    #[must_use]
+    pub const fn bottom(&self) -> u16 {
+        self.top.saturating_add(self.height).saturating_sub(1)
+    }
+
+    /// Column of the right edge.
+    #[must_use]
+    pub const fn right(&self) -> u16 {
+        self.left.saturating_add(self.width).saturating_sub(1)
+    }
+
+    /// Whether the rectangle covers no cells.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
 
 /// A builder for constructing ANSI escape sequences.
 #[derive(Debug, Clone, Default)]
 pub struct SequenceBuilder {
     sequences: Vec<String>,
+    caps: TermCaps,
 }
 
 impl SequenceBuilder {
@@ -15,6 +68,16 @@ impl SequenceBuilder {
         Self::default()
     }
 
+    /// Use the sequences `caps` says the terminal understands.
+    ///
+    /// Without this, [`repeat_char`](Self::repeat_char) and
+    /// [`fill_region`](Self::fill_region) write the characters out.
+    #[must_use]
+    pub const fn with_caps(mut self, caps: TermCaps) -> Self {
+        self.caps = caps;
+        self
+    }
+
     /// Add a raw sequence.
     #[must_use]
     pub fn raw(mut self, seq: &str) -> Self {
@@ -24,19 +87,20 @@ impl SequenceBuilder {
 
     /// Add a CSI sequence with the given parameters and final character.
     #[must_use]
-    pub fn csi(mut self, params: &[u16], final_char: char) -> Self {
+    pub fn csi(mut self, params: &[u32], final_char: char) -> Self {
         let params_str = params
             .iter()
-            .map(std::string::ToString::to_string)
+            .map(ToString::to_string)
             .collect::<Vec<_>>()
             .join(";");
-        self.sequences.push(format!("\x1b[{params_str}{final_char}"));
+        self.sequences
+            .push(format!("\x1b[{params_str}{final_char}"));
         self
     }
 
     /// Add an SGR (style) sequence.
     #[must_use]
-    pub fn sgr(self, params: &[u16]) -> Self {
+    pub fn sgr(self, params: &[u32]) -> Self {
         self.csi(params, 'm')
     }
 
@@ -60,6 +124,200 @@ impl SequenceBuilder {
         self
     }
 
+    /// Restrict scrolling to rows `top` through `bottom` (1-indexed, DECSTBM).
+    #[must_use]
+    pub fn scroll_region(mut self, top: u16, bottom: u16) -> Self {
+        self.sequences.push(format!("\x1b[{top};{bottom}r"));
+        self
+    }
+
+    /// Reset the scroll region to the whole screen.
+    #[must_use]
+    pub fn reset_scroll_region(self) -> Self {
+        self.raw(crate::sequences::RESET_SCROLL_REGION)
+    }
+
+    /// Enable or disable alternate scroll mode (`?1007`).
+    #[must_use]
+    pub fn alternate_scroll(self, enabled: bool) -> Self {
+        self.raw(if enabled {
+            crate::sequences::ALT_SCROLL_ENABLE
+        } else {
+            crate::sequences::ALT_SCROLL_DISABLE
+        })
+    }
+
+    /// Switch the keypad between application (`ESC =`) and numeric
+    /// (`ESC >`) mode.
+    #[must_use]
+    pub fn keypad_application(self, enabled: bool) -> Self {
+        self.raw(if enabled {
+            crate::sequences::KEYPAD_APPLICATION
+        } else {
+            crate::sequences::KEYPAD_NUMERIC
+        })
+    }
+
+    /// Enable or disable focus reporting (`?1004`).
+    #[must_use]
+    pub fn focus_reporting(self, enabled: bool) -> Self {
+        self.raw(if enabled {
+            crate::sequences::FOCUS_REPORTING_ENABLE
+        } else {
+            crate::sequences::FOCUS_REPORTING_DISABLE
+        })
+    }
+
+    /// Print `c` `n` times.
+    ///
+    /// With [`TermCaps::repeat`], `c` is printed once and repeated with
+    /// REP (`CSI n b`) when that is shorter; otherwise, and for control
+    /// characters, it is written out `n` times.
+    ///
+    /// ```rust
+    /// use glyphs::{SequenceBuilder, TermCaps};
+    ///
+    /// let caps = TermCaps { repeat: true, ..TermCaps::default() };
+    /// let rule = SequenceBuilder::new().with_caps(caps).repeat_char('-', 40).build();
+    /// assert_eq!(rule, "-\x1b[39b");
+    /// assert_eq!(SequenceBuilder::new().repeat_char('-', 3).build(), "---");
+    /// ```
+    #[must_use]
+    pub fn repeat_char(mut self, c: char, n: u16) -> Self {
+        let mut out = String::new();
+        push_repeated(&mut out, c, n, self.caps.repeat);
+        self.sequences.push(out);
+        self
+    }
+
+    /// Fill `rect` with `c` in `style`.
+    ///
+    /// With [`TermCaps::rectangles`], this is one DECFRA sequence (`CSI Pc
+    /// ; Pt ; Pl ; Pb ; Pr $ x`) that leaves the cursor where it was;
+    /// otherwise each row is written in turn with
+    /// [`repeat_char`](Self::repeat_char), leaving the cursor after the
+    /// last row. DECFRA only fills with printable Latin-1 characters, so
+    /// other characters always take the second path. A wide `c` is
+    /// written once per two columns, leaving the last column of an
+    /// odd-width `rect` untouched. The style is reset afterwards.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Rect, SequenceBuilder, Style};
+    ///
+    /// let blue = Style::new().bg(Color::Blue);
+    /// let fill = SequenceBuilder::new().fill_region(Rect::new(2, 3, 4, 2), ' ', blue).build();
+    /// assert_eq!(fill, "\x1b[44m\x1b[2;3H    \x1b[3;3H    \x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn fill_region(mut self, rect: Rect, c: char, style: Style) -> Self {
+        if rect.is_empty() {
+            return self;
+        }
+
+        let mut out = String::from(style.prefix().as_str());
+        if self.caps.rectangles && matches!(c, ' '..='~' | '\u{a0}'..='\u{ff}') {
+            let _ = write!(
+                out,
+                "\x1b[{};{};{};{};{}$x",
+                u32::from(c),
+                rect.top,
+                rect.left,
+                rect.bottom(),
+                rect.right()
+            );
+        } else {
+            // Control characters and combining marks count as one column.
+            let char_width = WidthPolicy::new().char_width(c).max(1);
+            let count = rect.width / u16::try_from(char_width).unwrap_or(u16::MAX);
+            for row in rect.top..=rect.bottom() {
+                let _ = write!(out, "\x1b[{row};{}H", rect.left);
+                push_repeated(&mut out, c, count, self.caps.repeat);
+            }
+        }
+        if !style.is_plain() {
+            out.push_str(RESET);
+        }
+        self.sequences.push(out);
+        self
+    }
+
+    /// Erase `rect` to blank cells.
+    ///
+    /// With [`TermCaps::rectangles`], this is one DECERA sequence (`CSI Pt
+    /// ; Pl ; Pb ; Pr $ z`); otherwise the area is overwritten with spaces
+    /// as by [`fill_region`](Self::fill_region).
+    #[must_use]
+    pub fn erase_region(mut self, rect: Rect) -> Self {
+        if rect.is_empty() {
+            return self;
+        }
+        if !self.caps.rectangles {
+            return self.fill_region(rect, ' ', Style::new());
+        }
+        self.sequences.push(format!(
+            "\x1b[{};{};{};{}$z",
+            rect.top,
+            rect.left,
+            rect.bottom(),
+            rect.right()
+        ));
+        self
+    }
+
+    /// Copy the contents of `rect` so its top-left corner lands at `row`,
+    /// `col` (DECCRA, `CSI ... $ v`).
+    ///
+    /// There is no fallback: the screen contents can't be read back, so
+    /// check [`TermCaps::rectangles`] first. Terminals without support
+    /// ignore the sequence.
+    ///
+    /// ```rust
+    /// use glyphs::{Rect, SequenceBuilder};
+    ///
+    /// let seq = SequenceBuilder::new().copy_region(Rect::new(1, 1, 10, 2), 5, 20).build();
+    /// assert_eq!(seq, "\x1b[1;1;2;10;1;5;20;1$v");
+    /// ```
+    #[must_use]
+    pub fn copy_region(mut self, rect: Rect, row: u16, col: u16) -> Self {
+        if rect.is_empty() {
+            return self;
+        }
+        self.sequences.push(format!(
+            "\x1b[{};{};{};{};1;{row};{col};1$v",
+            rect.top,
+            rect.left,
+            rect.bottom(),
+            rect.right()
+        ));
+        self
+    }
+
+    /// Apply SGR `params` to the text already in `rect` (DECCARA, `CSI ...
+    /// $ r`).
+    ///
+    /// Terminals only honor bold, underline, blink, and reverse (and `0`
+    /// and their off codes) here, not colors. As with
+    /// [`copy_region`](Self::copy_region), there is no fallback.
+    #[must_use]
+    pub fn change_attributes(mut self, rect: Rect, params: &[u32]) -> Self {
+        if rect.is_empty() {
+            return self;
+        }
+        let mut seq = format!(
+            "\x1b[{};{};{};{}",
+            rect.top,
+            rect.left,
+            rect.bottom(),
+            rect.right()
+        );
+        for param in params {
+            let _ = write!(seq, ";{param}");
+        }
+        seq.push_str("$r");
+        self.sequences.push(seq);
+        self
+    }
+
     /// Build the final sequence string.
     #[must_use]
     pub fn build(self) -> String {
@@ -67,6 +325,23 @@ impl SequenceBuilder {
     }
 }
 
+/// Write `c` `n` times, using REP if `rep` is set and it is shorter.
+fn push_repeated(out: &mut String, c: char, n: u16, rep: bool) {
+    if n == 0 {
+        return;
+    }
+    out.push(c);
+    let rest = n - 1;
+    let sequence = format!("\x1b[{rest}b");
+    if rep && !c.is_control() && sequence.len() < usize::from(rest) * c.len_utf8() {
+        out.push_str(&sequence);
+    } else {
+        for _ in 0..rest {
+            out.push(c);
+        }
+    }
+}
+
 impl fmt::Display for SequenceBuilder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.clone().build())
@@ -137,10 +412,8 @@ mod tests {
 
     #[test]
     fn test_sequence_builder() {
-        let seq = SequenceBuilder::new()
-            .sgr(&[1, 31])
-            .build();
-        
+        let seq = SequenceBuilder::new().sgr(&[1, 31]).build();
+
         assert_eq!(seq, "\x1b[1;31m");
     }
 
@@ -151,9 +424,97 @@ mod tests {
             .cursor_to(1, 1)
             .sgr(&[32])
             .build();
-        
+
         assert!(seq.contains("\x1b[2J"));
         assert!(seq.contains("\x1b[1;1H"));
         assert!(seq.contains("\x1b[32m"));
     }
+
+    #[test]
+    fn test_scroll_region_builders() {
+        let seq = SequenceBuilder::new()
+            .scroll_region(2, 23)
+            .alternate_scroll(true)
+            .reset_scroll_region()
+            .build();
+        assert_eq!(seq, "\x1b[2;23r\x1b[?1007h\x1b[r");
+    }
+
+    #[test]
+    fn test_keypad_and_focus_builders() {
+        let seq = SequenceBuilder::new()
+            .keypad_application(true)
+            .focus_reporting(true)
+            .focus_reporting(false)
+            .keypad_application(false)
+            .build();
+        assert_eq!(seq, "\x1b=\x1b[?1004h\x1b[?1004l\x1b>");
+    }
+
+    #[test]
+    fn test_repeat_char() {
+        let rep = TermCaps {
+            repeat: true,
+            ..TermCaps::default()
+        };
+        let build = |caps, c, n| {
+            SequenceBuilder::new()
+                .with_caps(caps)
+                .repeat_char(c, n)
+                .build()
+        };
+        assert_eq!(build(TermCaps::default(), 'x', 6), "xxxxxx");
+        assert_eq!(build(rep, 'x', 6), "x\x1b[5b");
+        assert_eq!(build(rep, 'x', 4), "xxxx");
+        assert_eq!(build(rep, '\u{2500}', 3), "\u{2500}\x1b[2b");
+        assert_eq!(build(rep, '\t', 9), "\t".repeat(9));
+        assert_eq!(build(rep, 'x', 0), "");
+    }
+
+    #[test]
+    fn test_fill_region() {
+        let caps = TermCaps {
+            rectangles: true,
+            ..TermCaps::default()
+        };
+        let fill = |rect, c| {
+            SequenceBuilder::new()
+                .with_caps(caps)
+                .fill_region(rect, c, Style::new())
+                .build()
+        };
+        assert_eq!(fill(Rect::new(1, 2, 10, 3), '.'), "\x1b[46;1;2;3;11$x");
+        assert_eq!(
+            fill(Rect::new(5, 1, 2, 1), '\u{2588}'),
+            "\x1b[5;1H\u{2588}\u{2588}"
+        );
+        assert_eq!(
+            fill(Rect::new(1, 4, 5, 2), '\u{4e16}'),
+            "\x1b[1;4H\u{4e16}\u{4e16}\x1b[2;4H\u{4e16}\u{4e16}"
+        );
+        assert_eq!(fill(Rect::new(5, 1, 0, 4), '.'), "");
+    }
+
+    #[test]
+    fn test_rectangle_operations() {
+        let area = Rect::new(3, 4, 5, 2);
+        let caps = TermCaps {
+            rectangles: true,
+            ..TermCaps::default()
+        };
+        let seq = SequenceBuilder::new()
+            .with_caps(caps)
+            .erase_region(area)
+            .change_attributes(area, &[1, 7])
+            .build();
+        assert_eq!(seq, "\x1b[3;4;4;8$z\x1b[3;4;4;8;1;7$r");
+        assert_eq!(
+            SequenceBuilder::new().erase_region(area).build(),
+            "\x1b[3;4H     \x1b[4;4H     "
+        );
+        assert!(SequenceBuilder::new()
+            .copy_region(Rect::new(1, 1, 0, 0), 2, 2)
+            .build()
+            .is_empty());
+    }
 }
@@ -0,0 +1,221 @@
+//! Styles written as words, such as `bold red on #222`.
+
+use crate::color::Color;
+use crate::modifier::Modifier;
+use crate::style::Style;
+use alloc::string::String;
+use core::fmt;
+use core::str::{FromStr, SplitWhitespace};
+
+/// Error returned by [`Style::parse_spec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SpecError {
+    /// A word is not a modifier, a color, or `on`.
+    Unknown(String),
+    /// `on` was not followed by anything.
+    MissingBackground,
+    /// A second foreground or background color was given.
+    DuplicateColor(String),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(word) => write!(f, "unknown style word {word:?}"),
+            Self::MissingBackground => write!(f, "expected a color after \"on\""),
+            Self::DuplicateColor(word) => write!(f, "color {word:?} given twice"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpecError {}
+
+impl Style {
+    /// Parse a style from a spec such as `bold red on #222 underline`.
+    ///
+    /// Words are separated by whitespace and matched without regard to
+    /// case, `-`, or `_`:
+    ///
+    /// - modifiers by [name](Modifier::name): `bold`, `italic`,
+    ///   `double underline`, ...
+    /// - named colors: `red`, `bright blue`, `default`, and `gray` or
+    ///   `grey` for bright black
+    /// - `#rgb` and `#rrggbb` hex colors, and `0`–`255` for the 256-color
+    ///   palette
+    /// - `on` followed by a color sets the background
+    ///
+    /// The first color sets the foreground. An empty spec is the plain
+    /// style.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SpecError`] for unknown words, a trailing `on`, or a
+    /// second foreground or background.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Modifier, Style};
+    ///
+    /// let style = Style::parse_spec("bold red on #222 underline")?;
+    /// assert_eq!(
+    ///     style,
+    ///     Style::new()
+    ///         .fg(Color::Red)
+    ///         .bg(Color::rgb(0x22, 0x22, 0x22))
+    ///         .modifier(Modifier::Bold)
+    ///         .modifier(Modifier::Underline)
+    /// );
+    ///
+    /// let dim = "bright_cyan on 236".parse::<Style>()?;
+    /// assert_eq!(dim, Style::new().fg(Color::BrightCyan).bg(Color::Ansi256(236)));
+    /// # Ok::<(), glyphs::SpecError>(())
+    /// ```
+    pub fn parse_spec(spec: &str) -> Result<Self, SpecError> {
+        let mut style = Self::new();
+        let (mut fg, mut bg) = (false, false);
+        let mut words = spec.split_whitespace();
+        while let Some((raw, key)) = next_word(&mut words) {
+            if key == "on" {
+                let (raw, key) = next_word(&mut words).ok_or(SpecError::MissingBackground)?;
+                let color = parse_color(&key).ok_or_else(|| SpecError::Unknown(raw.clone()))?;
+                if bg {
+                    return Err(SpecError::DuplicateColor(raw));
+                }
+                bg = true;
+                style = style.bg(color);
+            } else if let Some(modifier) = parse_modifier(&key) {
+                style = style.modifier(modifier);
+            } else if let Some(color) = parse_color(&key) {
+                if fg {
+                    return Err(SpecError::DuplicateColor(raw));
+                }
+                fg = true;
+                style = style.fg(color);
+            } else {
+                return Err(SpecError::Unknown(raw));
+            }
+        }
+        Ok(style)
+    }
+}
+
+impl FromStr for Style {
+    type Err = SpecError;
+
+    /// Same as [`Style::parse_spec`].
+    fn from_str(spec: &str) -> Result<Self, SpecError> {
+        Self::parse_spec(spec)
+    }
+}
+
+/// The next word as written and as a lookup key, joining two-word names
+/// like `bright red`.
+fn next_word(words: &mut SplitWhitespace<'_>) -> Option<(String, String)> {
+    let first = words.next()?;
+    let mut raw = String::from(first);
+    let mut key = normalize(first);
+    if matches!(key.as_str(), "bright" | "double" | "rapid") {
+        if let Some(second) = words.next() {
+            raw.push(' ');
+            raw.push_str(second);
+            key.push_str(&normalize(second));
+        }
+    }
+    Some((raw, key))
+}
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| !matches!(c, '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn parse_modifier(key: &str) -> Option<Modifier> {
+    Modifier::ALL
+        .into_iter()
+        .find(|m| m.name().chars().filter(|&c| c != ' ').eq(key.chars()))
+}
+
+fn parse_color(key: &str) -> Option<Color> {
+    if key.starts_with('#') {
+        return Color::try_from_hex(key).ok();
+    }
+    if key.bytes().all(|b| b.is_ascii_digit()) {
+        return key.parse().ok().map(Color::Ansi256);
+    }
+    let color = match key {
+        "default" => Color::Default,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        "brightblack" | "gray" | "grey" => Color::BrightBlack,
+        "brightred" => Color::BrightRed,
+        "brightgreen" => Color::BrightGreen,
+        "brightyellow" => Color::BrightYellow,
+        "brightblue" => Color::BrightBlue,
+        "brightmagenta" => Color::BrightMagenta,
+        "brightcyan" => Color::BrightCyan,
+        "brightwhite" => Color::BrightWhite,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(Style::parse_spec(""), Ok(Style::new()));
+        assert_eq!(
+            Style::parse_spec("Double-Underline bright  magenta on #ff8800 rapid_blink"),
+            Ok(Style::new()
+                .fg(Color::BrightMagenta)
+                .bg(Color::rgb(0xff, 0x88, 0x00))
+                .modifier(Modifier::DoubleUnderline)
+                .modifier(Modifier::RapidBlink))
+        );
+        assert_eq!(
+            Style::parse_spec("on grey 0"),
+            Ok(Style::new().fg(Color::Ansi256(0)).bg(Color::BrightBlack))
+        );
+    }
+
+    #[test]
+    fn test_parse_spec_errors() {
+        assert_eq!(
+            Style::parse_spec("bold purple"),
+            Err(SpecError::Unknown("purple".into()))
+        );
+        assert_eq!(
+            Style::parse_spec("bright bold"),
+            Err(SpecError::Unknown("bright bold".into()))
+        );
+        assert_eq!(
+            Style::parse_spec("red on"),
+            Err(SpecError::MissingBackground)
+        );
+        assert_eq!(
+            Style::parse_spec("red blue"),
+            Err(SpecError::DuplicateColor("blue".into()))
+        );
+        assert_eq!(
+            Style::parse_spec("on red on 256"),
+            Err(SpecError::Unknown("256".into()))
+        );
+        assert_eq!(
+            Style::parse_spec("#12"),
+            Err(SpecError::Unknown("#12".into()))
+        );
+    }
+}
@@ -1,12 +1,24 @@
 //! Text styling API.
 
-use crate::color::Color;
+use crate::caps::{ColorLevel, TermCaps};
+use crate::color::{self, Color};
+use crate::config;
 use crate::modifier::{Modifier, ModifierSet};
-use crate::{RESET, CSI, SGR_SUFFIX};
-use std::fmt;
+use crate::policy;
+use crate::sequences::CLEAR_LINE_TO_END;
+use crate::theme::{Role, Theme};
+use crate::{CSI, RESET, SGR_SUFFIX};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 
 /// Create a styled string.
 ///
+/// Borrowed text such as a string literal or a slice is kept borrowed, so
+/// building and rendering it copies the text only once, into the output.
+///
 /// # Example
 ///
 /// ```rust
@@ -18,27 +30,139 @@ use std::fmt;
 ///     .to_string();
 /// ```
 #[must_use]
-pub fn style<S: Into<String>>(text: S) -> Styled {
-    Styled::new(text.into())
+pub fn style<'a, S: Into<Cow<'a, str>>>(text: S) -> Styled<'a> {
+    Styled::new(text)
+}
+
+/// Foreground and background shortcuts such as `.red()` and `.on_blue()`.
+macro_rules! styled_color_methods {
+    ($($color:ident => $fg:ident, $bg:ident;)*) => {
+        $(
+            #[doc = concat!("Set the foreground color to [`Color::", stringify!($color), "`].")]
+            #[must_use]
+            pub fn $fg(self) -> Self {
+                self.fg(Color::$color)
+            }
+
+            #[doc = concat!("Set the background color to [`Color::", stringify!($color), "`].")]
+            #[must_use]
+            pub fn $bg(self) -> Self {
+                self.bg(Color::$color)
+            }
+        )*
+    };
+}
+
+/// What [`Styled`] appends after its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResetMode {
+    /// A full reset (`\x1b[0m`).
+    #[default]
+    Full,
+    /// Only the off-codes for the attributes the style set (`22` for bold,
+    /// `39` for a foreground color, ...), so styles from surrounding text
+    /// are kept.
+    Targeted,
+    /// Nothing; the style stays active after the text.
+    None,
 }
 
 /// A styled string with colors and modifiers.
-#[derive(Debug, Clone)]
-pub struct Styled {
-    text: String,
+///
+/// The text is either borrowed or owned; see [`Styled::into_owned`] for
+/// keeping a borrowed one past the lifetime of its text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Styled<'a> {
+    text: Cow<'a, str>,
     style: Style,
+    role: Option<Role>,
+    level: Option<ColorLevel>,
+    per_line: bool,
+    fill_line: bool,
+    reset: ResetMode,
 }
 
-impl Styled {
-    /// Create a new styled string.
+impl<'a> Styled<'a> {
+    /// Create a new styled string from borrowed or owned text.
     #[must_use]
-    pub fn new(text: String) -> Self {
+    pub fn new<S: Into<Cow<'a, str>>>(text: S) -> Self {
         Self {
-            text,
+            text: text.into(),
             style: Style::default(),
+            role: None,
+            level: None,
+            per_line: false,
+            fill_line: false,
+            reset: ResetMode::Full,
         }
     }
 
+    /// Extend the background color to the right edge of the terminal.
+    ///
+    /// Emits an erase-to-end-of-line while the background is still active,
+    /// which is how status bars and diff viewers paint full-width rows. Has
+    /// no effect without a background color.
+    #[must_use]
+    pub const fn fill_line(mut self) -> Self {
+        self.fill_line = true;
+        self
+    }
+
+    /// Reset the style before each line break and re-apply it after.
+    ///
+    /// Some terminals and pagers (such as `less -R`) drop attributes at line
+    /// boundaries, and background colors otherwise bleed across wrapped lines.
+    #[must_use]
+    pub const fn per_line(mut self, enabled: bool) -> Self {
+        self.per_line = enabled;
+        self
+    }
+
+    /// Choose what is appended after the text (default [`ResetMode::Full`]).
+    ///
+    /// Libraries embedding output in a larger styled document can use
+    /// [`ResetMode::Targeted`] or [`ResetMode::None`] so the outer style
+    /// isn't cleared.
+    #[must_use]
+    pub const fn reset_mode(mut self, mode: ResetMode) -> Self {
+        self.reset = mode;
+        self
+    }
+
+    /// Render for a specific color level instead of the global
+    /// [`ColorChoice`](crate::ColorChoice).
+    ///
+    /// Colors are downgraded to fit the level, and [`ColorLevel::None`]
+    /// renders the plain text.
+    #[must_use]
+    pub const fn color_level(mut self, level: ColorLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Style the text for `role` in [`Theme::current`].
+    ///
+    /// The theme is looked up when rendering, not now, so a value built
+    /// before [`set_default_theme`](crate::set_default_theme) (or before
+    /// the background was detected) still follows the theme in effect.
+    /// Colors and modifiers set on this value are layered on top.
+    ///
+    /// ```rust
+    /// use glyphs::{style, ColorLevel, Role, Theme};
+    ///
+    /// let message = style("disk almost full").role(Role::Warning);
+    /// let expected = style("disk almost full").fg(Theme::current().warning.foreground.unwrap());
+    /// assert_eq!(
+    ///     message.color_level(ColorLevel::Ansi16).render(),
+    ///     expected.color_level(ColorLevel::Ansi16).render()
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
     /// Set the foreground color.
     #[must_use]
     pub fn fg(mut self, color: Color) -> Self {
@@ -53,6 +177,65 @@ impl Styled {
         self
     }
 
+    /// Set the foreground color if `color` is `Some`, leaving it
+    /// unchanged otherwise.
+    ///
+    /// ```rust
+    /// use glyphs::{style, Color};
+    ///
+    /// let configured: Option<Color> = None;
+    /// let styled = style("name").fg_opt(configured);
+    /// assert_eq!(styled.get_style().foreground, None);
+    /// ```
+    #[must_use]
+    pub fn fg_opt(self, color: Option<Color>) -> Self {
+        match color {
+            Some(color) => self.fg(color),
+            None => self,
+        }
+    }
+
+    /// Set the background color if `color` is `Some`, leaving it
+    /// unchanged otherwise.
+    #[must_use]
+    pub fn bg_opt(self, color: Option<Color>) -> Self {
+        match color {
+            Some(color) => self.bg(color),
+            None => self,
+        }
+    }
+
+    /// Add every modifier in `modifiers`.
+    ///
+    /// ```rust
+    /// use glyphs::{style, Modifier};
+    ///
+    /// let from_config = vec![Modifier::Bold, Modifier::Italic];
+    /// let styled = style("title").modifiers(from_config);
+    /// assert!(styled.get_style().modifiers.contains(Modifier::Italic));
+    /// ```
+    #[must_use]
+    pub fn modifiers<I: IntoIterator<Item = Modifier>>(mut self, modifiers: I) -> Self {
+        for modifier in modifiers {
+            self.style.modifiers = self.style.modifiers.with(modifier);
+        }
+        self
+    }
+
+    /// Set an RGB foreground color.
+    #[must_use]
+    pub fn rgb(self, r: u8, g: u8, b: u8) -> Self {
+        self.fg(Color::rgb(r, g, b))
+    }
+
+    /// Set an RGB background color.
+    #[must_use]
+    pub fn on_rgb(self, r: u8, g: u8, b: u8) -> Self {
+        self.bg(Color::rgb(r, g, b))
+    }
+
+    color::named_colors!(styled_color_methods);
+
     /// Make the text bold.
     #[must_use]
     pub fn bold(mut self) -> Self {
@@ -116,44 +299,246 @@ impl Styled {
         self
     }
 
+    /// Render the text as superscript (replaces subscript).
+    #[must_use]
+    pub fn superscript(mut self) -> Self {
+        self.style.modifiers = self
+            .style
+            .modifiers
+            .without(Modifier::Subscript)
+            .with(Modifier::Superscript);
+        self
+    }
+
+    /// Render the text as subscript (replaces superscript).
+    #[must_use]
+    pub fn subscript(mut self) -> Self {
+        self.style.modifiers = self
+            .style
+            .modifiers
+            .without(Modifier::Superscript)
+            .with(Modifier::Subscript);
+        self
+    }
+
     /// Get the underlying text.
     #[must_use]
     pub fn text(&self) -> &str {
         &self.text
     }
 
-    /// Get the style.
+    /// Copy borrowed text so the styled string no longer borrows anything.
+    #[must_use]
+    pub fn into_owned(self) -> Styled<'static> {
+        Styled {
+            text: Cow::Owned(self.text.into_owned()),
+            style: self.style,
+            role: self.role,
+            level: self.level,
+            per_line: self.per_line,
+            fill_line: self.fill_line,
+            reset: self.reset,
+        }
+    }
+
+    /// Get the style set on this value, without its [`role`](Styled::role).
     #[must_use]
     pub const fn get_style(&self) -> &Style {
         &self.style
     }
 
+    /// Get the role set with [`Styled::role`].
+    #[must_use]
+    pub const fn get_role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// The style to render with: the role's style in [`Theme::current`]
+    /// with this value's own style on top.
+    fn resolved_style(&self) -> Style {
+        match self.role {
+            Some(role) => Theme::current().role(role).patch(self.style),
+            None => self.style,
+        }
+    }
+
+    /// Render and prepend the rendered `prefix` to every line.
+    ///
+    /// Useful for gutters such as `│ ` in front of multi-line output; see
+    /// [`prefix_lines`](crate::prefix_lines).
+    #[must_use]
+    pub fn prefix_lines(&self, prefix: &Styled<'_>) -> String {
+        crate::text::prefix_lines(&self.render(), &prefix.render())
+    }
+
     /// Render to a string with ANSI codes.
+    ///
+    /// Returns the plain text when colors are disabled (see [`crate::config`]).
+    /// The accessibility [`Policy`](crate::Policy) is applied before rendering.
     #[must_use]
     pub fn render(&self) -> String {
-        let codes = self.style.codes();
-        if codes.is_empty() {
-            return self.text.clone();
+        let resolved = self.resolved_style();
+        let style = match self.level {
+            Some(level) => policy::policy().enforce(&resolved).downgrade(level),
+            None if config::colors_enabled() => policy::policy().enforce(&resolved),
+            None => return self.text.clone().into_owned(),
+        };
+        if style.is_plain() {
+            return self.text.clone().into_owned();
         }
 
-        let codes_str = codes
-            .iter()
-            .map(std::string::ToString::to_string)
-            .collect::<Vec<_>>()
-            .join(";");
+        let mut prefix = String::from(CSI);
+        let _ = style.write_codes(&mut prefix);
+        prefix.push_str(SGR_SUFFIX);
+        let fill = self.fill_line && style.background.is_some();
+        let reset = match self.reset {
+            ResetMode::Full => RESET.to_string(),
+            ResetMode::Targeted => style.off_sequence(),
+            ResetMode::None => String::new(),
+        };
+        let suffix = if fill {
+            format!("{CLEAR_LINE_TO_END}{reset}")
+        } else {
+            reset
+        };
+
+        if !self.per_line || !self.text.contains('\n') {
+            return format!("{prefix}{}{suffix}", self.text);
+        }
 
-        format!("{CSI}{codes_str}{SGR_SUFFIX}{}{RESET}", self.text)
+        let mut out = String::with_capacity(self.text.len() * 2);
+        for (i, line) in self.text.split('\n').enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let (line, cr) = line.strip_suffix('\r').map_or((line, ""), |l| (l, "\r"));
+            if fill || !line.is_empty() {
+                out.push_str(&prefix);
+                out.push_str(line);
+                out.push_str(&suffix);
+            }
+            out.push_str(cr);
+        }
+        out
     }
 }
 
-impl fmt::Display for Styled {
+impl fmt::Display for Styled<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.render())
     }
 }
 
+/// An SGR sequence built at compile time by [`Style::prefix`].
+#[derive(Clone, Copy)]
+pub struct SgrPrefix {
+    buf: [u8; Style::PREFIX_LEN],
+    len: usize,
+}
+
+impl SgrPrefix {
+    const EMPTY: Self = Self {
+        buf: [0; Style::PREFIX_LEN],
+        len: 0,
+    };
+
+    /// The escape sequence.
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => "",
+        }
+    }
+
+    /// Length of the sequence in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the sequence is empty (the style was plain).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    const fn push(mut self, byte: u8) -> Self {
+        self.buf[self.len] = byte;
+        self.len += 1;
+        self
+    }
+
+    const fn sep(self, needed: bool) -> Self {
+        if needed {
+            self.push(b';')
+        } else {
+            self
+        }
+    }
+
+    const fn push_num(mut self, n: u8) -> Self {
+        if n >= 100 {
+            self = self.push(b'0' + n / 100);
+        }
+        if n >= 10 {
+            self = self.push(b'0' + n / 10 % 10);
+        }
+        self.push(b'0' + n % 10)
+    }
+
+    const fn push_color(self, color: Color, base: u8) -> Self {
+        match color {
+            Color::Default => self.push_num(base + 9),
+            Color::Ansi256(code) => self
+                .push_num(base + 8)
+                .push(b';')
+                .push(b'5')
+                .push(b';')
+                .push_num(code),
+            Color::Rgb { r, g, b } => self
+                .push_num(base + 8)
+                .push(b';')
+                .push(b'2')
+                .push(b';')
+                .push_num(r)
+                .push(b';')
+                .push_num(g)
+                .push(b';')
+                .push_num(b),
+            basic => match basic.basic_index() {
+                Some(index) if index < 8 => self.push_num(base + index),
+                Some(index) => self.push_num(base + 52 + index),
+                None => self,
+            },
+        }
+    }
+}
+
+impl fmt::Debug for SgrPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SgrPrefix").field(&self.as_str()).finish()
+    }
+}
+
+impl fmt::Display for SgrPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for SgrPrefix {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 /// A style definition (without text).
-#[derive(Debug, Clone, Default)]
+///
+/// Styles compare equal when they set the same colors and modifiers,
+/// regardless of the order they were added in. A `Style` is a small `Copy`
+/// value, so it can be stored per cell in screen buffers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Style {
     /// Foreground color.
     pub foreground: Option<Color>,
@@ -201,7 +586,7 @@ impl Style {
         let mut codes = Vec::new();
 
         // Add modifier codes
-        for modifier in self.modifiers.modifiers() {
+        for modifier in self.modifiers.iter() {
             codes.push(modifier.on_code().to_string());
         }
 
@@ -218,19 +603,251 @@ impl Style {
         codes
     }
 
+    /// Write the `;`-separated SGR codes for this style without allocating.
+    ///
+    /// Writes nothing for a plain style.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails.
+    pub fn write_codes<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let mut sep = "";
+        for modifier in self.modifiers.iter() {
+            write!(out, "{sep}{}", modifier.on_code())?;
+            sep = ";";
+        }
+        if let Some(fg) = &self.foreground {
+            out.write_str(sep)?;
+            fg.write_code(out, 30)?;
+            sep = ";";
+        }
+        if let Some(bg) = &self.background {
+            out.write_str(sep)?;
+            bg.write_code(out, 40)?;
+        }
+        Ok(())
+    }
+
+    /// The SGR sequence turning off only what this style sets, or an empty
+    /// string for a plain style.
+    pub(crate) fn off_sequence(&self) -> String {
+        let mut codes: Vec<u8> = Vec::new();
+        for modifier in self.modifiers.iter() {
+            if !codes.contains(&modifier.off_code()) {
+                codes.push(modifier.off_code());
+            }
+        }
+        if self.foreground.is_some() {
+            codes.push(39);
+        }
+        if self.background.is_some() {
+            codes.push(49);
+        }
+        if codes.is_empty() {
+            return String::new();
+        }
+        let codes: Vec<String> = codes.iter().map(ToString::to_string).collect();
+        format!("{CSI}{}{SGR_SUFFIX}", codes.join(";"))
+    }
+
+    /// Check if this style has no colors or modifiers.
+    #[must_use]
+    pub const fn is_plain(&self) -> bool {
+        self.foreground.is_none() && self.background.is_none() && self.modifiers.is_empty()
+    }
+
+    /// Layer `other` on top of this style: its colors win where set, and
+    /// its modifiers are added.
+    #[must_use]
+    pub const fn patch(mut self, other: Self) -> Self {
+        if other.foreground.is_some() {
+            self.foreground = other.foreground;
+        }
+        if other.background.is_some() {
+            self.background = other.background;
+        }
+        self.modifiers = self.modifiers.union(other.modifiers);
+        self
+    }
+
+    /// Upper bound on the length of [`Style::prefix`] output.
+    pub const PREFIX_LEN: usize = 80;
+
+    /// Build the SGR sequence that turns this style on, in a `const` context.
+    ///
+    /// The codes match [`Style::apply`], but neither the color
+    /// [`config`](crate::config) nor the accessibility policy is consulted.
+    /// A plain style yields an empty prefix.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Modifier, SgrPrefix, Style, RESET};
+    ///
+    /// const ERROR: SgrPrefix = Style::new().fg(Color::Red).modifier(Modifier::Bold).prefix();
+    ///
+    /// assert_eq!(ERROR.as_str(), "\x1b[1;31m");
+    /// println!("{ERROR}error{RESET}: something broke");
+    /// ```
+    #[must_use]
+    pub const fn prefix(&self) -> SgrPrefix {
+        if self.is_plain() {
+            return SgrPrefix::EMPTY;
+        }
+
+        let mut out = SgrPrefix::EMPTY.push(0x1b).push(b'[');
+        let mut sep = false;
+        let mut i = 0;
+        while i < Modifier::ALL.len() {
+            let modifier = Modifier::ALL[i];
+            if self.modifiers.contains(modifier) {
+                out = out.sep(sep).push_num(modifier.on_code());
+                sep = true;
+            }
+            i += 1;
+        }
+        if let Some(fg) = self.foreground {
+            out = out.sep(sep).push_color(fg, 30);
+            sep = true;
+        }
+        if let Some(bg) = self.background {
+            out = out.sep(sep).push_color(bg, 40);
+        }
+        out.push(b'm')
+    }
+
     /// Apply this style to a string.
+    ///
+    /// Returns the plain text when colors are disabled (see [`crate::config`]).
+    /// The accessibility [`Policy`](crate::Policy) is applied before rendering.
     #[must_use]
     pub fn apply(&self, text: &str) -> String {
-        let codes = self.codes();
-        if codes.is_empty() {
+        if !config::colors_enabled() {
             return text.to_string();
         }
+        let style = policy::policy().enforce(self);
+        if style.is_plain() {
+            return text.to_string();
+        }
+
+        let mut out = String::with_capacity(text.len() + 16);
+        out.push_str(CSI);
+        let _ = style.write_codes(&mut out);
+        out.push_str(SGR_SUFFIX);
+        out.push_str(text);
+        out.push_str(RESET);
+        out
+    }
+
+    /// Build the style a terminal ends up with after an SGR sequence with
+    /// the given parameters, such as `[1, 38, 5, 208]`.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Modifier, Style};
+    ///
+    /// let style = Style::from_sgr_params(&[1, 38, 5, 208]);
+    /// assert_eq!(style, Style::new().fg(Color::Ansi256(208)).modifier(Modifier::Bold));
+    /// ```
+    #[must_use]
+    pub fn from_sgr_params(params: &[u32]) -> Self {
+        let mut style = Self::new();
+        style.apply_sgr(params);
+        style
+    }
+
+    /// Update this style as a terminal would when it receives an SGR
+    /// sequence with the given parameters.
+    pub(crate) fn apply_sgr(&mut self, params: &[u32]) {
+        if params.is_empty() {
+            *self = Self::new();
+            return;
+        }
+
+        let mut i = 0;
+        while i < params.len() {
+            let code = params[i];
+            i += 1;
+            match code {
+                0 => *self = Self::new(),
+                #[allow(clippy::cast_possible_truncation)]
+                30..=37 => self.foreground = Some(color::basic((code - 30) as u8)),
+                #[allow(clippy::cast_possible_truncation)]
+                90..=97 => self.foreground = Some(color::basic((code - 82) as u8)),
+                39 => self.foreground = None,
+                #[allow(clippy::cast_possible_truncation)]
+                40..=47 => self.background = Some(color::basic((code - 40) as u8)),
+                #[allow(clippy::cast_possible_truncation)]
+                100..=107 => self.background = Some(color::basic((code - 92) as u8)),
+                49 => self.background = None,
+                38 | 48 | 58 => {
+                    let (color, used) = extended_color(&params[i..]);
+                    i += used;
+                    // Underline colors (58) aren't tracked, but their
+                    // arguments still need skipping.
+                    match code {
+                        38 => self.foreground = color.or(self.foreground),
+                        48 => self.background = color.or(self.background),
+                        _ => {}
+                    }
+                }
+                _ => self.modifiers = self.modifiers.apply_code(code),
+            }
+        }
+    }
+
+    /// Adapt this style to what a terminal can display.
+    ///
+    /// Unsupported modifiers are replaced with the closest supported one
+    /// (italic and double underline become underline, rapid blink becomes
+    /// blink) or dropped, and colors are downgraded to `caps.color`.
+    ///
+    /// ```rust
+    /// use glyphs::{ColorLevel, Modifier, Style, TermCaps};
+    ///
+    /// let caps = TermCaps {
+    ///     color: ColorLevel::Ansi16,
+    ///     unsupported: Style::new().modifier(Modifier::Italic).modifiers,
+    ///     ..TermCaps::default()
+    /// };
+    /// let style = Style::new().modifier(Modifier::Italic).adapt(&caps);
+    /// assert_eq!(style, Style::new().modifier(Modifier::Underline));
+    /// ```
+    #[must_use]
+    pub fn adapt(&self, caps: &TermCaps) -> Self {
+        let mut modifiers = ModifierSet::empty();
+        for modifier in self.modifiers.iter() {
+            let substitute = match modifier {
+                _ if caps.supports(modifier) => Some(modifier),
+                Modifier::Italic | Modifier::DoubleUnderline => Some(Modifier::Underline),
+                Modifier::RapidBlink => Some(Modifier::Blink),
+                _ => None,
+            };
+            if let Some(substitute) = substitute.filter(|&m| caps.supports(m)) {
+                modifiers = modifiers.with(substitute);
+            }
+        }
+        Self { modifiers, ..*self }.downgrade(caps.color)
+    }
 
-        let codes_str = codes.join(";");
-        format!("{CSI}{codes_str}{SGR_SUFFIX}{text}{RESET}")
+    /// Downgrade this style so it can be displayed at the given color level.
+    ///
+    /// [`ColorLevel::None`] yields an empty style.
+    #[must_use]
+    pub fn downgrade(&self, level: ColorLevel) -> Self {
+        if level == ColorLevel::None {
+            return Self::new();
+        }
+        Self {
+            foreground: self.foreground.and_then(|c| c.downgrade(level)),
+            background: self.background.and_then(|c| c.downgrade(level)),
+            modifiers: self.modifiers,
+        }
     }
 
     /// Get a human-readable description of this style.
+    ///
+    /// The order is fixed so descriptions can be compared in tests:
+    /// modifiers in the order their codes are emitted (bold, dim, italic,
+    /// ...), then the foreground, then the background, such as `bold, underline, fg: red, bg: black`.
+    /// A plain style is `no style`.
     #[must_use]
     pub fn describe(&self) -> String {
         let mut parts = Vec::new();
@@ -253,6 +870,86 @@ impl Style {
             parts.join(", ")
         }
     }
+
+    /// A short, single-word description for snapshot tests and golden
+    /// files, such as `bold+red/on-black`.
+    ///
+    /// Modifiers come first in the order their codes are emitted, then the
+    /// foreground, joined by `+`; the background follows after `/on-`.
+    /// Multi-word names are hyphenated (`double-underline`,
+    /// `bright-red`), 256-color indices are plain numbers, and RGB colors
+    /// are `#rrggbb`. A plain style is `plain`.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Modifier, Style};
+    ///
+    /// let style = Style::new().fg(Color::Red).bg(Color::Black).modifier(Modifier::Bold);
+    /// assert_eq!(style.describe_compact(), "bold+red/on-black");
+    /// assert_eq!(Style::new().bg(Color::rgb(0, 128, 255)).describe_compact(), "on-#0080ff");
+    /// ```
+    #[must_use]
+    pub fn describe_compact(&self) -> String {
+        let mut out = String::new();
+        for modifier in self.modifiers.iter() {
+            if !out.is_empty() {
+                out.push('+');
+            }
+            out.push_str(&modifier.name().replace(' ', "-"));
+        }
+        if let Some(fg) = self.foreground {
+            if !out.is_empty() {
+                out.push('+');
+            }
+            out.push_str(&compact_color(fg));
+        }
+        if let Some(bg) = self.background {
+            if !out.is_empty() {
+                out.push('/');
+            }
+            out.push_str("on-");
+            out.push_str(&compact_color(bg));
+        }
+        if out.is_empty() {
+            out.push_str("plain");
+        }
+        out
+    }
+}
+
+/// A color name for [`Style::describe_compact`].
+fn compact_color(color: Color) -> String {
+    match color {
+        Color::Ansi256(code) => code.to_string(),
+        Color::Rgb { r, g, b } => format!("#{r:02x}{g:02x}{b:02x}"),
+        named => named.name().replace(' ', "-"),
+    }
+}
+
+/// Parse an SGR parameter string such as `01;38;5;208` into the style it
+/// selects. Empty parameters count as `0`.
+pub(crate) fn parse_sgr_value(value: &str) -> Option<Style> {
+    let params = value
+        .split(';')
+        .map(|p| if p.is_empty() { Ok(0) } else { p.parse() })
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+    Some(Style::from_sgr_params(&params))
+}
+
+/// Parse the tail of a `38;…`/`48;…` parameter list.
+///
+/// Returns the color (if well-formed) and how many parameters were consumed.
+pub(crate) fn extended_color(rest: &[u32]) -> (Option<Color>, usize) {
+    let channel = |v: u32| u8::try_from(v).ok();
+    match rest {
+        [5, n, ..] => (channel(*n).map(Color::Ansi256), 2),
+        [2, r, g, b, ..] => match (channel(*r), channel(*g), channel(*b)) {
+            (Some(r), Some(g), Some(b)) => (Some(Color::rgb(r, g, b)), 4),
+            _ => (None, 4),
+        },
+        [5] | [2, ..] => (None, rest.len()),
+        _ => (None, 0),
+    }
 }
 
 #[cfg(test)]
@@ -261,7 +958,10 @@ mod tests {
 
     #[test]
     fn test_style_rendering() {
-        let s = style("test").fg(Color::Red).to_string();
+        let s = style("test")
+            .fg(Color::Red)
+            .color_level(ColorLevel::Ansi16)
+            .to_string();
         assert!(s.starts_with("\x1b["));
         assert!(s.contains("31")); // Red foreground
         assert!(s.contains("test"));
@@ -270,19 +970,281 @@ mod tests {
 
     #[test]
     fn test_style_with_modifiers() {
-        let s = style("test").bold().underline().to_string();
-        assert!(s.contains("1")); // Bold
-        assert!(s.contains("4")); // Underline
+        let s = style("test")
+            .bold()
+            .underline()
+            .color_level(ColorLevel::Ansi16)
+            .to_string();
+        assert!(s.contains('1')); // Bold
+        assert!(s.contains('4')); // Underline
     }
 
     #[test]
-    fn test_style_description() {
-        let style = Style::new()
+    fn test_styled_borrows_text() {
+        let line = String::from("status: ok");
+        let s = style(&line[8..]).fg(Color::Green);
+        assert!(matches!(s.text, Cow::Borrowed("ok")));
+        assert!(matches!(style(String::from("ok")).text, Cow::Owned(_)));
+
+        let owned: Styled<'static> = s.into_owned();
+        drop(line);
+        assert_eq!(owned.text(), "ok");
+        assert_eq!(owned.get_style(), &Style::new().fg(Color::Green));
+    }
+
+    #[test]
+    fn test_superscript_subscript() {
+        let s = style("2").superscript().color_level(ColorLevel::Ansi16);
+        assert_eq!(s.render(), "\x1b[73m2\x1b[0m");
+        let s = style("2").superscript().subscript();
+        assert!(!s.get_style().modifiers.contains(Modifier::Superscript));
+        assert!(s.get_style().modifiers.contains(Modifier::Subscript));
+    }
+
+    #[test]
+    fn test_style_per_line() {
+        let s = style("a\n\nb")
             .fg(Color::Red)
-            .modifier(Modifier::Bold);
-        
+            .per_line(true)
+            .color_level(ColorLevel::Ansi16);
+        assert_eq!(s.render(), "\x1b[31ma\x1b[0m\n\n\x1b[31mb\x1b[0m");
+
+        let s = style("a\nb").fg(Color::Red).color_level(ColorLevel::Ansi16);
+        assert_eq!(s.render(), "\x1b[31ma\nb\x1b[0m");
+    }
+
+    #[test]
+    fn test_style_fill_line() {
+        let s = style("bar")
+            .bg(Color::Blue)
+            .fill_line()
+            .color_level(ColorLevel::Ansi16);
+        assert_eq!(s.render(), "\x1b[44mbar\x1b[0K\x1b[0m");
+
+        let s = style("bar")
+            .fg(Color::Blue)
+            .fill_line()
+            .color_level(ColorLevel::Ansi16);
+        assert_eq!(s.render(), "\x1b[34mbar\x1b[0m");
+    }
+
+    #[test]
+    fn test_style_reset_mode() {
+        let s = style("x")
+            .bold()
+            .dim()
+            .fg(Color::Red)
+            .color_level(ColorLevel::Ansi16);
+        assert_eq!(s.clone().render(), "\x1b[1;2;31mx\x1b[0m");
+        assert_eq!(
+            s.clone().reset_mode(ResetMode::Targeted).render(),
+            "\x1b[1;2;31mx\x1b[22;39m"
+        );
+        assert_eq!(s.reset_mode(ResetMode::None).render(), "\x1b[1;2;31mx");
+
+        let s = style("bar")
+            .bg(Color::Blue)
+            .fill_line()
+            .reset_mode(ResetMode::None);
+        assert_eq!(
+            s.color_level(ColorLevel::Ansi16).render(),
+            "\x1b[44mbar\x1b[0K"
+        );
+    }
+
+    #[test]
+    fn test_styled_role() {
+        let s = style("x").role(Role::Error).color_level(ColorLevel::Ansi16);
+        assert_eq!(s.get_role(), Some(Role::Error));
+        assert!(s.get_style().is_plain());
+        assert_eq!(s.render(), "\x1b[1;91mx\x1b[0m");
+        let s = s.fg(Color::Blue).italic();
+        assert_eq!(s.render(), "\x1b[1;3;34mx\x1b[0m");
+        assert_eq!(s.into_owned().get_role(), Some(Role::Error));
+    }
+
+    #[test]
+    fn test_style_adapt() {
+        let unsupported = ModifierSet::empty()
+            .with(Modifier::Italic)
+            .with(Modifier::Strikethrough)
+            .with(Modifier::RapidBlink);
+        let caps = TermCaps {
+            color: ColorLevel::Ansi256,
+            unsupported,
+            ..TermCaps::default()
+        };
+        let style = Style::new()
+            .modifier(Modifier::Bold)
+            .modifier(Modifier::Italic)
+            .modifier(Modifier::Strikethrough)
+            .modifier(Modifier::RapidBlink)
+            .fg(Color::rgb(255, 0, 0));
+        assert_eq!(
+            style.adapt(&caps),
+            Style::new()
+                .modifier(Modifier::Bold)
+                .modifier(Modifier::Underline)
+                .modifier(Modifier::Blink)
+                .fg(Color::Ansi256(196))
+        );
+
+        let caps = TermCaps {
+            unsupported: unsupported.with(Modifier::Underline),
+            ..caps
+        };
+        let italic = Style::new().modifier(Modifier::Italic);
+        assert!(italic.adapt(&caps).is_plain());
+        assert_eq!(italic.adapt(&TermCaps::default()), italic);
+    }
+
+    #[test]
+    fn test_write_codes() {
+        let style = Style::new()
+            .modifier(Modifier::Bold)
+            .fg(Color::BrightRed)
+            .bg(Color::rgb(1, 2, 3));
+        let mut out = String::new();
+        style.write_codes(&mut out).unwrap();
+        assert_eq!(out, "1;91;48;2;1;2;3");
+        assert_eq!(out, style.codes().join(";"));
+        assert!(Style::new().is_plain());
+    }
+
+    #[test]
+    fn test_const_prefix() {
+        const WARN: SgrPrefix = Style::new()
+            .fg(Color::BrightYellow)
+            .bg(Color::Ansi256(236))
+            .modifier(Modifier::Underline)
+            .prefix();
+        assert_eq!(WARN.as_str(), "\x1b[4;93;48;5;236m");
+        assert!(Style::new().prefix().is_empty());
+
+        let mut widest = Style::new()
+            .fg(Color::rgb(255, 255, 255))
+            .bg(Color::rgb(255, 255, 255));
+        for modifier in Modifier::ALL {
+            widest = widest.modifier(modifier);
+        }
+        let mut codes = String::new();
+        widest.write_codes(&mut codes).unwrap();
+        assert_eq!(
+            widest.prefix().as_str(),
+            format!("{CSI}{codes}{SGR_SUFFIX}")
+        );
+        assert!(widest.prefix().len() <= Style::PREFIX_LEN);
+    }
+
+    #[test]
+    fn test_style_equality() {
+        let a = Style::new().modifier(Modifier::Bold).fg(Color::Red);
+        let b = Style::new().fg(Color::Red).modifier(Modifier::Bold);
+        assert_eq!(a, b);
+        assert_ne!(a, Style::new().fg(Color::Red));
+        assert_eq!(style("x").bold().red(), style("x").red().bold());
+
+        // Copy, and small enough to store per cell.
+        let c = a;
+        assert_eq!(a, c);
+        assert!(core::mem::size_of::<Style>() <= 12);
+    }
+
+    #[test]
+    fn test_apply_sgr() {
+        let mut style = Style::new();
+        style.apply_sgr(&[1, 31, 48, 2, 1, 2, 3]);
+        assert!(style.modifiers.contains(Modifier::Bold));
+        assert_eq!(style.foreground, Some(Color::Red));
+        assert_eq!(style.background, Some(Color::rgb(1, 2, 3)));
+
+        style.apply_sgr(&[22, 39]);
+        assert!(style.modifiers.modifiers().is_empty());
+        assert_eq!(style.foreground, None);
+
+        style.apply_sgr(&[0]);
+        assert_eq!(style.background, None);
+    }
+
+    #[test]
+    fn test_style_description() {
+        let style = Style::new().fg(Color::Red).modifier(Modifier::Bold);
+
         let desc = style.describe();
         assert!(desc.contains("bold"));
         assert!(desc.contains("red"));
     }
+
+    #[test]
+    fn test_describe_order_is_stable() {
+        let a = Style::new()
+            .bg(Color::Black)
+            .modifier(Modifier::Underline)
+            .fg(Color::BrightRed)
+            .modifier(Modifier::Bold);
+        let b = Style::new()
+            .modifier(Modifier::Bold)
+            .fg(Color::BrightRed)
+            .modifier(Modifier::Underline)
+            .bg(Color::Black);
+        assert_eq!(a.describe(), "bold, underline, fg: bright red, bg: black");
+        assert_eq!(a.describe(), b.describe());
+        assert_eq!(a.describe_compact(), "bold+underline+bright-red/on-black");
+        assert_eq!(b.describe_compact(), a.describe_compact());
+    }
+
+    #[test]
+    fn test_optional_builders() {
+        let base = style("x").fg(Color::Red);
+        assert_eq!(base.clone().fg_opt(None), base);
+        assert_eq!(
+            base.clone().fg_opt(Some(Color::Blue)),
+            style("x").fg(Color::Blue)
+        );
+        assert_eq!(
+            base.clone().bg_opt(Some(Color::Black)),
+            base.clone().bg(Color::Black)
+        );
+        assert_eq!(
+            base.clone().modifiers([Modifier::Bold, Modifier::Dim]),
+            base.clone().bold().dim()
+        );
+        assert_eq!(base.clone().modifiers(None), base);
+    }
+
+    #[test]
+    fn test_describe_compact() {
+        assert_eq!(Style::new().describe_compact(), "plain");
+        let double = Style::new().modifier(Modifier::DoubleUnderline);
+        assert_eq!(double.describe_compact(), "double-underline");
+        assert_eq!(
+            Style::new().fg(Color::Ansi256(208)).describe_compact(),
+            "208"
+        );
+        assert_eq!(
+            Style::new()
+                .fg(Color::rgb(249, 115, 22))
+                .bg(Color::Default)
+                .describe_compact(),
+            "#f97316/on-default"
+        );
+    }
+
+    #[test]
+    fn test_parse_sgr_value() {
+        assert_eq!(parse_sgr_value(""), Some(Style::new()));
+        assert_eq!(
+            parse_sgr_value("38;5;208;;4"),
+            Some(Style::new().modifier(Modifier::Underline))
+        );
+        assert_eq!(
+            parse_sgr_value("01;38;5;208"),
+            Some(
+                Style::new()
+                    .fg(Color::Ansi256(208))
+                    .modifier(Modifier::Bold)
+            )
+        );
+        assert_eq!(parse_sgr_value("1;x"), None);
+    }
 }
@@ -0,0 +1,177 @@
+//! Extension trait for styling anything that implements `Display`.
+
+use crate::color::{self, Color};
+use crate::style::Styled;
+use alloc::string::ToString;
+use core::fmt;
+
+/// Provided color shortcuts forwarding to the [`Styled`] methods.
+macro_rules! stylize_color_methods {
+    ($($color:ident => $fg:ident, $bg:ident;)*) => {
+        $(
+            #[doc = concat!("Set the foreground color to [`Color::", stringify!($color), "`].")]
+            #[must_use]
+            fn $fg(self) -> Styled<'static> {
+                self.stylize().$fg()
+            }
+
+            #[doc = concat!("Set the background color to [`Color::", stringify!($color), "`].")]
+            #[must_use]
+            fn $bg(self) -> Styled<'static> {
+                self.stylize().$bg()
+            }
+        )*
+    };
+}
+
+/// Style values directly, like `"done".green().bold()`.
+///
+/// Implemented for every [`Display`](fmt::Display) type, including `&str`
+/// and `String`. Each method converts the value into a [`Styled`] and
+/// continues with the regular builder.
+///
+/// ```rust
+/// use glyphs::Stylize;
+///
+/// println!("{} {}", "error:".red().bold(), 42.yellow());
+/// ```
+pub trait Stylize: Sized {
+    /// Convert into an unstyled [`Styled`].
+    #[must_use]
+    fn stylize(self) -> Styled<'static>;
+
+    /// Set the foreground color.
+    #[must_use]
+    fn fg(self, color: Color) -> Styled<'static> {
+        self.stylize().fg(color)
+    }
+
+    /// Set the background color.
+    #[must_use]
+    fn bg(self, color: Color) -> Styled<'static> {
+        self.stylize().bg(color)
+    }
+
+    /// Set an RGB foreground color.
+    #[must_use]
+    fn rgb(self, r: u8, g: u8, b: u8) -> Styled<'static> {
+        self.stylize().rgb(r, g, b)
+    }
+
+    /// Set an RGB background color.
+    #[must_use]
+    fn on_rgb(self, r: u8, g: u8, b: u8) -> Styled<'static> {
+        self.stylize().on_rgb(r, g, b)
+    }
+
+    color::named_colors!(stylize_color_methods);
+
+    /// Make the text bold.
+    #[must_use]
+    fn bold(self) -> Styled<'static> {
+        self.stylize().bold()
+    }
+
+    /// Make the text dim.
+    #[must_use]
+    fn dim(self) -> Styled<'static> {
+        self.stylize().dim()
+    }
+
+    /// Make the text italic.
+    #[must_use]
+    fn italic(self) -> Styled<'static> {
+        self.stylize().italic()
+    }
+
+    /// Underline the text.
+    #[must_use]
+    fn underline(self) -> Styled<'static> {
+        self.stylize().underline()
+    }
+
+    /// Make the text blink.
+    #[must_use]
+    fn blink(self) -> Styled<'static> {
+        self.stylize().blink()
+    }
+
+    /// Reverse/invert the colors.
+    #[must_use]
+    fn reverse(self) -> Styled<'static> {
+        self.stylize().reverse()
+    }
+
+    /// Hide the text.
+    #[must_use]
+    fn hidden(self) -> Styled<'static> {
+        self.stylize().hidden()
+    }
+
+    /// Strikethrough the text.
+    #[must_use]
+    fn strikethrough(self) -> Styled<'static> {
+        self.stylize().strikethrough()
+    }
+
+    /// Add an overline.
+    #[must_use]
+    fn overline(self) -> Styled<'static> {
+        self.stylize().overline()
+    }
+
+    /// Render the text as superscript.
+    #[must_use]
+    fn superscript(self) -> Styled<'static> {
+        self.stylize().superscript()
+    }
+
+    /// Render the text as subscript.
+    #[must_use]
+    fn subscript(self) -> Styled<'static> {
+        self.stylize().subscript()
+    }
+}
+
+impl<T: fmt::Display> Stylize for T {
+    fn stylize(self) -> Styled<'static> {
+        Styled::new(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caps::ColorLevel;
+    use alloc::string::String;
+
+    #[test]
+    fn test_stylize_str_and_string() {
+        let a = "hi".red().bold().color_level(ColorLevel::Ansi16);
+        let b = String::from("hi")
+            .bold()
+            .fg(Color::Red)
+            .color_level(ColorLevel::Ansi16);
+        assert_eq!(a.render(), "\x1b[1;31mhi\x1b[0m");
+        assert_eq!(a.render(), b.render());
+    }
+
+    #[test]
+    fn test_color_shortcuts() {
+        let styled = "x"
+            .bright_blue()
+            .on_red()
+            .color_level(ColorLevel::TrueColor);
+        assert_eq!(styled.render(), "\x1b[94;41mx\x1b[0m");
+        let styled = "x".on_rgb(1, 2, 3).on_bright_black().rgb(4, 5, 6);
+        assert_eq!(styled.get_style().background, Some(Color::BrightBlack));
+        assert_eq!(styled.get_style().foreground, Some(Color::rgb(4, 5, 6)));
+    }
+
+    #[test]
+    fn test_stylize_display() {
+        let styled = 42.underline().color_level(ColorLevel::Ansi16);
+        assert_eq!(styled.text(), "42");
+        assert_eq!(styled.render(), "\x1b[4m42\x1b[0m");
+    }
+}
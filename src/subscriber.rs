@@ -0,0 +1,190 @@
+//! Theme-aware event formatting for `tracing-subscriber` (requires the
+//! `tracing` feature).
+//!
+//! [`SigilFormat`] implements both `FormatEvent` and `FormatFields`, so one
+//! value styles the level, the span scope, the target, and the fields of
+//! every event with a [`Theme`]:
+//!
+//! ```rust,no_run
+//! use glyphs::{SigilFormat, Theme};
+//!
+//! let format = SigilFormat::new(Theme::dark());
+//! tracing_subscriber::fmt()
+//!     .event_format(format)
+//!     .fmt_fields(format)
+//!     .init();
+//! ```
+//!
+//! Lines look like `WARN  request{id=7}: app::db: slow query rows=120`.
+//! Colors are only written when the subscriber has ANSI output enabled.
+
+use crate::style::Style;
+use crate::theme::{LogLevel, Theme};
+use core::fmt;
+use tracing_core::field::{Field, Visit};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Formats `tracing` events and fields with a [`Theme`].
+///
+/// Levels use [`Theme::for_log_level`], span names [`Theme::accent`], and
+/// targets and field names [`Theme::muted`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SigilFormat {
+    theme: Theme,
+}
+
+impl SigilFormat {
+    /// Format events with `theme`.
+    #[must_use]
+    pub const fn new(theme: Theme) -> Self {
+        Self { theme }
+    }
+}
+
+/// Write `text` in `style` if the writer accepts escape sequences.
+fn paint(writer: &mut Writer<'_>, text: &str, style: Style) -> fmt::Result {
+    if writer.has_ansi_escapes() {
+        writer.write_str(&style.apply(text))
+    } else {
+        writer.write_str(text)
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for SigilFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let level = LogLevel::from(*metadata.level());
+        paint(
+            &mut writer,
+            &format!("{:<5}", level.as_str()),
+            self.theme.for_log_level(level),
+        )?;
+        writer.write_char(' ')?;
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                paint(&mut writer, span.name(), self.theme.accent)?;
+                let extensions = span.extensions();
+                if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                    if !fields.is_empty() {
+                        write!(writer, "{{{fields}}}")?;
+                    }
+                }
+                writer.write_str(": ")?;
+            }
+        }
+
+        paint(&mut writer, metadata.target(), self.theme.muted)?;
+        writer.write_str(": ")?;
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+impl<'w> FormatFields<'w> for SigilFormat {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'w>, fields: R) -> fmt::Result {
+        let mut visitor = FieldVisitor {
+            writer,
+            theme: &self.theme,
+            first: true,
+            result: Ok(()),
+        };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+/// Writes `message` as-is and other fields as `name=value`, separated by
+/// spaces.
+struct FieldVisitor<'w, 't> {
+    writer: Writer<'w>,
+    theme: &'t Theme,
+    first: bool,
+    result: fmt::Result,
+}
+
+impl FieldVisitor<'_, '_> {
+    fn write(&mut self, field: &Field, value: &dyn fmt::Debug) -> fmt::Result {
+        if !self.first {
+            self.writer.write_char(' ')?;
+        }
+        self.first = false;
+        if field.name() == "message" {
+            return write!(self.writer, "{value:?}");
+        }
+        paint(
+            &mut self.writer,
+            &format!("{}=", field.name()),
+            self.theme.muted,
+        )?;
+        write!(self.writer, "{value:?}")
+    }
+}
+
+impl Visit for FieldVisitor<'_, '_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.result.is_ok() {
+            self.result = self.write(field, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_format_event() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let format = SigilFormat::new(Theme::dark());
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .event_format(format)
+            .fmt_fields(format)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", id = 7);
+            let _entered = span.enter();
+            tracing::warn!(target: "app::db", rows = 120, "slow query");
+            tracing::info!(target: "app", "done");
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output,
+            "WARN  request{id=7}: app::db: slow query rows=120\nINFO  request{id=7}: app: done\n"
+        );
+    }
+}
@@ -0,0 +1,673 @@
+//! RAII guards, size detection, and resize notifications for the terminal
+//! (requires the `term` feature).
+//!
+//! Each guard emits its "enter" sequence when created and the matching
+//! "exit" sequence when dropped. Because `Drop` runs during unwinding, the
+//! terminal is also restored if the guarded code panics (unless the binary
+//! is built with `panic = "abort"`).
+//!
+//! ```rust,no_run
+//! use glyphs::term::{AltScreenGuard, RawModeGuard};
+//!
+//! let _screen = AltScreenGuard::new()?;
+//! let _raw = RawModeGuard::new()?;
+//! // ... draw the UI ...
+//! // Both guards restore the terminal when they go out of scope.
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::color::Color;
+use crate::sequences::{ALT_SCREEN_ENTER, ALT_SCREEN_EXIT, MOUSE_DISABLE, MOUSE_ENABLE};
+use std::io::{self, IsTerminal, Stdout, Write};
+use std::time::{Duration, Instant};
+
+/// Writes `seq` to `writer` and flushes it.
+fn emit<W: Write>(writer: &mut W, seq: &str) -> io::Result<()> {
+    writer.write_all(seq.as_bytes())?;
+    writer.flush()
+}
+
+/// Switches to the alternate screen buffer for the lifetime of the guard.
+#[derive(Debug)]
+pub struct AltScreenGuard<W: Write = Stdout> {
+    writer: W,
+}
+
+impl AltScreenGuard<Stdout> {
+    /// Enter the alternate screen on stdout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence cannot be written.
+    pub fn new() -> io::Result<Self> {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl<W: Write> AltScreenGuard<W> {
+    /// Enter the alternate screen on the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence cannot be written.
+    pub fn with_writer(mut writer: W) -> io::Result<Self> {
+        emit(&mut writer, ALT_SCREEN_ENTER)?;
+        Ok(Self { writer })
+    }
+}
+
+impl<W: Write> Drop for AltScreenGuard<W> {
+    fn drop(&mut self) {
+        let _ = emit(&mut self.writer, ALT_SCREEN_EXIT);
+    }
+}
+
+/// Enables mouse tracking for the lifetime of the guard.
+#[derive(Debug)]
+pub struct MouseCaptureGuard<W: Write = Stdout> {
+    writer: W,
+}
+
+impl MouseCaptureGuard<Stdout> {
+    /// Enable mouse tracking on stdout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence cannot be written.
+    pub fn new() -> io::Result<Self> {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl<W: Write> MouseCaptureGuard<W> {
+    /// Enable mouse tracking on the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence cannot be written.
+    pub fn with_writer(mut writer: W) -> io::Result<Self> {
+        emit(&mut writer, MOUSE_ENABLE)?;
+        Ok(Self { writer })
+    }
+}
+
+impl<W: Write> Drop for MouseCaptureGuard<W> {
+    fn drop(&mut self) {
+        let _ = emit(&mut self.writer, MOUSE_DISABLE);
+    }
+}
+
+/// Puts the controlling terminal into raw mode for the lifetime of the guard.
+///
+/// On Unix this toggles termios on stdin; on Windows it changes the console
+/// input mode. The previous settings are restored on drop.
+#[derive(Debug)]
+pub struct RawModeGuard {
+    saved: sys::Saved,
+}
+
+impl RawModeGuard {
+    /// Enable raw mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if stdin is not a terminal or its mode cannot be changed.
+    pub fn new() -> io::Result<Self> {
+        let saved = sys::enable_raw_mode()?;
+        Ok(Self { saved })
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = sys::restore(&self.saved);
+    }
+}
+
+/// The size of the terminal as `(columns, rows)`.
+///
+/// Asks the terminal attached to stdout, stderr, or stdin (`TIOCGWINSZ` on
+/// Unix, the console screen buffer on Windows), then falls back to the
+/// `COLUMNS` and `LINES` environment variables. Returns `None` when none of
+/// them give a non-zero size, for example when output is piped.
+///
+/// ```rust,no_run
+/// let width = glyphs::term_size().map_or(80, |(cols, _)| cols);
+/// ```
+#[must_use]
+pub fn term_size() -> Option<(u16, u16)> {
+    sys::size().or_else(|| size_from_lookup(|name| std::env::var(name).ok()))
+}
+
+/// Reports changes of the terminal size.
+///
+/// On Unix a `SIGWINCH` handler (installed once with `sigaction`, chaining
+/// to any handler that was already there) counts size changes, and each
+/// watcher remembers the count it last saw, so polling is cheap and any
+/// number of watchers see every change; elsewhere each poll compares
+/// [`term_size`] with the last known size.
+///
+/// Iterating blocks until the next change and yields the new
+/// `(columns, rows)`.
+///
+/// ```rust,no_run
+/// use glyphs::term::ResizeWatcher;
+///
+/// for (cols, rows) in ResizeWatcher::new() {
+///     println!("now {cols}x{rows}");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResizeWatcher {
+    last: Option<(u16, u16)>,
+    generation: u64,
+    interval: Duration,
+}
+
+impl ResizeWatcher {
+    /// Start watching from the current size.
+    #[must_use]
+    pub fn new() -> Self {
+        sys::watch_resize();
+        Self {
+            last: term_size(),
+            generation: sys::resize_generation(),
+            interval: Duration::from_millis(50),
+        }
+    }
+
+    /// How long iteration sleeps between polls (50ms by default).
+    #[must_use]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The size seen by the last poll.
+    #[must_use]
+    pub const fn size(&self) -> Option<(u16, u16)> {
+        self.last
+    }
+
+    /// The new size if it changed since the last poll, without blocking.
+    pub fn poll(&mut self) -> Option<(u16, u16)> {
+        if !self.resized() {
+            return None;
+        }
+        let size = term_size();
+        if size == self.last {
+            return None;
+        }
+        self.last = size;
+        size
+    }
+
+    /// Whether a resize was signalled since the last call.
+    fn resized(&mut self) -> bool {
+        let generation = sys::resize_generation();
+        let resized = generation != self.generation;
+        self.generation = generation;
+        resized
+    }
+}
+
+impl Default for ResizeWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for ResizeWatcher {
+    type Item = (u16, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(size) = self.poll() {
+                return Some(size);
+            }
+            std::thread::sleep(self.interval);
+        }
+    }
+}
+
+fn size_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Option<(u16, u16)> {
+    let read = |name: &str| lookup(name)?.trim().parse::<u16>().ok().filter(|&n| n > 0);
+    Some((read("COLUMNS")?, read("LINES")?))
+}
+
+/// Ask the terminal for its background color (`OSC 11 ; ?`).
+///
+/// Stdin is put in raw mode while waiting for the reply, for at most
+/// `timeout`. Returns `None` when stdin or stdout isn't a terminal, the
+/// terminal doesn't answer in time, or the reply can't be read. Only
+/// supported on Unix.
+///
+/// A reply that starts arriving after `timeout` is read up to its BEL or
+/// ST and thrown away, waiting up to 50 ms between bytes, so it doesn't
+/// show up later as typed input. Input typed during that wait is lost.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// if let Some(bg) = glyphs::term::query_background(Duration::from_millis(100)) {
+///     println!("background is {}", bg.name());
+/// }
+/// ```
+#[must_use]
+pub fn query_background(timeout: Duration) -> Option<Color> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return None;
+    }
+    let _raw = RawModeGuard::new().ok()?;
+    emit(&mut io::stdout(), "\x1b]11;?\x1b\\").ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    while !(reply.ends_with(b"\x07") || reply.ends_with(b"\x1b\\")) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        let mut byte = [0];
+        if left.is_zero() || reply.len() > 64 || sys::read_timeout(&mut byte, left).ok()? == 0 {
+            discard_late_reply(reply.last().copied(), |buf| {
+                sys::read_timeout(buf, LATE_REPLY_GRACE)
+            });
+            return None;
+        }
+        reply.push(byte[0]);
+    }
+    parse_color_reply(std::str::from_utf8(&reply).ok()?)
+}
+
+/// How long [`discard_late_reply`] waits for each byte.
+const LATE_REPLY_GRACE: Duration = Duration::from_millis(50);
+
+/// Read and drop bytes up to the BEL or ST ending a reply, stopping early
+/// when `read` returns nothing or fails. `last` is the byte read before.
+fn discard_late_reply(mut last: Option<u8>, mut read: impl FnMut(&mut [u8]) -> io::Result<usize>) {
+    let mut byte = [0];
+    while !matches!(read(&mut byte), Ok(0) | Err(_)) {
+        if byte[0] == b'\x07' || (last == Some(b'\x1b') && byte[0] == b'\\') {
+            return;
+        }
+        last = Some(byte[0]);
+    }
+}
+
+/// Parse an `OSC 11 ; rgb:RRRR/GGGG/BBBB` reply, with 1 to 4 hex digits
+/// per channel.
+fn parse_color_reply(reply: &str) -> Option<Color> {
+    let start = reply.find("rgb:")? + 4;
+    let body = reply[start..].trim_end_matches(['\x07', '\x1b', '\\']);
+    let mut channels = body.split('/').map(|hex| {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = match hex.len() {
+            1..=4 => (1u32 << (4 * hex.len())) - 1,
+            _ => return None,
+        };
+        u8::try_from(value * 255 / max).ok()
+    });
+    let (r, g, b) = (channels.next()??, channels.next()??, channels.next()??);
+    channels.next().is_none().then_some(Color::rgb(r, g, b))
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::io;
+    use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+    use std::sync::Once;
+    use std::time::Duration;
+
+    type Handler = extern "C" fn(libc::c_int);
+    type InfoHandler = extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void);
+
+    /// Number of `SIGWINCH` signals received.
+    static GENERATION: AtomicU64 = AtomicU64::new(0);
+    static PREVIOUS_HANDLER: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+    /// Whether the previous handler takes `SA_SIGINFO` arguments.
+    static PREVIOUS_SIGINFO: AtomicBool = AtomicBool::new(false);
+    static INSTALL: Once = Once::new();
+
+    extern "C" fn on_sigwinch(
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+        let previous = PREVIOUS_HANDLER.load(Ordering::Acquire);
+        if previous == libc::SIG_DFL || previous == libc::SIG_IGN || previous == libc::SIG_ERR {
+            return;
+        }
+        if PREVIOUS_SIGINFO.load(Ordering::Relaxed) {
+            // SAFETY: `previous` was installed with `SA_SIGINFO`, so it is
+            // a three-argument `sa_sigaction` handler.
+            let handler =
+                unsafe { std::mem::transmute::<libc::sighandler_t, InfoHandler>(previous) };
+            handler(signal, info, context);
+        } else {
+            // SAFETY: `previous` was installed without `SA_SIGINFO`, so it
+            // is a one-argument `sa_handler`.
+            let handler = unsafe { std::mem::transmute::<libc::sighandler_t, Handler>(previous) };
+            handler(signal);
+        }
+    }
+
+    pub fn watch_resize() {
+        INSTALL.call_once(|| {
+            // SAFETY: `sigaction` structs are plain old data, filled in by
+            // `sigemptyset` and `sigaction` before use. The handler only
+            // touches atomics and forwards to the previously installed
+            // handler with the signature its flags call for.
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_sigwinch as InfoHandler as libc::sighandler_t;
+                action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(libc::SIGWINCH, &action, &mut previous) == 0 {
+                    let siginfo = previous.sa_flags & libc::SA_SIGINFO != 0;
+                    PREVIOUS_SIGINFO.store(siginfo, Ordering::Relaxed);
+                    PREVIOUS_HANDLER.store(previous.sa_sigaction, Ordering::Release);
+                }
+            }
+        });
+    }
+
+    pub fn resize_generation() -> u64 {
+        GENERATION.load(Ordering::Relaxed)
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct Saved(libc::termios);
+
+    impl std::fmt::Debug for Saved {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Saved(termios)")
+        }
+    }
+
+    pub fn enable_raw_mode() -> io::Result<Saved> {
+        // SAFETY: `termios` is plain old data and is fully initialised by
+        // `tcgetattr` before being read.
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            libc::cfmakeraw(&mut raw);
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Saved(original))
+        }
+    }
+
+    pub fn restore(saved: &Saved) -> io::Result<()> {
+        // SAFETY: `saved.0` was produced by a successful `tcgetattr`.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &saved.0) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Read from stdin, returning `Ok(0)` if nothing arrives in `timeout`.
+    pub fn read_timeout(buf: &mut [u8], timeout: Duration) -> io::Result<usize> {
+        let mut fd = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let millis = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+        // SAFETY: `fd` is a single valid `pollfd`.
+        match unsafe { libc::poll(&mut fd, 1, millis) } {
+            0 => return Ok(0),
+            n if n < 0 => return Err(io::Error::last_os_error()),
+            _ => {}
+        }
+        // SAFETY: `buf` is valid for writes of `buf.len()` bytes.
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        usize::try_from(n).map_err(|_| io::Error::last_os_error())
+    }
+
+    pub fn size() -> Option<(u16, u16)> {
+        [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO]
+            .into_iter()
+            .find_map(|fd| {
+                // SAFETY: `winsize` is plain old data that `TIOCGWINSZ` fills in.
+                let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+                if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut ws) } != 0 {
+                    return None;
+                }
+                (ws.ws_col > 0 && ws.ws_row > 0).then_some((ws.ws_col, ws.ws_row))
+            })
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::io;
+    use std::time::Duration;
+
+    type Handle = *mut core::ffi::c_void;
+
+    const STD_INPUT_HANDLE: u32 = 0xFFFF_FFF6; // (DWORD)-10
+    const STD_OUTPUT_HANDLE: u32 = 0xFFFF_FFF5; // (DWORD)-11
+    const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+    const ENABLE_LINE_INPUT: u32 = 0x0002;
+    const ENABLE_ECHO_INPUT: u32 = 0x0004;
+    const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x0200;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(handle: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(handle: Handle, mode: u32) -> i32;
+        fn GetConsoleScreenBufferInfo(handle: Handle, info: *mut ScreenBufferInfo) -> i32;
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Coord {
+        x: i16,
+        y: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct SmallRect {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ScreenBufferInfo {
+        size: Coord,
+        cursor_position: Coord,
+        attributes: u16,
+        window: SmallRect,
+        maximum_window_size: Coord,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct Saved(u32);
+
+    pub fn watch_resize() {}
+
+    /// A new value on every call, so every poll checks the size.
+    pub fn resize_generation() -> u64 {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        static POLLS: AtomicU64 = AtomicU64::new(0);
+        POLLS.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub fn enable_raw_mode() -> io::Result<Saved> {
+        // SAFETY: plain Win32 calls on the process's own stdin handle.
+        unsafe {
+            let handle = GetStdHandle(STD_INPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let raw = (mode & !(ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT))
+                | ENABLE_VIRTUAL_TERMINAL_INPUT;
+            if SetConsoleMode(handle, raw) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Saved(mode))
+        }
+    }
+
+    pub fn restore(saved: &Saved) -> io::Result<()> {
+        // SAFETY: plain Win32 calls on the process's own stdin handle.
+        unsafe {
+            if SetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), saved.0) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_timeout(_buf: &mut [u8], _timeout: Duration) -> io::Result<usize> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    pub fn size() -> Option<(u16, u16)> {
+        let mut info = ScreenBufferInfo::default();
+        // SAFETY: plain Win32 call filling in a `#[repr(C)]` struct we own.
+        if unsafe { GetConsoleScreenBufferInfo(GetStdHandle(STD_OUTPUT_HANDLE), &mut info) } == 0 {
+            return None;
+        }
+        let cols = u16::try_from(info.window.right - info.window.left + 1).ok()?;
+        let rows = u16::try_from(info.window.bottom - info.window.top + 1).ok()?;
+        (cols > 0 && rows > 0).then_some((cols, rows))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    use std::io;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct Saved;
+
+    pub fn watch_resize() {}
+
+    /// A new value on every call, so every poll checks the size.
+    pub fn resize_generation() -> u64 {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        static POLLS: AtomicU64 = AtomicU64::new(0);
+        POLLS.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub fn enable_raw_mode() -> io::Result<Saved> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "raw mode is not supported on this platform",
+        ))
+    }
+
+    pub fn restore(_saved: &Saved) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn read_timeout(_buf: &mut [u8], _timeout: Duration) -> io::Result<usize> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    pub fn size() -> Option<(u16, u16)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::lookup_from;
+
+    #[test]
+    fn test_alt_screen_guard_restores_on_drop() {
+        let mut buf = Vec::new();
+        {
+            let _guard = AltScreenGuard::with_writer(&mut buf).unwrap();
+        }
+        assert_eq!(
+            buf,
+            format!("{ALT_SCREEN_ENTER}{ALT_SCREEN_EXIT}").into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_discard_late_reply() {
+        let mut input: &[u8] = b"]11;rgb:0/0/0\x1b\\typed";
+        discard_late_reply(Some(b'\x1b'), |buf| io::Read::read(&mut input, buf));
+        assert_eq!(input, b"typed");
+
+        let mut input: &[u8] = b"\\x";
+        discard_late_reply(Some(b'\x1b'), |buf| io::Read::read(&mut input, buf));
+        assert_eq!(input, b"x");
+
+        let mut input: &[u8] = b"rgb:1/2";
+        discard_late_reply(None, |buf| io::Read::read(&mut input, buf));
+        assert!(input.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resize_seen_by_every_watcher() {
+        let mut first = ResizeWatcher::new();
+        let mut second = ResizeWatcher::new();
+        assert!(!first.resized());
+        assert!(!second.resized());
+        // SAFETY: raising a signal we handle on the current thread.
+        unsafe { libc::raise(libc::SIGWINCH) };
+        assert!(first.resized());
+        assert!(second.resized());
+        assert!(!first.resized());
+        assert!(!second.resized());
+    }
+
+    #[test]
+    fn test_size_from_env() {
+        let size = |vars: &[(&str, &str)]| size_from_lookup(lookup_from(vars));
+        assert_eq!(
+            size(&[("COLUMNS", "120"), ("LINES", " 40")]),
+            Some((120, 40))
+        );
+        assert_eq!(size(&[("COLUMNS", "120")]), None);
+        assert_eq!(size(&[("COLUMNS", "0"), ("LINES", "40")]), None);
+    }
+
+    #[test]
+    fn test_parse_color_reply() {
+        assert_eq!(
+            parse_color_reply("\x1b]11;rgb:ffff/8080/0000\x1b\\"),
+            Some(Color::rgb(255, 128, 0))
+        );
+        assert_eq!(
+            parse_color_reply("\x1b]11;rgb:f/80/000\x07"),
+            Some(Color::rgb(255, 128, 0))
+        );
+        assert_eq!(parse_color_reply("\x1b]11;rgb:ff/ff\x07"), None);
+        assert_eq!(parse_color_reply("\x1b]11;?\x07"), None);
+    }
+
+    #[test]
+    fn test_mouse_guard_restores_on_panic() {
+        let buf = std::sync::Mutex::new(Vec::new());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut lock = buf.lock().unwrap();
+            let _guard = MouseCaptureGuard::with_writer(&mut *lock).unwrap();
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        let bytes = buf
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        assert_eq!(bytes, format!("{MOUSE_ENABLE}{MOUSE_DISABLE}").into_bytes());
+    }
+}
@@ -0,0 +1,345 @@
+//! Reading compiled terminfo entries.
+//!
+//! Only the parts needed for [`TermCaps`] are decoded: the color count,
+//! whether the attribute and `rep` strings are present, and the extended
+//! capabilities (`Tc`, `RGB`, `smxx`) that ncurses stores after the
+//! standard ones.
+
+use crate::caps::{ColorLevel, TermCaps};
+use crate::modifier::{Modifier, ModifierSet};
+use std::path::PathBuf;
+
+/// Magic number of the legacy format with 16-bit numbers.
+const MAGIC_16: i16 = 0o432;
+/// Magic number of the ncurses 6.1 format with 32-bit numbers.
+const MAGIC_32: i16 = 0o1036;
+
+/// `max_colors` in the numeric section.
+const COLORS: usize = 13;
+
+/// `repeat_char` in the string section.
+const REP: usize = 121;
+
+/// Standard string capabilities that turn on an attribute.
+const ATTRIBUTES: [(usize, &[Modifier]); 7] = [
+    (26, &[Modifier::Blink, Modifier::RapidBlink]), // blink
+    (27, &[Modifier::Bold]),                        // bold
+    (30, &[Modifier::Dim]),                         // dim
+    (32, &[Modifier::Hidden]),                      // invis
+    (34, &[Modifier::Reverse]),                     // rev
+    (36, &[Modifier::Underline]),                   // smul
+    (311, &[Modifier::Italic]),                     // sitm
+];
+
+/// The capabilities of one terminfo entry that matter for styling.
+#[derive(Debug, Default)]
+pub(crate) struct Entry {
+    colors: Option<i32>,
+    strings: Vec<bool>,
+    extended: Vec<String>,
+}
+
+impl Entry {
+    /// Find and read the entry for `term` in the terminfo search path.
+    pub(crate) fn load(term: &str) -> Option<Self> {
+        let first = term.chars().next()?;
+        if term.contains('/') {
+            return None;
+        }
+        search_dirs().into_iter().find_map(|dir| {
+            // Linux uses the first letter; macOS uses its hex code.
+            [first.to_string(), format!("{:x}", u32::from(first))]
+                .iter()
+                .find_map(|sub| std::fs::read(dir.join(sub).join(term)).ok())
+                .and_then(|data| Self::parse(&data))
+        })
+    }
+
+    /// Decode a compiled entry.
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader { data, pos: 0 };
+        let num_width = match reader.short()? {
+            MAGIC_16 => 2,
+            MAGIC_32 => 4,
+            _ => return None,
+        };
+        let names_size = reader.count()?;
+        let bool_count = reader.count()?;
+        let num_count = reader.count()?;
+        let str_count = reader.count()?;
+        let table_size = reader.count()?;
+
+        reader.skip(names_size + bool_count)?;
+        reader.align();
+        let mut entry = Self::default();
+        for i in 0..num_count {
+            let value = reader.number(num_width)?;
+            if i == COLORS && value >= 0 {
+                entry.colors = Some(value);
+            }
+        }
+        for _ in 0..str_count {
+            entry.strings.push(reader.short()? >= 0);
+        }
+        reader.skip(table_size)?;
+        reader.align();
+
+        // The extended section is optional; ignore it if it's damaged.
+        let _ = entry.parse_extended(&mut reader, num_width);
+        Some(entry)
+    }
+
+    fn parse_extended(&mut self, reader: &mut Reader<'_>, num_width: usize) -> Option<()> {
+        let bool_count = reader.count()?;
+        let num_count = reader.count()?;
+        let str_count = reader.count()?;
+        let _items = reader.count()?;
+        let table_size = reader.count()?;
+
+        let mut present = Vec::new();
+        for _ in 0..bool_count {
+            present.push(reader.byte()? == 1);
+        }
+        reader.align();
+        for _ in 0..num_count {
+            present.push(reader.number(num_width)? >= 0);
+        }
+        let mut values = Vec::new();
+        for _ in 0..str_count {
+            let offset = reader.short()?;
+            present.push(offset >= 0);
+            values.push(offset);
+        }
+        let mut names = Vec::new();
+        for _ in 0..present.len() {
+            names.push(usize::try_from(reader.short()?).ok()?);
+        }
+        let table = reader.take(table_size)?;
+
+        // Names follow the string values in the table.
+        let names_start = values
+            .iter()
+            .filter_map(|&offset| usize::try_from(offset).ok())
+            .map(|offset| c_str(table, offset).map_or(0, |s| offset + s.len() + 1))
+            .max()
+            .unwrap_or(0);
+        for (name, present) in names.into_iter().zip(present) {
+            if present {
+                let name = c_str(table, names_start + name)?;
+                self.extended
+                    .push(String::from_utf8_lossy(name).into_owned());
+            }
+        }
+        Some(())
+    }
+
+    fn has_string(&self, index: usize) -> bool {
+        self.strings.get(index).copied().unwrap_or(false)
+    }
+
+    fn has_extended(&self, name: &str) -> bool {
+        self.extended.iter().any(|n| n == name)
+    }
+
+    /// What this entry says the terminal can display.
+    pub(crate) fn caps(&self) -> TermCaps {
+        let color = if self.has_extended("Tc") || self.has_extended("RGB") {
+            ColorLevel::TrueColor
+        } else {
+            match self.colors {
+                Some(256..) => ColorLevel::Ansi256,
+                Some(8..) => ColorLevel::Ansi16,
+                _ => ColorLevel::None,
+            }
+        };
+
+        let mut unsupported = ModifierSet::empty();
+        for (index, modifiers) in ATTRIBUTES {
+            if !self.has_string(index) {
+                unsupported = modifiers.iter().fold(unsupported, |set, &m| set.with(m));
+            }
+        }
+        if !self.has_extended("smxx") {
+            unsupported = unsupported.with(Modifier::Strikethrough);
+        }
+
+        TermCaps {
+            color,
+            unsupported,
+            hyperlinks: false,
+            repeat: self.has_string(REP),
+            rectangles: false,
+        }
+    }
+}
+
+/// Directories searched for entries, in the order ncurses uses.
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Ok(list) = std::env::var("TERMINFO_DIRS") {
+        dirs.extend(list.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+    }
+    for dir in [
+        "/etc/terminfo",
+        "/lib/terminfo",
+        "/usr/share/terminfo",
+        "/usr/lib/terminfo",
+    ] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+/// The NUL-terminated string at `offset`.
+fn c_str(table: &[u8], offset: usize) -> Option<&[u8]> {
+    let rest = table.get(offset..)?;
+    let end = rest.iter().position(|&b| b == 0)?;
+    Some(&rest[..end])
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn align(&mut self) {
+        self.pos += self.pos % 2;
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn short(&mut self) -> Option<i16> {
+        self.take(2).map(|b| i16::from_le_bytes([b[0], b[1]]))
+    }
+
+    /// A size from a header; negative sizes are invalid.
+    fn count(&mut self) -> Option<usize> {
+        usize::try_from(self.short()?).ok()
+    }
+
+    fn number(&mut self, width: usize) -> Option<i32> {
+        if width == 4 {
+            self.take(4)
+                .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        } else {
+            self.short().map(i32::from)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_short(out: &mut Vec<u8>, value: i16) {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// A compiled entry with 16 colors and the given standard strings, plus
+    /// extended booleans.
+    fn entry(strings: &[usize], ext_bools: &[&str]) -> Vec<u8> {
+        let names = b"test|test terminal\0";
+        let str_count = 312;
+        let mut table = Vec::new();
+        let mut offsets = vec![-1i16; str_count];
+        for &index in strings {
+            offsets[index] = i16::try_from(table.len()).unwrap();
+            table.extend_from_slice(b"\x1b[1m\0");
+        }
+
+        let mut out = Vec::new();
+        for value in [
+            MAGIC_16,
+            19,
+            0,
+            14,
+            312,
+            i16::try_from(table.len()).unwrap(),
+        ] {
+            push_short(&mut out, value);
+        }
+        out.extend_from_slice(names);
+        out.push(0);
+        for i in 0..14 {
+            push_short(&mut out, if i == COLORS { 16 } else { -1 });
+        }
+        for offset in offsets {
+            push_short(&mut out, offset);
+        }
+        out.extend_from_slice(&table);
+        if out.len() % 2 == 1 {
+            out.push(0);
+        }
+
+        let mut ext_table = Vec::new();
+        let mut name_offsets = Vec::new();
+        for name in ext_bools {
+            name_offsets.push(i16::try_from(ext_table.len()).unwrap());
+            ext_table.extend_from_slice(name.as_bytes());
+            ext_table.push(0);
+        }
+        let count = i16::try_from(ext_bools.len()).unwrap();
+        let size = i16::try_from(ext_table.len()).unwrap();
+        for value in [count, 0, 0, count, size] {
+            push_short(&mut out, value);
+        }
+        out.extend(ext_bools.iter().map(|_| 1));
+        if out.len() % 2 == 1 {
+            out.push(0);
+        }
+        for offset in name_offsets {
+            push_short(&mut out, offset);
+        }
+        out.extend_from_slice(&ext_table);
+        out
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let caps = Entry::parse(&entry(&[27, 36, 121, 311], &[]))
+            .unwrap()
+            .caps();
+        assert_eq!(caps.color, ColorLevel::Ansi16);
+        assert!(caps.repeat);
+        assert!(caps.supports(Modifier::Bold));
+        assert!(caps.supports(Modifier::Italic));
+        assert!(!caps.supports(Modifier::Dim));
+        assert!(!caps.supports(Modifier::Strikethrough));
+        // Modifiers terminfo has no capability for are assumed to work.
+        assert!(caps.supports(Modifier::Overline));
+
+        let caps = Entry::parse(&entry(&[27], &["Tc", "smxx"])).unwrap().caps();
+        assert_eq!(caps.color, ColorLevel::TrueColor);
+        assert!(caps.supports(Modifier::Strikethrough));
+        assert!(!caps.supports(Modifier::Italic));
+        assert!(!caps.repeat);
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert!(Entry::parse(b"").is_none());
+        assert!(Entry::parse(&[0x1a, 0x01, 0xff, 0xff]).is_none());
+        let mut data = entry(&[27], &[]);
+        data.truncate(40);
+        assert!(Entry::parse(&data).is_none());
+    }
+}
@@ -0,0 +1,317 @@
+//! Helpers for testing code that emits ANSI escape sequences.
+//!
+//! ```rust
+//! use glyphs::{assert_styled_eq, assert_visible_eq, style, Color, ColorLevel};
+//!
+//! let out = style("ok")
+//!     .fg(Color::Green)
+//!     .bold()
+//!     .color_level(ColorLevel::Ansi16)
+//!     .to_string();
+//! assert_visible_eq!(out, "ok");
+//! assert_styled_eq!(out, "\x1b[32;1mok\x1b[0m");
+//! ```
+
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod arbitrary;
+
+use crate::control::ControlChar;
+use crate::escape::{normalize_sgr, EscapeKind};
+use crate::parser::{parse, strip_ansi, ParsedSequence};
+use crate::style::Style;
+use crate::tracker::StyleTracker;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Rewrite every SGR sequence in `input` into a canonical form.
+///
+/// Parameters that are overridden later in the same sequence are dropped,
+/// and the remaining ones are sorted (modifiers, then foreground, then
+/// background), so `\x1b[31;1m` and `\x1b[1;31m` normalize identically.
+/// A reset inside a sequence is kept as a leading `0`. Text and non-SGR
+/// sequences are left untouched.
+#[must_use]
+pub fn normalize_ansi(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for segment in parse(input) {
+        match segment {
+            ParsedSequence::Text(text) => out.push_str(&text),
+            ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr => {
+                out.push_str(&normalize_sgr(&e.params));
+            }
+            ParsedSequence::Escape(e) => out.push_str(&e.raw),
+            ParsedSequence::Control(c) => out.push(c.as_char()),
+            ParsedSequence::Malformed { raw, .. } => out.push_str(&raw),
+        }
+    }
+    out
+}
+
+/// Displays a string with its escape sequences replaced by readable tokens.
+///
+/// `\x1b[1;31mError\x1b[0m` is shown as `⟨bold, red fg⟩Error⟨reset⟩`. The
+/// `Debug` output is the same (quoted), so failing `assert_eq!`s on
+/// `HumanDisplay` values are readable.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HumanDisplay<'a>(pub &'a str);
+
+impl fmt::Display for HumanDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in parse(self.0) {
+            match segment {
+                ParsedSequence::Text(text) => f.write_str(&text)?,
+                ParsedSequence::Escape(e) => write!(f, "⟨{}⟩", e.description)?,
+                ParsedSequence::Control(c @ (ControlChar::LineFeed | ControlChar::Tab)) => {
+                    write!(f, "{}", c.as_char())?;
+                }
+                ParsedSequence::Control(c) => write!(f, "⟨{c}⟩")?,
+                ParsedSequence::Malformed { raw, reason } => write!(f, "⟨{reason}: {raw:?}⟩")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HumanDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{self}\"")
+    }
+}
+
+/// One visible cell of a styled string.
+#[derive(Debug, Clone)]
+pub struct DiffCell {
+    /// The grapheme cluster shown in the cell.
+    pub grapheme: String,
+    /// The style active when the grapheme was printed.
+    pub style: Style,
+}
+
+impl fmt::Display for DiffCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({})", self.grapheme, self.style.describe())
+    }
+}
+
+/// Result of [`diff_ansi`].
+#[derive(Debug, Clone)]
+pub enum AnsiDiff {
+    /// Both strings show the same graphemes with the same styles.
+    Equal,
+    /// The strings differ, starting at visible cell `index`.
+    Differs {
+        /// Index of the first differing cell (in graphemes).
+        index: usize,
+        /// The cell in the left string, or `None` if it ended first.
+        left: Option<DiffCell>,
+        /// The cell in the right string, or `None` if it ended first.
+        right: Option<DiffCell>,
+    },
+}
+
+impl AnsiDiff {
+    /// Whether the strings render identically.
+    #[must_use]
+    pub const fn is_equal(&self) -> bool {
+        matches!(self, Self::Equal)
+    }
+}
+
+impl fmt::Display for AnsiDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Equal => f.write_str("no differences"),
+            Self::Differs { index, left, right } => {
+                let show = |cell: &Option<DiffCell>| {
+                    cell.as_ref()
+                        .map_or_else(|| "<end of string>".to_string(), ToString::to_string)
+                };
+                write!(
+                    f,
+                    "first difference at cell {index}:\n   left: {}\n  right: {}",
+                    show(left),
+                    show(right)
+                )
+            }
+        }
+    }
+}
+
+/// Compare two styled strings cell by cell.
+///
+/// Each visible grapheme is paired with the style active when it was
+/// printed, so `\x1b[1;31mx` and `\x1b[31m\x1b[1mx` compare equal while a
+/// byte-wise comparison would not.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::testing::diff_ansi;
+///
+/// let diff = diff_ansi("\x1b[31mab\x1b[0m", "\x1b[31ma\x1b[32mb\x1b[0m");
+/// assert!(!diff.is_equal());
+/// println!("{diff}"); // first difference at cell 1: ...
+/// ```
+#[must_use]
+pub fn diff_ansi(left: &str, right: &str) -> AnsiDiff {
+    let (left, right) = (styled_cells(left), styled_cells(right));
+    for index in 0..left.len().max(right.len()) {
+        let (l, r) = (left.get(index), right.get(index));
+        let same = match (l, r) {
+            (Some(l), Some(r)) => l.grapheme == r.grapheme && l.style == r.style,
+            _ => false,
+        };
+        if !same {
+            return AnsiDiff::Differs {
+                index,
+                left: l.cloned(),
+                right: r.cloned(),
+            };
+        }
+    }
+    AnsiDiff::Equal
+}
+
+fn styled_cells(input: &str) -> Vec<DiffCell> {
+    let mut tracker = StyleTracker::new();
+    let mut cells = Vec::new();
+    for segment in parse(input) {
+        match &segment {
+            ParsedSequence::Text(text) => {
+                cells.extend(text.graphemes(true).map(|g| DiffCell {
+                    grapheme: g.to_string(),
+                    style: tracker.style(),
+                }));
+            }
+            ParsedSequence::Control(c) => cells.push(DiffCell {
+                grapheme: c.as_char().to_string(),
+                style: tracker.style(),
+            }),
+            ParsedSequence::Escape(_) | ParsedSequence::Malformed { .. } => {
+                tracker.apply_segment(&segment);
+            }
+        }
+    }
+    cells
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn __visible_eq_failure(actual: &str, expected: &str) -> Option<String> {
+    let (a, e) = (strip_ansi(actual), strip_ansi(expected));
+    (a != e).then(|| {
+        format!(
+            "assertion failed: visible text differs\n  actual: {a:?}\nexpected: {e:?}\n  actual (styled): {}\nexpected (styled): {}",
+            HumanDisplay(actual),
+            HumanDisplay(expected),
+        )
+    })
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn __styled_eq_failure(actual: &str, expected: &str) -> Option<String> {
+    let (a, e) = (normalize_ansi(actual), normalize_ansi(expected));
+    (a != e).then(|| {
+        format!(
+            "assertion failed: styled text differs\n  actual: {}\nexpected: {}\n{}",
+            HumanDisplay(&a),
+            HumanDisplay(&e),
+            diff_ansi(actual, expected),
+        )
+    })
+}
+
+/// Assert that two strings have the same visible text, ignoring escapes.
+#[macro_export]
+macro_rules! assert_visible_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(msg) = $crate::testing::__visible_eq_failure(
+            ::core::convert::AsRef::<str>::as_ref(&$actual),
+            ::core::convert::AsRef::<str>::as_ref(&$expected),
+        ) {
+            panic!("{}", msg);
+        }
+    };
+}
+
+/// Assert that two strings are equal after [`normalize_ansi`](crate::testing::normalize_ansi).
+#[macro_export]
+macro_rules! assert_styled_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(msg) = $crate::testing::__styled_eq_failure(
+            ::core::convert::AsRef::<str>::as_ref(&$actual),
+            ::core::convert::AsRef::<str>::as_ref(&$expected),
+        ) {
+            panic!("{}", msg);
+        }
+    };
+}
+
+/// An environment lookup that only knows `vars`, for tests of the
+/// `*_from_lookup` detection functions.
+#[cfg(all(test, feature = "std"))]
+pub(crate) fn lookup_from<'a>(
+    vars: &'a [(&'a str, &'a str)],
+) -> impl Fn(&str) -> Option<String> + 'a {
+    move |name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| (*v).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ansi() {
+        assert_eq!(normalize_ansi("\x1b[31;1mx"), normalize_ansi("\x1b[1;31mx"));
+        assert_eq!(normalize_ansi("\x1b[31;32mx"), "\x1b[32mx");
+        assert_eq!(normalize_ansi("\x1b[1;0;38;2;1;2;3m"), "\x1b[0;38;2;1;2;3m");
+        assert_eq!(normalize_ansi("\x1b[1;22m"), "\x1b[22m");
+        assert_eq!(normalize_ansi("\x1b[m"), "\x1b[0m");
+    }
+
+    #[test]
+    fn test_human_display() {
+        let shown = HumanDisplay("\x1b[1;31mError\x1b[0m").to_string();
+        assert_eq!(shown, "⟨bold, red fg⟩Error⟨reset⟩");
+    }
+
+    #[test]
+    fn test_diff_ansi() {
+        assert!(diff_ansi("\x1b[1;31mx\x1b[0m", "\x1b[31m\x1b[1mx").is_equal());
+
+        let AnsiDiff::Differs { index, left, right } = diff_ansi("ab\x1b[1mc", "abc") else {
+            panic!("expected a difference");
+        };
+        assert_eq!(index, 2);
+        assert!(left
+            .unwrap()
+            .style
+            .modifiers
+            .contains(crate::Modifier::Bold));
+        assert_eq!(right.unwrap().grapheme, "c");
+
+        assert!(matches!(
+            diff_ansi("ab", "a"),
+            AnsiDiff::Differs {
+                index: 1,
+                right: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_assert_macros() {
+        crate::assert_visible_eq!("\x1b[31mhi\x1b[0m", "hi");
+        crate::assert_styled_eq!("\x1b[31;1mhi", String::from("\x1b[1;31mhi"));
+        assert!(__visible_eq_failure("a", "b").is_some());
+    }
+}
@@ -0,0 +1,313 @@
+//! Random ANSI streams for fuzzing and property tests (requires the
+//! `proptest` or `arbitrary` feature).
+//!
+//! Streams are built from pieces: plain text, SGR sequences, cursor
+//! movement, erases, mode switches, OSC titles and hyperlinks, short
+//! escapes, control characters, and, in the non-valid generators,
+//! truncated, unterminated, and overflowing sequences. Neighbouring pieces
+//! can combine (a truncated CSI followed by text may end up complete), so
+//! streams that allow malformed pieces contain malformed sequences often
+//! but not always.
+//!
+//! With `proptest`, [`ansi_stream`] and [`valid_ansi_stream`] are
+//! strategies; with `arbitrary`, [`AnsiStream`] and [`ValidAnsiStream`]
+//! implement `Arbitrary` for `cargo fuzz` targets. Either way,
+//! [`assert_parser_invariants`] checks what the parser guarantees for any
+//! input:
+//!
+//! ```rust,ignore
+//! use glyphs::testing::arbitrary::{ansi_stream, assert_parser_invariants};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn my_filter_keeps_text(input in ansi_stream()) {
+//!         assert_parser_invariants(&input);
+//!         assert_eq!(glyphs::strip_ansi(&my_filter(&input)), glyphs::strip_ansi(&input));
+//!     }
+//! }
+//! ```
+
+use crate::parser::{parse, parse_strict, strip_ansi, ParsedSequence, StreamParser};
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "arbitrary")]
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Piece kinds that are always well-formed on their own.
+const VALID_KINDS: u8 = 10;
+
+/// All piece kinds, including the malformed ones.
+const ALL_KINDS: u8 = 15;
+
+/// Append the piece of kind `kind`, using `nums` and `text` for its
+/// parameters and content.
+fn piece(kind: u8, nums: &[u32], text: &str, out: &mut String) {
+    let n = |i: usize| nums.get(i).copied().unwrap_or(0);
+    // Text without anything that could start an escape sequence.
+    let clean = |keep_controls: bool| -> String {
+        text.chars()
+            .filter(|&c| c != '\x1b' && !('\u{80}'..='\u{9f}').contains(&c))
+            .filter(|c| keep_controls || !c.is_control())
+            .collect()
+    };
+
+    match kind {
+        0 => out.push_str(&clean(true)),
+        1 => {
+            let params: alloc::vec::Vec<String> =
+                nums.iter().map(|p| format!("{}", p % 108)).collect();
+            let _ = write!(out, "\x1b[{}m", params.join(";"));
+        }
+        2 => {
+            let target = if n(0) % 2 == 0 { 38 } else { 48 };
+            let _ = if n(1) % 2 == 0 {
+                write!(out, "\x1b[{target};5;{}m", n(2) % 256)
+            } else {
+                let (r, g, b) = (n(2) % 256, n(3) % 256, n(4) % 256);
+                write!(out, "\x1b[{target};2;{r};{g};{b}m")
+            };
+        }
+        3 => {
+            let count = n(1) % 200;
+            let _ = match n(0) % 6 {
+                0 => write!(out, "\x1b[{count}A"),
+                1 => write!(out, "\x1b[{count}B"),
+                2 => write!(out, "\x1b[{count}C"),
+                3 => write!(out, "\x1b[{count}D"),
+                4 => write!(out, "\x1b[{count}G"),
+                _ => write!(out, "\x1b[{};{count}H", n(2) % 60),
+            };
+        }
+        4 => {
+            let target = if n(0) % 2 == 0 { 'J' } else { 'K' };
+            let _ = write!(out, "\x1b[{}{target}", n(1) % 4);
+        }
+        5 => {
+            let mode = [25, 1049, 1000, 2004, 1007][(n(0) % 5) as usize];
+            let action = if n(1) % 2 == 0 { 'h' } else { 'l' };
+            let _ = write!(out, "\x1b[?{mode}{action}");
+        }
+        6 => {
+            let _ = write!(out, "\x1b]{};{}\x07", n(0) % 3, clean(false));
+        }
+        7 => {
+            let _ = write!(
+                out,
+                "\x1b]8;id={};https://example.com/{}\x1b\\{}\x1b]8;;\x1b\\",
+                n(0),
+                n(1),
+                clean(false)
+            );
+        }
+        8 => {
+            let short = [
+                "\x1b7", "\x1b8", "\x1bc", "\x1b=", "\x1b>", "\x1b(0", "\x1b(B",
+            ];
+            out.push_str(short[(n(0) % 7) as usize]);
+        }
+        9 => out.push(['\n', '\r', '\t', '\x07', '\x08', '\0'][(n(0) % 6) as usize]),
+        10 => out.push('\x1b'),
+        11 => {
+            let _ = write!(out, "\x1b[{};", n(0) % 1000);
+        }
+        12 => {
+            let _ = write!(out, "\x1b]0;{}", clean(false));
+        }
+        13 => {
+            let _ = write!(out, "\x1b[{}m", u64::from(n(0)) + (1 << 32));
+        }
+        _ => {
+            let _ = write!(out, "\x1b[{}\u{e9}m", n(0) % 100);
+        }
+    }
+}
+
+/// A strategy for one piece of an ANSI stream, malformed ones included
+/// if `malformed` is true.
+#[cfg(feature = "proptest")]
+pub fn ansi_piece(malformed: bool) -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+
+    let kinds = if malformed { ALL_KINDS } else { VALID_KINDS };
+    (
+        0..kinds,
+        proptest::collection::vec(any::<u32>(), 0..6),
+        any::<String>(),
+    )
+        .prop_map(|(kind, nums, text)| {
+            let mut out = String::new();
+            piece(kind, &nums, &text, &mut out);
+            out
+        })
+}
+
+/// A strategy for ANSI streams that may contain malformed sequences.
+#[cfg(feature = "proptest")]
+pub fn ansi_stream() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::strategy::Strategy;
+
+    proptest::collection::vec(ansi_piece(true), 0..24).prop_map(|pieces| pieces.concat())
+}
+
+/// A strategy for ANSI streams made only of well-formed pieces.
+#[cfg(feature = "proptest")]
+pub fn valid_ansi_stream() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::strategy::Strategy;
+
+    proptest::collection::vec(ansi_piece(false), 0..24).prop_map(|pieces| pieces.concat())
+}
+
+/// An ANSI stream that may contain malformed sequences, for fuzz targets.
+#[cfg(feature = "arbitrary")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnsiStream(pub String);
+
+/// An ANSI stream made only of well-formed pieces, for fuzz targets.
+#[cfg(feature = "arbitrary")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidAnsiStream(pub String);
+
+#[cfg(feature = "arbitrary")]
+impl<'a> ::arbitrary::Arbitrary<'a> for AnsiStream {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+        arbitrary_stream(u, ALL_KINDS).map(Self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> ::arbitrary::Arbitrary<'a> for ValidAnsiStream {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+        arbitrary_stream(u, VALID_KINDS).map(Self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_stream(
+    u: &mut ::arbitrary::Unstructured<'_>,
+    kinds: u8,
+) -> ::arbitrary::Result<String> {
+    let mut out = String::new();
+    for _ in 0..u.int_in_range(0..=24_u8)? {
+        let kind = u.int_in_range(0..=kinds - 1)?;
+        let len = u.int_in_range(0..=5_u8)?;
+        let nums = (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<::arbitrary::Result<Vec<u32>>>()?;
+        let text: &str = u.arbitrary()?;
+        piece(kind, &nums, text, &mut out);
+    }
+    Ok(out)
+}
+
+/// Check what the parser guarantees for any input, panicking with the
+/// input on failure:
+///
+/// - the segments from [`parse`] put back together are the input,
+/// - [`parse_strict`] fails exactly when some segment is malformed,
+/// - [`strip_ansi`] keeps exactly the text and control segments, and
+/// - a [`StreamParser`] fed the input in two chunks loses nothing.
+///
+/// # Panics
+///
+/// Panics if any of these doesn't hold.
+pub fn assert_parser_invariants(input: &str) {
+    let segments = parse(input);
+    let mut rebuilt = String::with_capacity(input.len());
+    let mut text = String::new();
+    for segment in &segments {
+        push_raw(segment, &mut rebuilt);
+        if matches!(
+            segment,
+            ParsedSequence::Text(_) | ParsedSequence::Control(_)
+        ) {
+            push_raw(segment, &mut text);
+        }
+    }
+    assert_eq!(rebuilt, input, "parse lost or changed input: {input:?}");
+
+    let malformed = segments
+        .iter()
+        .any(|s| matches!(s, ParsedSequence::Malformed { .. }));
+    assert_eq!(
+        parse_strict(input).is_err(),
+        malformed,
+        "parse_strict disagrees with parse: {input:?}"
+    );
+    assert_eq!(
+        strip_ansi(input),
+        text,
+        "strip_ansi kept the wrong segments: {input:?}"
+    );
+
+    let mut mid = input.len() / 2;
+    while !input.is_char_boundary(mid) {
+        mid -= 1;
+    }
+    let mut stream = StreamParser::new();
+    let mut streamed = String::with_capacity(input.len());
+    for segment in stream
+        .push(&input[..mid])
+        .iter()
+        .chain(&stream.push(&input[mid..]))
+        .chain(&stream.finish())
+    {
+        push_raw(segment, &mut streamed);
+    }
+    assert_eq!(
+        streamed, input,
+        "StreamParser lost or changed input: {input:?}"
+    );
+}
+
+/// Append the input a segment was parsed from.
+fn push_raw(segment: &ParsedSequence, out: &mut String) {
+    match segment {
+        ParsedSequence::Text(text) => out.push_str(text),
+        ParsedSequence::Escape(escape) => out.push_str(&escape.raw),
+        ParsedSequence::Control(control) => out.push(control.as_char()),
+        ParsedSequence::Malformed { raw, .. } => out.push_str(raw),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pieces() {
+        let build = |kind, nums: &[u32], text| {
+            let mut out = String::new();
+            piece(kind, nums, text, &mut out);
+            out
+        };
+        assert_eq!(build(0, &[], "a\x1bb\u{9b}\n"), "ab\n");
+        assert_eq!(build(1, &[1, 139], ""), "\x1b[1;31m");
+        assert_eq!(build(3, &[5, 3, 10], ""), "\x1b[10;3H");
+        assert_eq!(build(6, &[2], "t\x07i"), "\x1b]2;ti\x07");
+
+        for kind in 0..VALID_KINDS {
+            let valid = build(kind, &[7, 8, 9, 10, 11], "text");
+            assert!(parse_strict(&valid).is_ok(), "{valid:?}");
+        }
+        for kind in VALID_KINDS..ALL_KINDS {
+            let malformed = build(kind, &[7], "text");
+            assert!(parse_strict(&malformed).is_err(), "{malformed:?}");
+            assert_parser_invariants(&malformed);
+        }
+    }
+
+    #[test]
+    fn test_parser_invariants() {
+        let mut all = String::new();
+        for kind in 0..ALL_KINDS {
+            piece(kind, &[3, 1, 4, 1, 5], "caf\u{e9} \u{4e16}", &mut all);
+        }
+        for len in 0..=all.len() {
+            if all.is_char_boundary(len) {
+                assert_parser_invariants(&all[..len]);
+            }
+        }
+    }
+}
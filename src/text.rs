@@ -0,0 +1,460 @@
+//! Operations on styled text by visible position.
+
+use crate::control::ControlChar;
+use crate::parser::{parse, ParsedSequence};
+use crate::style::Style;
+use crate::tracker::StyleTracker;
+use crate::width::WidthPolicy;
+use crate::RESET;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Bound, Range, RangeBounds};
+
+/// A match found by [`find_visible`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VisibleMatch {
+    /// Visible character positions of the match.
+    pub visible: Range<usize>,
+    /// Byte offsets of the match in the styled input, from its first to
+    /// its last visible character (escapes in between included).
+    pub bytes: Range<usize>,
+}
+
+/// The visible characters of `input` with their byte offsets.
+fn visible_chars(input: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    let mut offset = 0;
+    for segment in parse(input) {
+        match &segment {
+            ParsedSequence::Text(text) => {
+                chars.extend(text.char_indices().map(|(i, c)| (offset + i, c)));
+                offset += text.len();
+            }
+            ParsedSequence::Control(c) => {
+                chars.push((offset, c.as_char()));
+                offset += 1;
+            }
+            ParsedSequence::Escape(e) => offset += e.raw.len(),
+            ParsedSequence::Malformed { raw, .. } => offset += raw.len(),
+        }
+    }
+    chars
+}
+
+/// Visible character ranges of the non-overlapping matches of `needle`.
+fn match_ranges(chars: &[(usize, char)], needle: &str) -> Vec<Range<usize>> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let plain: String = chars.iter().map(|&(_, c)| c).collect();
+    let needle_len = needle.chars().count();
+    plain
+        .match_indices(needle)
+        .map(|(i, _)| {
+            let start = plain[..i].chars().count();
+            start..start + needle_len
+        })
+        .collect()
+}
+
+/// Find the first occurrence of `needle` in the visible text of a styled
+/// string, ignoring escape sequences.
+///
+/// Returns `None` if there is no match or `needle` is empty.
+///
+/// ```rust
+/// use glyphs::find_visible;
+///
+/// let line = "\x1b[1mwarn\x1b[0m: disk full";
+/// let m = find_visible(line, "n: d").unwrap();
+/// assert_eq!(m.visible, 3..7);
+/// assert_eq!(&line[m.bytes], "n\x1b[0m: d");
+/// ```
+#[must_use]
+pub fn find_visible(haystack: &str, needle: &str) -> Option<VisibleMatch> {
+    let chars = visible_chars(haystack);
+    let visible = match_ranges(&chars, needle).into_iter().next()?;
+    let (first, _) = chars[visible.start];
+    let (last, c) = chars[visible.end - 1];
+    Some(VisibleMatch {
+        visible,
+        bytes: first..last + c.len_utf8(),
+    })
+}
+
+/// Highlight every occurrence of `pattern` in the visible text.
+///
+/// The highlight is layered over the style already active at each match
+/// (see [`Style::patch`]), kept across escape sequences inside the match,
+/// and the original style is restored after it.
+///
+/// ```rust
+/// use glyphs::{highlight_matches, Color, Style};
+///
+/// let out = highlight_matches("\x1b[31mred alert\x1b[0m", "alert", Style::new().bg(Color::Yellow));
+/// assert_eq!(out, "\x1b[31mred \x1b[31;43malert\x1b[0m\x1b[31m\x1b[0m");
+/// ```
+#[must_use]
+pub fn highlight_matches(input: &str, pattern: &str, style: Style) -> String {
+    let chars = visible_chars(input);
+    let ranges = match_ranges(&chars, pattern);
+    if ranges.is_empty() {
+        return String::from(input);
+    }
+
+    let mut layers = vec![Style::new(); chars.len()];
+    for range in ranges {
+        layers[range].fill(style);
+    }
+    overlay(input, &layers)
+}
+
+/// Apply styles to the matches of regular expressions in the visible text.
+///
+/// Rules are applied in order, each one patched over the previous ones and
+/// over the style already active in `input` (see [`Style::patch`]), so
+/// this works on plain text as well as on output that is already colored.
+/// Patterns match against the text with escape sequences removed; empty
+/// matches are ignored.
+///
+/// ```rust
+/// use glyphs::{colorize, Color, Style};
+/// use regex::Regex;
+///
+/// let rules = [
+///     (Regex::new(r"\bERROR\b").unwrap(), Style::new().fg(Color::Red)),
+///     (Regex::new(r"\d+ms").unwrap(), Style::new().fg(Color::Cyan)),
+/// ];
+/// let out = colorize("ERROR after 30ms", &rules);
+/// assert_eq!(out, "\x1b[31mERROR\x1b[0m after \x1b[36m30ms\x1b[0m");
+/// ```
+#[cfg(feature = "regex")]
+#[must_use]
+pub fn colorize(input: &str, rules: &[(regex::Regex, Style)]) -> String {
+    let chars = visible_chars(input);
+    let plain: String = chars.iter().map(|&(_, c)| c).collect();
+    let starts: Vec<usize> = plain.char_indices().map(|(i, _)| i).collect();
+    let position = |byte: usize| starts.partition_point(|&start| start < byte);
+
+    let mut layers = vec![Style::new(); chars.len()];
+    for (pattern, style) in rules {
+        for m in pattern.find_iter(&plain).filter(|m| !m.is_empty()) {
+            for layer in &mut layers[position(m.start())..position(m.end())] {
+                *layer = layer.patch(*style);
+            }
+        }
+    }
+    if layers.iter().all(Style::is_plain) {
+        return String::from(input);
+    }
+    overlay(input, &layers)
+}
+
+/// Re-render `input` with `layers[i]` patched over the active style of
+/// visible character `i`.
+pub(crate) fn overlay(input: &str, layers: &[Style]) -> String {
+    let mut out = String::with_capacity(input.len() + layers.len());
+    let mut tracker = StyleTracker::new();
+    let mut pos = 0;
+    let mut active = Style::new();
+
+    for segment in parse(input) {
+        let mut buf = [0; 4];
+        let visible: &str = match &segment {
+            ParsedSequence::Text(text) => text,
+            ParsedSequence::Control(c) => c.as_char().encode_utf8(&mut buf),
+            ParsedSequence::Escape(e) => {
+                tracker.apply(e);
+                out.push_str(&e.raw);
+                if !active.is_plain() {
+                    out.push_str(tracker.style().patch(active).prefix().as_str());
+                }
+                continue;
+            }
+            ParsedSequence::Malformed { raw, .. } => {
+                out.push_str(raw);
+                continue;
+            }
+        };
+
+        for ch in visible.chars() {
+            let layer = layers.get(pos).copied().unwrap_or_default();
+            if layer != active {
+                active = layer;
+                out.push_str(tracker.style().patch(active).prefix().as_str());
+            }
+            out.push(ch);
+            pos += 1;
+            let next = layers.get(pos).copied().unwrap_or_default();
+            if !active.is_plain() && next != active {
+                active = Style::new();
+                out.push_str(RESET);
+                out.push_str(tracker.style().prefix().as_str());
+            }
+        }
+    }
+    out
+}
+
+/// Prepend `prefix` to every line of a styled string.
+///
+/// A style still active at the end of a line is reset before the line
+/// break and re-opened after the prefix, so the prefix keeps its own
+/// colors and the text keeps its styling on the next line.
+///
+/// ```rust
+/// use glyphs::prefix_lines;
+///
+/// let out = prefix_lines("\x1b[31mone\ntwo\x1b[0m", "| ");
+/// assert_eq!(out, "| \x1b[31mone\x1b[0m\n| \x1b[31mtwo\x1b[0m");
+/// ```
+#[must_use]
+pub fn prefix_lines(input: &str, prefix: &str) -> String {
+    let mut out = String::with_capacity(input.len() + prefix.len() * 2);
+    let mut tracker = StyleTracker::new();
+    out.push_str(prefix);
+
+    for segment in parse(input) {
+        match &segment {
+            ParsedSequence::Text(text) => out.push_str(text),
+            ParsedSequence::Control(ControlChar::LineFeed) => {
+                let style = tracker.style();
+                if !style.is_plain() {
+                    out.push_str(RESET);
+                }
+                out.push('\n');
+                out.push_str(prefix);
+                out.push_str(style.prefix().as_str());
+            }
+            ParsedSequence::Control(c) => out.push(c.as_char()),
+            ParsedSequence::Escape(e) => {
+                tracker.apply(e);
+                out.push_str(&e.raw);
+            }
+            ParsedSequence::Malformed { raw, .. } => out.push_str(raw),
+        }
+    }
+    out
+}
+
+/// Indent every line of a styled string by `n` spaces.
+///
+/// See [`prefix_lines`] for how styles are carried across lines.
+#[must_use]
+pub fn indent_ansi(input: &str, n: usize) -> String {
+    prefix_lines(input, &" ".repeat(n))
+}
+
+/// Replace each tab in a styled string with spaces up to the next tab
+/// stop, every `tabstop` columns.
+///
+/// Columns are counted on screen as by [`WidthPolicy::visible_width`]:
+/// escape sequences take none and wide characters two. A line feed or
+/// carriage return goes back to column 0. Padding and wrapping count a
+/// tab as one character, so expand tabs before laying text out. With a
+/// `tabstop` of 0, tabs are removed. Widths follow the default
+/// [`WidthPolicy`]; use [`WidthPolicy::expand_tabs`] for another.
+///
+/// ```rust
+/// use glyphs::expand_tabs;
+///
+/// assert_eq!(expand_tabs("a\tbc\td", 4), "a   bc  d");
+/// assert_eq!(expand_tabs("\x1b[1mab\x1b[0m\tc", 4), "\x1b[1mab\x1b[0m  c");
+/// ```
+#[must_use]
+pub fn expand_tabs(input: &str, tabstop: usize) -> String {
+    WidthPolicy::new().expand_tabs(input, tabstop)
+}
+
+impl WidthPolicy {
+    /// Expand tabs as [`expand_tabs`] does, counting columns with this
+    /// policy.
+    ///
+    /// ```rust
+    /// use glyphs::WidthPolicy;
+    ///
+    /// let cjk = WidthPolicy::new().ambiguous_wide(true);
+    /// assert_eq!(WidthPolicy::new().expand_tabs("\u{b1}\tx", 4), "\u{b1}   x");
+    /// assert_eq!(cjk.expand_tabs("\u{b1}\tx", 4), "\u{b1}  x");
+    /// ```
+    #[must_use]
+    pub fn expand_tabs(&self, input: &str, tabstop: usize) -> String {
+        let mut out = String::with_capacity(input.len());
+        let mut column = 0;
+
+        for segment in parse(input) {
+            match &segment {
+                ParsedSequence::Text(text) => {
+                    column += self.width(text);
+                    out.push_str(text);
+                }
+                ParsedSequence::Control(ControlChar::Tab) => {
+                    if tabstop > 0 {
+                        let n = tabstop - column % tabstop;
+                        for _ in 0..n {
+                            out.push(' ');
+                        }
+                        column += n;
+                    }
+                }
+                ParsedSequence::Control(c) => {
+                    if matches!(c, ControlChar::LineFeed | ControlChar::CarriageReturn) {
+                        column = 0;
+                    }
+                    out.push(c.as_char());
+                }
+                ParsedSequence::Escape(e) => out.push_str(&e.raw),
+                ParsedSequence::Malformed { raw, .. } => out.push_str(raw),
+            }
+        }
+        out
+    }
+}
+
+/// Extract the visible characters in `range` from a styled string.
+///
+/// Positions count characters as [`visible_len`](crate::visible_len) does.
+/// The style active at the start of the slice is re-opened, escape
+/// sequences inside the slice are kept, and a reset is added at the end if
+/// any style is still active.
+///
+/// ```rust
+/// use glyphs::slice_visible;
+///
+/// let line = "\x1b[31mhello\x1b[0m world";
+/// assert_eq!(slice_visible(line, 2..8), "\x1b[31mllo\x1b[0m wo");
+/// ```
+#[must_use]
+pub fn slice_visible(input: &str, range: impl RangeBounds<usize>) -> String {
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.saturating_add(1),
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => usize::MAX,
+    };
+
+    let mut out = String::new();
+    let mut tracker = StyleTracker::new();
+    let mut pos = 0;
+    let mut opened = false;
+
+    for segment in parse(input) {
+        let mut buf = [0; 4];
+        let visible: &str = match &segment {
+            ParsedSequence::Text(text) => text,
+            ParsedSequence::Control(c) => c.as_char().encode_utf8(&mut buf),
+            ParsedSequence::Escape(e) => {
+                tracker.apply(e);
+                if opened {
+                    out.push_str(&e.raw);
+                }
+                continue;
+            }
+            ParsedSequence::Malformed { .. } => continue,
+        };
+
+        for ch in visible.chars() {
+            if pos >= end {
+                break;
+            }
+            if pos >= start {
+                if !opened {
+                    opened = true;
+                    out.push_str(tracker.style().prefix().as_str());
+                }
+                out.push(ch);
+            }
+            pos += 1;
+        }
+        if pos >= end {
+            break;
+        }
+    }
+
+    if opened && !tracker.style().is_plain() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_visible() {
+        let line = "ab\x1b[1;32mcd\x1b[22mef\x1b[0mgh";
+        assert_eq!(slice_visible(line, 3..5), "\x1b[1;32md\x1b[22me\x1b[0m");
+        assert_eq!(slice_visible(line, 6..), "gh");
+        assert_eq!(slice_visible(line, ..2), "ab");
+        assert_eq!(slice_visible(line, 4..4), "");
+        assert_eq!(slice_visible(line, 20..), "");
+    }
+
+    #[test]
+    fn test_find_and_highlight() {
+        let line = "a\x1b[32mb\u{e9}\x1b[0mb\u{e9}";
+        let m = find_visible(line, "b\u{e9}").unwrap();
+        assert_eq!(m.visible, 1..3);
+        assert_eq!(&line[m.bytes], "b\u{e9}");
+        assert!(find_visible(line, "x").is_none());
+        assert!(find_visible(line, "").is_none());
+
+        let hl = Style::new().modifier(crate::Modifier::Reverse);
+        let out = highlight_matches("x\x1b[1my\x1b[0mz", "xyz", hl);
+        assert_eq!(out, "\x1b[7mx\x1b[1m\x1b[1;7my\x1b[0m\x1b[7mz\x1b[0m");
+        assert_eq!(crate::strip_ansi(&out), "xyz");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_colorize_merges_styles() {
+        use crate::{Color, Modifier};
+        use regex::Regex;
+
+        let rules = [
+            (
+                Regex::new("b+").unwrap(),
+                Style::new().modifier(Modifier::Bold),
+            ),
+            (Regex::new("bc").unwrap(), Style::new().bg(Color::Blue)),
+            (Regex::new("x*").unwrap(), Style::new().fg(Color::Red)),
+        ];
+        let out = colorize("a\x1b[32mbbc\x1b[0m", &rules);
+        assert_eq!(
+            out,
+            "a\x1b[32m\x1b[1;32mb\x1b[0m\x1b[32m\x1b[1;32;44mb\x1b[0m\x1b[32m\x1b[32;44mc\x1b[0m\x1b[32m\x1b[0m"
+        );
+        assert_eq!(colorize("plain", &rules), "plain");
+    }
+
+    #[test]
+    fn test_prefix_lines() {
+        let out = prefix_lines("\x1b[1ma\r\n\x1b[22mb\n", "> ");
+        assert_eq!(out, "> \x1b[1ma\r\x1b[0m\n> \x1b[1m\x1b[22mb\n> ");
+        assert_eq!(indent_ansi("x\ny", 2), "  x\n  y");
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs("\tx\n1234\ty", 4), "    x\n1234    y");
+        assert_eq!(expand_tabs("ab\rc\td", 8), "ab\rc       d");
+        assert_eq!(expand_tabs("\u{4e16}\t|", 4), "\u{4e16}  |");
+        assert_eq!(
+            expand_tabs("\x1b[31m\t\x1b[0m\t.", 2),
+            "\x1b[31m  \x1b[0m  ."
+        );
+        assert_eq!(expand_tabs("a\tb", 0), "ab");
+    }
+
+    #[test]
+    fn test_slice_visible_controls_and_unicode() {
+        let line = "\x1b[4m\u{e9}\tx\x1b[24m";
+        assert_eq!(slice_visible(line, 1..=2), "\x1b[4m\tx\x1b[0m");
+    }
+}
@@ -0,0 +1,513 @@
+//! Semantic styles and dark/light background detection.
+
+use crate::color::Color;
+use crate::modifier::Modifier;
+use crate::style::{parse_sgr_value, Style};
+#[cfg(feature = "term")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// How long [`Background::detect`] waits for the terminal to answer.
+#[cfg(feature = "term")]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Whether the terminal has a dark or a light background.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Background {
+    /// Light text on a dark background.
+    #[default]
+    Dark,
+    /// Dark text on a light background.
+    Light,
+}
+
+impl Background {
+    /// Classify a background color by its luminance.
+    ///
+    /// Returns `None` for [`Color::Default`].
+    #[must_use]
+    pub fn from_color(color: Color) -> Option<Self> {
+        // Above this, black text has more contrast than white.
+        let light = color.luminance()? > 0.179;
+        Some(if light { Self::Light } else { Self::Dark })
+    }
+
+    /// Parse a `COLORFGBG` value such as `15;0`, as set by rxvt and
+    /// Konsole.
+    ///
+    /// The last field is the background's palette index: 7 and 9–15 are
+    /// light, the other basic colors dark.
+    #[must_use]
+    pub fn from_colorfgbg(value: &str) -> Option<Self> {
+        let index: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        match index {
+            0..=6 | 8 => Some(Self::Dark),
+            7 | 9..=15 => Some(Self::Light),
+            _ => None,
+        }
+    }
+
+    /// Detect the terminal's background.
+    ///
+    /// With the `term` feature the terminal is asked for its background
+    /// color (`OSC 11`), waiting up to 100ms for an answer. Otherwise, or
+    /// if it doesn't answer, `COLORFGBG` is consulted. Returns `None` when
+    /// neither is available.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        #[cfg(feature = "term")]
+        let queried = crate::term::query_background(QUERY_TIMEOUT).and_then(Self::from_color);
+        #[cfg(not(feature = "term"))]
+        let queried = None;
+
+        queried.or_else(|| Self::from_colorfgbg(&std::env::var("COLORFGBG").ok()?))
+    }
+
+    /// Whether this is [`Background::Light`].
+    #[must_use]
+    pub const fn is_light(self) -> bool {
+        matches!(self, Self::Light)
+    }
+}
+
+/// The severity of a log message, for [`Theme::for_log_level`].
+///
+/// With the `log` feature, `log::Level` converts into it, and with the
+/// `tracing` feature, `tracing::Level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Failures.
+    Error,
+    /// Problems that don't stop the program.
+    Warn,
+    /// Normal progress messages.
+    Info,
+    /// Details for developers.
+    Debug,
+    /// Very fine-grained details.
+    Trace,
+}
+
+impl LogLevel {
+    /// The upper-case name, such as `WARN`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Self::Error,
+            log::Level::Warn => Self::Warn,
+            log::Level::Info => Self::Info,
+            log::Level::Debug => Self::Debug,
+            log::Level::Trace => Self::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<tracing_core::Level> for LogLevel {
+    fn from(level: tracing_core::Level) -> Self {
+        match level {
+            tracing_core::Level::ERROR => Self::Error,
+            tracing_core::Level::WARN => Self::Warn,
+            tracing_core::Level::INFO => Self::Info,
+            tracing_core::Level::DEBUG => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+/// How large a measured value is, for [`Theme::for_magnitude`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Magnitude {
+    /// Comfortably small or fast.
+    Low,
+    /// Worth noticing.
+    Medium,
+    /// Too large or slow.
+    High,
+}
+
+/// The role text plays in a program's output, naming a [`Theme`] field.
+///
+/// See [`Styled::role`](crate::Styled::role) for styling with a role
+/// instead of fixed colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Errors and failures ([`Theme::error`]).
+    Error,
+    /// Warnings ([`Theme::warning`]).
+    Warning,
+    /// Success messages ([`Theme::success`]).
+    Success,
+    /// Informational messages ([`Theme::info`]).
+    Info,
+    /// Secondary text ([`Theme::muted`]).
+    Muted,
+    /// Highlighted names, paths, and values ([`Theme::accent`]).
+    Accent,
+    /// Keyboard shortcuts ([`Theme::key`]).
+    Key,
+}
+
+/// Styles for the roles text plays in a program's output.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::Theme;
+///
+/// let theme = Theme::adaptive(Theme::dark(), Theme::light());
+/// println!("{}", theme.error.apply("error: file not found"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Theme {
+    /// Errors and failures.
+    pub error: Style,
+    /// Warnings.
+    pub warning: Style,
+    /// Success messages.
+    pub success: Style,
+    /// Informational messages.
+    pub info: Style,
+    /// Secondary text such as timestamps and hints.
+    pub muted: Style,
+    /// Highlighted names, paths, and values.
+    pub accent: Style,
+    /// Keyboard shortcuts, as drawn by [`keycap`](crate::keycap).
+    pub key: Style,
+}
+
+impl Theme {
+    /// A theme that leaves all text unstyled.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            error: Style::new(),
+            warning: Style::new(),
+            success: Style::new(),
+            info: Style::new(),
+            muted: Style::new(),
+            accent: Style::new(),
+            key: Style::new(),
+        }
+    }
+
+    /// Bright colors for dark backgrounds.
+    #[must_use]
+    pub const fn dark() -> Self {
+        Self {
+            error: Style::new().fg(Color::BrightRed).modifier(Modifier::Bold),
+            warning: Style::new().fg(Color::BrightYellow),
+            success: Style::new().fg(Color::BrightGreen),
+            info: Style::new().fg(Color::BrightCyan),
+            muted: Style::new().fg(Color::BrightBlack),
+            accent: Style::new().fg(Color::BrightMagenta),
+            key: Style::new().fg(Color::BrightWhite).modifier(Modifier::Bold),
+        }
+    }
+
+    /// Deeper colors for light backgrounds.
+    #[must_use]
+    pub const fn light() -> Self {
+        Self {
+            error: Style::new().fg(Color::Red).modifier(Modifier::Bold),
+            warning: Style::new().fg(Color::Ansi256(130)),
+            success: Style::new().fg(Color::Green),
+            info: Style::new().fg(Color::Blue),
+            muted: Style::new().fg(Color::Ansi256(244)),
+            accent: Style::new().fg(Color::Magenta),
+            key: Style::new().fg(Color::Black).modifier(Modifier::Bold),
+        }
+    }
+
+    /// Pick `dark` or `light` to suit the terminal's background.
+    ///
+    /// Uses [`Background::detect`]; `dark` is chosen when the background
+    /// can't be detected, and always without `std`.
+    #[must_use]
+    pub fn adaptive(dark: Self, light: Self) -> Self {
+        #[cfg(feature = "std")]
+        let background = Background::detect().unwrap_or_default();
+        #[cfg(not(feature = "std"))]
+        let background = Background::Dark;
+
+        Self::for_background(background, dark, light)
+    }
+
+    /// The style for `role`.
+    #[must_use]
+    pub const fn role(&self, role: Role) -> Style {
+        match role {
+            Role::Error => self.error,
+            Role::Warning => self.warning,
+            Role::Success => self.success,
+            Role::Info => self.info,
+            Role::Muted => self.muted,
+            Role::Accent => self.accent,
+            Role::Key => self.key,
+        }
+    }
+
+    /// The style for a log level's label: errors use [`Theme::error`],
+    /// warnings [`Theme::warning`], info [`Theme::info`], debug
+    /// [`Theme::accent`], and trace [`Theme::muted`].
+    ///
+    /// ```rust
+    /// use glyphs::{LogLevel, Theme};
+    ///
+    /// let theme = Theme::dark();
+    /// println!("{} disk almost full", theme.for_log_level(LogLevel::Warn).apply("WARN"));
+    /// ```
+    #[must_use]
+    pub fn for_log_level(&self, level: impl Into<LogLevel>) -> Style {
+        match level.into() {
+            LogLevel::Error => self.error,
+            LogLevel::Warn => self.warning,
+            LogLevel::Info => self.info,
+            LogLevel::Debug => self.accent,
+            LogLevel::Trace => self.muted,
+        }
+    }
+
+    /// The style for a measurement: low values use [`Theme::success`],
+    /// medium ones [`Theme::warning`], and high ones [`Theme::error`].
+    #[must_use]
+    pub const fn for_magnitude(&self, magnitude: Magnitude) -> Style {
+        match magnitude {
+            Magnitude::Low => self.success,
+            Magnitude::Medium => self.warning,
+            Magnitude::High => self.error,
+        }
+    }
+
+    /// Replace styles from a `GCC_COLORS`-style `key=SGR` list such as
+    /// `error=01;31:warning=01;35:note=01;36`.
+    ///
+    /// Keys are the field names, plus GCC's `note` for [`Theme::info`],
+    /// `locus` for [`Theme::muted`], and `caret` for [`Theme::accent`].
+    /// Unknown keys and malformed entries are ignored. An empty list turns
+    /// all styling off, as an empty `GCC_COLORS` does.
+    ///
+    /// ```rust
+    /// use glyphs::{Color, Modifier, Style, Theme};
+    ///
+    /// let theme = Theme::dark().with_overrides("error=01;31:note=36:range1=32");
+    /// assert_eq!(theme.error, Style::new().fg(Color::Red).modifier(Modifier::Bold));
+    /// assert_eq!(theme.info, Style::new().fg(Color::Cyan));
+    /// assert_eq!(theme.warning, Theme::dark().warning);
+    /// ```
+    #[must_use]
+    pub fn with_overrides(mut self, spec: &str) -> Self {
+        if spec.trim().is_empty() {
+            return Self::new();
+        }
+        for entry in spec.split(':') {
+            let Some((key, sgr)) = entry.split_once('=') else {
+                continue;
+            };
+            let field = match key.trim() {
+                "error" => &mut self.error,
+                "warning" => &mut self.warning,
+                "success" => &mut self.success,
+                "info" | "note" => &mut self.info,
+                "muted" | "locus" => &mut self.muted,
+                "accent" | "caret" => &mut self.accent,
+                "key" => &mut self.key,
+                _ => continue,
+            };
+            if let Some(style) = parse_sgr_value(sgr) {
+                *field = style;
+            }
+        }
+        self
+    }
+
+    /// Apply [`Theme::with_overrides`] with the value of the environment
+    /// variable `name`, if it is set.
+    ///
+    /// ```rust,no_run
+    /// use glyphs::Theme;
+    ///
+    /// let theme = Theme::dark().with_env_overrides("GCC_COLORS");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_env_overrides(self, name: &str) -> Self {
+        match std::env::var(name) {
+            Ok(spec) => self.with_overrides(&spec),
+            Err(_) => self,
+        }
+    }
+
+    /// The theme set with [`set_default_theme`], or [`Theme::dark`] if
+    /// none was set.
+    ///
+    /// Reports, keycaps, humanized values, and the logger start out with
+    /// this theme, so a library can match the application using it
+    /// without taking a `Theme` parameter.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn current() -> Self {
+        *THEME
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// The theme set with `set_default_theme`, or [`Theme::dark`] if none
+    /// was set.
+    ///
+    /// Without `std` it is always [`Theme::dark`].
+    #[cfg(not(feature = "std"))]
+    #[must_use]
+    pub const fn current() -> Self {
+        Self::dark()
+    }
+
+    /// Pick `dark` or `light` for a known background.
+    #[must_use]
+    pub const fn for_background(background: Background, dark: Self, light: Self) -> Self {
+        match background {
+            Background::Dark => dark,
+            Background::Light => light,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+static THEME: RwLock<Theme> = RwLock::new(Theme::dark());
+
+/// Set the theme returned by [`Theme::current`] for the whole process.
+///
+/// Meant for applications: set it once at startup, and libraries styling
+/// with [`Theme::current`] or [`themed!`](crate::themed) follow along.
+///
+/// ```rust
+/// use glyphs::{set_default_theme, Theme};
+///
+/// set_default_theme(Theme::adaptive(Theme::dark(), Theme::light()));
+/// println!("{}", glyphs::themed!(success, "done"));
+/// # set_default_theme(Theme::light());
+/// # assert_eq!(Theme::current(), Theme::light());
+/// # set_default_theme(Theme::dark());
+/// ```
+#[cfg(feature = "std")]
+pub fn set_default_theme(theme: Theme) {
+    *THEME
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = theme;
+}
+
+/// Style text with a role of [`Theme::current`].
+///
+/// `themed!(error, text)` is `Theme::current().error.apply(text)`; the
+/// role is any [`Theme`] field, and the text anything that is
+/// `AsRef<str>`.
+///
+/// ```rust
+/// use glyphs::{themed, Theme};
+///
+/// let path = String::from("src/main.rs");
+/// assert_eq!(themed!(accent, path), Theme::current().accent.apply("src/main.rs"));
+/// ```
+#[macro_export]
+macro_rules! themed {
+    ($role:ident, $text:expr $(,)?) => {
+        $crate::Theme::current()
+            .$role
+            .apply(::core::convert::AsRef::<str>::as_ref(&$text))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn test_background_from_color() {
+        assert_eq!(Background::from_color(Color::Black), Some(Background::Dark));
+        assert_eq!(
+            Background::from_color(Color::rgb(0xfd, 0xf6, 0xe3)),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            Background::from_color(Color::rgb(0x28, 0x2c, 0x34)),
+            Some(Background::Dark)
+        );
+        assert_eq!(Background::from_color(Color::Default), None);
+    }
+
+    #[test]
+    fn test_background_from_colorfgbg() {
+        assert_eq!(Background::from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(Background::from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(
+            Background::from_colorfgbg("0;default;7"),
+            Some(Background::Light)
+        );
+        assert_eq!(Background::from_colorfgbg("7;8"), Some(Background::Dark));
+        assert_eq!(Background::from_colorfgbg("15;default"), None);
+        assert_eq!(Background::from_colorfgbg(""), None);
+    }
+
+    #[test]
+    fn test_for_log_level() {
+        let theme = Theme::dark();
+        assert_eq!(theme.for_log_level(LogLevel::Error), theme.error);
+        assert_eq!(theme.for_log_level(LogLevel::Trace), theme.muted);
+        assert!(LogLevel::Error < LogLevel::Trace);
+        assert_eq!(LogLevel::Warn.as_str(), "WARN");
+    }
+
+    #[test]
+    fn test_for_background() {
+        let light = Theme::for_background(Background::Light, Theme::dark(), Theme::light());
+        assert_eq!(light, Theme::light());
+        let dark = Theme::for_background(Background::Dark, Theme::dark(), Theme::light());
+        assert_eq!(dark.error.foreground, Some(Color::BrightRed));
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let theme = Theme::dark().with_overrides("locus=01:caret=x:quote=01:key=7:bad:");
+        assert_eq!(theme.muted, Style::new().modifier(Modifier::Bold));
+        assert_eq!(theme.accent, Theme::dark().accent);
+        assert_eq!(theme.key, Style::new().modifier(Modifier::Reverse));
+        assert_eq!(Theme::dark().with_overrides(" "), Theme::new());
+    }
+
+    #[test]
+    fn test_themed() {
+        let text = String::from("failed");
+        assert_eq!(themed!(error, text), Theme::current().error.apply("failed"));
+        assert_eq!(themed!(muted, "x",), Theme::current().muted.apply("x"));
+    }
+
+    #[test]
+    fn test_role() {
+        let theme = Theme::light();
+        assert_eq!(theme.role(Role::Warning), theme.warning);
+        assert_eq!(theme.role(Role::Key), theme.key);
+        assert_eq!(Theme::new().role(Role::Error), Style::new());
+    }
+}
@@ -0,0 +1,124 @@
+//! Tracking the active style through a stream of escape sequences.
+
+use crate::escape::{Escape, EscapeKind};
+use crate::parser::{ParsedSequence, StreamParser};
+use crate::style::Style;
+
+/// Keeps track of the style a terminal would be using at each point of a
+/// parsed stream.
+///
+/// Feed it escapes, parsed segments, or raw output; SGR sequences update the
+/// current style (including resets and partial off-codes), and full or soft
+/// terminal resets (`ESC c`, `CSI ! p`) clear it.
+///
+/// ```rust
+/// use glyphs::{Color, Modifier, StyleTracker};
+///
+/// let mut tracker = StyleTracker::new();
+/// tracker.feed("\x1b[1;31mError\x1b[22m: ");
+/// assert_eq!(tracker.style().foreground, Some(Color::Red));
+/// assert!(!tracker.style().modifiers.contains(Modifier::Bold));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleTracker {
+    style: Style,
+    parser: StreamParser,
+}
+
+impl StyleTracker {
+    /// Create a tracker starting from the default style.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tracker starting from `style`.
+    #[must_use]
+    pub fn with_style(style: Style) -> Self {
+        Self {
+            style,
+            parser: StreamParser::new(),
+        }
+    }
+
+    /// The style currently in effect.
+    #[must_use]
+    pub const fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Apply one escape sequence, returning whether the style changed.
+    pub fn apply(&mut self, escape: &Escape) -> bool {
+        let before = self.style;
+        if escape.kind == EscapeKind::Sgr {
+            self.style.apply_sgr(&escape.params);
+        } else if escape.raw == "\x1bc" || escape.raw == "\x1b[!p" {
+            self.style = Style::new();
+        }
+        self.style != before
+    }
+
+    /// Apply a parsed segment; only escape sequences affect the style.
+    pub fn apply_segment(&mut self, segment: &ParsedSequence) -> bool {
+        segment.as_escape().is_some_and(|e| self.apply(e))
+    }
+
+    /// Parse raw output and apply every sequence in it.
+    ///
+    /// Sequences split across calls are handled; the incomplete tail of
+    /// one chunk is completed by the next.
+    pub fn feed(&mut self, input: &str) {
+        for segment in self.parser.push(input) {
+            self.apply_segment(&segment);
+        }
+    }
+
+    /// Go back to the default style.
+    pub fn reset(&mut self) {
+        self.style = Style::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::modifier::Modifier;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_tracker_off_codes_and_resets() {
+        let mut tracker = StyleTracker::new();
+        tracker.feed("\x1b[1;2;4;38;5;208;44mx\x1b[22;24m");
+        assert_eq!(
+            tracker.style(),
+            Style::new().fg(Color::Ansi256(208)).bg(Color::Blue)
+        );
+
+        tracker.feed("\x1b[49m\x1b[9m");
+        assert_eq!(
+            tracker.style(),
+            Style::new()
+                .fg(Color::Ansi256(208))
+                .modifier(Modifier::Strikethrough)
+        );
+
+        tracker.feed("\x1bc");
+        assert_eq!(tracker.style(), Style::new());
+    }
+
+    #[test]
+    fn test_tracker_split_and_segments() {
+        let mut tracker = StyleTracker::new();
+        tracker.feed("a\x1b[3");
+        assert_eq!(tracker.style(), Style::new());
+        tracker.feed("2m");
+        assert_eq!(tracker.style().foreground, Some(Color::Green));
+
+        let segments = parse("\x1b[32mb\x1b[m");
+        assert!(!tracker.apply_segment(&segments[0]));
+        assert!(!tracker.apply_segment(&segments[1]));
+        assert!(tracker.apply_segment(&segments[2]));
+        assert_eq!(tracker.style(), Style::new());
+    }
+}
@@ -0,0 +1,185 @@
+//! Nested listings drawn with `├─` / `└─` guides.
+//!
+//! [`Tree`] holds a key, an optional value, and children; [`TreeStyle`]
+//! styles the guides, keys, and values separately. Keys and values may
+//! already contain escape sequences or span several lines; continuation
+//! lines are indented under their item without breaking styles.
+//!
+//! ```rust
+//! use glyphs::strip_ansi;
+//! use glyphs::tree::Tree;
+//!
+//! let deps = Tree::new("app")
+//!     .child(Tree::new("serde").value("1.0").child(Tree::new("serde_derive")))
+//!     .child(Tree::new("regex").value("1.10"));
+//!
+//! assert_eq!(
+//!     strip_ansi(&deps.to_string()),
+//!     "app\n├─ serde: 1.0\n│  └─ serde_derive\n└─ regex: 1.10"
+//! );
+//! ```
+
+use crate::color::Color;
+use crate::style::Style;
+use crate::text::prefix_lines;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+const BRANCH: &str = "├─ ";
+const LAST_BRANCH: &str = "└─ ";
+const PIPE: &str = "│  ";
+const SPACE: &str = "   ";
+
+/// Styles for the parts of a rendered [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeStyle {
+    /// Style of the `├─`, `└─`, and `│` guides.
+    pub guide: Style,
+    /// Style of item keys.
+    pub key: Style,
+    /// Style of item values.
+    pub value: Style,
+}
+
+impl TreeStyle {
+    /// Bright black guides, plain keys, and cyan values.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            guide: Style::new().fg(Color::BrightBlack),
+            key: Style::new(),
+            value: Style::new().fg(Color::Cyan),
+        }
+    }
+}
+
+impl Default for TreeStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An item with an optional value and nested children.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Tree {
+    key: String,
+    value: Option<String>,
+    children: Vec<Tree>,
+}
+
+impl Tree {
+    /// Create an item without a value or children.
+    #[must_use]
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Show `value` after the key, separated by `: `.
+    #[must_use]
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Append a child item.
+    #[must_use]
+    pub fn child(mut self, child: Tree) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Append a child item in place.
+    pub fn push(&mut self, child: Tree) {
+        self.children.push(child);
+    }
+
+    /// The child items.
+    #[must_use]
+    pub fn children(&self) -> &[Tree] {
+        &self.children
+    }
+
+    /// Render with the given styles, one line per item and no trailing
+    /// newline.
+    #[must_use]
+    pub fn render(&self, style: &TreeStyle) -> String {
+        let mut out = String::new();
+        out.push_str(&self.label(style, ""));
+        self.render_children(style, "", &mut out);
+        out
+    }
+
+    /// The styled key and value, with continuation lines behind `indent`.
+    fn label(&self, style: &TreeStyle, indent: &str) -> String {
+        let mut label = style.key.apply(&self.key);
+        if let Some(value) = &self.value {
+            label.push_str(": ");
+            label.push_str(&style.value.apply(value));
+        }
+        if indent.is_empty() {
+            return label;
+        }
+        // `prefix_lines` also prefixes the first line, which already has
+        // its guide.
+        prefix_lines(&label, indent).split_off(indent.len())
+    }
+
+    fn render_children(&self, style: &TreeStyle, prefix: &str, out: &mut String) {
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            let (branch, rest) = if last {
+                (LAST_BRANCH, SPACE)
+            } else {
+                (BRANCH, PIPE)
+            };
+
+            let mut nested = String::from(prefix);
+            nested.push_str(&style.guide.apply(rest));
+
+            out.push('\n');
+            out.push_str(prefix);
+            out.push_str(&style.guide.apply(branch));
+            out.push_str(&child.label(style, &nested));
+            child.render_children(style, &nested, out);
+        }
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(&TreeStyle::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{set_color_choice, ColorChoice};
+    use crate::parser::strip_ansi;
+
+    #[test]
+    fn test_multiline_values_and_styles() {
+        set_color_choice(ColorChoice::Always);
+        let tree = Tree::new("root")
+            .child(Tree::new("a").value("one\ntwo").child(Tree::new("b")))
+            .child(Tree::new("c"));
+        let plain = TreeStyle {
+            guide: Style::new(),
+            key: Style::new(),
+            value: Style::new(),
+        };
+        assert_eq!(
+            tree.render(&plain),
+            "root\n├─ a: one\n│  two\n│  └─ b\n└─ c"
+        );
+
+        let styled = tree.render(&TreeStyle::new());
+        assert_eq!(strip_ansi(&styled), tree.render(&plain));
+        assert!(styled.contains("\x1b[90m├─ \x1b[0ma: \x1b[36mone\x1b[0m\n"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Checking generated output for sequences a terminal would mishandle.
+
+use crate::escape::{Escape, EscapeKind};
+use crate::parser::{parse, MalformedReason, ParsedSequence};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A problem found by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Issue {
+    /// Byte offset of the offending sequence in the input.
+    pub position: usize,
+    /// The offending sequence.
+    pub raw: String,
+    /// What is wrong with it.
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}: {:?}", self.kind, self.position, self.raw)
+    }
+}
+
+/// The kinds of problem reported by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+    /// A sequence the parser could not parse, including OSC sequences
+    /// without a terminator.
+    Malformed(MalformedReason),
+    /// A DCS string (`ESC P`) without a string terminator (`ESC \`).
+    UnterminatedDcs,
+    /// A well-formed sequence with a final byte the parser doesn't
+    /// recognize.
+    Unrecognized,
+    /// An SGR parameter no terminal defines, or an extended color
+    /// component above 255.
+    SgrOutOfRange(u32),
+    /// The alternate screen was left without being entered.
+    AltScreenNotEntered,
+    /// The alternate screen was entered and still active at the end of
+    /// the input.
+    AltScreenNotExited,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(reason) => write!(f, "{reason}"),
+            Self::UnterminatedDcs => write!(f, "unterminated DCS sequence"),
+            Self::Unrecognized => write!(f, "unrecognized escape sequence"),
+            Self::SgrOutOfRange(n) => write!(f, "SGR parameter {n} out of range"),
+            Self::AltScreenNotEntered => write!(f, "alternate screen exit without enter"),
+            Self::AltScreenNotExited => write!(f, "alternate screen never exited"),
+        }
+    }
+}
+
+/// Check `input` for sequences a terminal would mishandle, in order of
+/// position.
+///
+/// Reports malformed and unterminated sequences, sequences the parser
+/// doesn't recognize, SGR parameters out of range, and alternate screen
+/// enters and exits that don't pair up. Meant as a lint for rendered
+/// output in tests; the contents of DCS strings are not checked.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::{validate, IssueKind};
+///
+/// assert!(validate("\x1b[1;31mok\x1b[0m").is_empty());
+///
+/// let issues = validate("\x1b[?1049h\x1b[38;5;300mframe");
+/// assert_eq!(issues[0].kind, IssueKind::AltScreenNotExited);
+/// assert_eq!(issues[0].position, 0);
+/// assert_eq!(issues[1].kind, IssueKind::SgrOutOfRange(300));
+/// ```
+#[must_use]
+pub fn validate(input: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut position = 0;
+    let mut alt_screen: Option<(usize, String)> = None;
+    let mut dcs: Option<(usize, String)> = None;
+
+    for segment in parse(input) {
+        let start = position;
+        position += match &segment {
+            ParsedSequence::Text(text) => text.len(),
+            ParsedSequence::Control(c) => c.as_char().len_utf8(),
+            ParsedSequence::Escape(e) => e.raw.len(),
+            ParsedSequence::Malformed { raw, .. } => raw.len(),
+        };
+        let issue = |kind, raw: &str| Issue {
+            position: start,
+            raw: raw.to_string(),
+            kind,
+        };
+
+        if dcs.is_some() {
+            if segment.as_escape().is_some_and(|e| e.raw == "\x1b\\") {
+                dcs = None;
+            }
+            continue;
+        }
+        match &segment {
+            ParsedSequence::Malformed { raw, reason } => {
+                issues.push(issue(IssueKind::Malformed(*reason), raw));
+            }
+            ParsedSequence::Escape(e) if e.raw == "\x1bP" => dcs = Some((start, e.raw.clone())),
+            ParsedSequence::Escape(e) if e.kind == EscapeKind::Unknown => {
+                issues.push(issue(IssueKind::Unrecognized, &e.raw));
+            }
+            ParsedSequence::Escape(e) if e.kind == EscapeKind::Sgr => {
+                if let Some(n) = sgr_out_of_range(&e.params) {
+                    issues.push(issue(IssueKind::SgrOutOfRange(n), &e.raw));
+                }
+            }
+            ParsedSequence::Escape(e) => match alt_screen_switch(e) {
+                Some(true) => alt_screen = Some((start, e.raw.clone())),
+                Some(false) if alt_screen.is_none() => {
+                    issues.push(issue(IssueKind::AltScreenNotEntered, &e.raw));
+                }
+                Some(false) => alt_screen = None,
+                None => {}
+            },
+            ParsedSequence::Text(_) | ParsedSequence::Control(_) => {}
+        }
+    }
+
+    for (open, kind) in [
+        (dcs, IssueKind::UnterminatedDcs),
+        (alt_screen, IssueKind::AltScreenNotExited),
+    ] {
+        if let Some((position, raw)) = open {
+            issues.push(Issue {
+                position,
+                raw,
+                kind,
+            });
+        }
+    }
+    issues.sort_by_key(|issue| issue.position);
+    issues
+}
+
+/// The first SGR parameter no terminal defines, if any.
+fn sgr_out_of_range(params: &[u32]) -> Option<u32> {
+    let mut i = 0;
+    while i < params.len() {
+        i += match (params[i], &params[i + 1..]) {
+            (38 | 48 | 58, [5, n, ..]) => match *n {
+                0..=255 => 3,
+                n => return Some(n),
+            },
+            (38 | 48 | 58, [2, rgb @ ..]) if rgb.len() >= 3 => {
+                match rgb[..3].iter().find(|&&c| c > 255) {
+                    Some(&c) => return Some(c),
+                    None => 5,
+                }
+            }
+            (38 | 48 | 58, [mode, ..]) => return Some(*mode),
+            (code @ (38 | 48 | 58), []) => return Some(code),
+            (0..=65 | 73..=75 | 90..=97 | 100..=107, _) => 1,
+            (code, _) => return Some(code),
+        };
+    }
+    None
+}
+
+/// Whether `escape` enters (`Some(true)`) or leaves (`Some(false)`) the
+/// alternate screen.
+fn alt_screen_switch(escape: &Escape) -> Option<bool> {
+    let body = escape
+        .raw
+        .strip_prefix("\x1b[?")
+        .or_else(|| escape.raw.strip_prefix("\u{9b}?"))?;
+    let (modes, enter) = match body.strip_suffix('h') {
+        Some(modes) => (modes, true),
+        None => (body.strip_suffix('l')?, false),
+    };
+    modes
+        .split(';')
+        .any(|mode| matches!(mode, "1049" | "1047" | "47"))
+        .then_some(enter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<(usize, IssueKind)> {
+        validate(input)
+            .into_iter()
+            .map(|issue| (issue.position, issue.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_malformed_and_unknown() {
+        assert_eq!(
+            kinds("a\x1b[9zb\x1b]0;title"),
+            [
+                (1, IssueKind::Unrecognized),
+                (6, IssueKind::Malformed(MalformedReason::UnterminatedOsc)),
+            ]
+        );
+        assert!(validate("\x1bPq#0;2;0;0;0\x1b\\ok").is_empty());
+        assert_eq!(kinds("x\x1bPq\x1b[9z"), [(1, IssueKind::UnterminatedDcs)]);
+    }
+
+    #[test]
+    fn test_validate_sgr_ranges() {
+        assert_eq!(sgr_out_of_range(&[1, 38, 2, 10, 20, 30, 48, 5, 255]), None);
+        assert_eq!(sgr_out_of_range(&[38, 2, 10, 256, 0]), Some(256));
+        assert_eq!(sgr_out_of_range(&[38, 7, 1]), Some(7));
+        assert_eq!(sgr_out_of_range(&[48]), Some(48));
+        assert_eq!(sgr_out_of_range(&[1, 80]), Some(80));
+        assert_eq!(kinds("\x1b[108m"), [(0, IssueKind::SgrOutOfRange(108))]);
+    }
+
+    #[test]
+    fn test_validate_alt_screen() {
+        assert!(validate("\x1b[?1049hui\x1b[?1049l").is_empty());
+        assert_eq!(
+            kinds("\x1b[?47l\x1b[?1049h"),
+            [
+                (0, IssueKind::AltScreenNotEntered),
+                (6, IssueKind::AltScreenNotExited),
+            ]
+        );
+        let issue = &validate("\x1b[?25l\x1b[?1049l")[0];
+        assert_eq!(
+            issue.to_string(),
+            "alternate screen exit without enter at byte 6: \"\\u{1b}[?1049l\""
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Terminal column widths of text.
+
+use crate::parser::{parse_visit, Visitor};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+/// Zero-width joiner, which glues emoji into one glyph (`👩‍💻`).
+const ZWJ: char = '\u{200d}';
+
+/// Variation selector 16, which asks for the emoji form of a character.
+const VS16: char = '\u{fe0f}';
+
+/// How wide a terminal draws characters whose width Unicode leaves open.
+///
+/// Terminals disagree on these cases, so layout code that has to line up
+/// with what is on screen should match the terminal in use. The default
+/// matches most modern terminals outside East Asian locales.
+///
+/// # Example
+///
+/// ```rust
+/// use glyphs::WidthPolicy;
+///
+/// let cjk = WidthPolicy::new().ambiguous_wide(true);
+/// assert_eq!(WidthPolicy::new().width("±1"), 2);
+/// assert_eq!(cjk.width("±1"), 3);
+///
+/// let coder = "\u{1f469}\u{200d}\u{1f4bb}";
+/// assert_eq!(WidthPolicy::new().width(coder), 2);
+/// assert_eq!(WidthPolicy::new().join_zwj(false).width(coder), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidthPolicy {
+    ambiguous_wide: bool,
+    join_zwj: bool,
+    emoji_presentation: bool,
+}
+
+impl WidthPolicy {
+    /// Ambiguous characters are narrow, ZWJ sequences are one glyph, and
+    /// variation selector 16 widens the character before it.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ambiguous_wide: false,
+            join_zwj: true,
+            emoji_presentation: true,
+        }
+    }
+
+    /// Draw East Asian ambiguous characters (`±`, `·`, `→`, `○`, `①`,
+    /// ...) two columns wide, as terminals do in CJK locales or with an
+    /// "ambiguous width: wide" setting.
+    #[must_use]
+    pub const fn ambiguous_wide(mut self, wide: bool) -> Self {
+        self.ambiguous_wide = wide;
+        self
+    }
+
+    /// Treat emoji joined by zero-width joiners as one two-column glyph.
+    ///
+    /// Terminals that don't render the joined form draw each emoji in the
+    /// sequence separately; turn this off to match them.
+    #[must_use]
+    pub const fn join_zwj(mut self, join: bool) -> Self {
+        self.join_zwj = join;
+        self
+    }
+
+    /// Draw a narrow character followed by variation selector 16 (`❤️`,
+    /// `☀️`) two columns wide, as its emoji form.
+    ///
+    /// Some terminals ignore the selector and keep such characters
+    /// narrow; turn this off to match them.
+    #[must_use]
+    pub const fn emoji_presentation(mut self, wide: bool) -> Self {
+        self.emoji_presentation = wide;
+        self
+    }
+
+    /// Columns taken by one character on its own. Control characters
+    /// and combining marks take none.
+    #[must_use]
+    pub fn char_width(&self, c: char) -> usize {
+        let width = if self.ambiguous_wide {
+            c.width_cjk()
+        } else {
+            c.width()
+        };
+        width.unwrap_or(0)
+    }
+
+    /// Columns taken by one grapheme cluster, such as `e` with a combining
+    /// accent or a joined emoji sequence.
+    #[must_use]
+    pub fn grapheme_width(&self, grapheme: &str) -> usize {
+        let mut chars = grapheme.chars();
+        let Some(base) = chars.next() else {
+            return 0;
+        };
+
+        if self.join_zwj && grapheme.contains(ZWJ) {
+            return grapheme
+                .chars()
+                .map(|c| self.char_width(c))
+                .max()
+                .unwrap_or(0);
+        }
+        let mut width = self.char_width(base);
+        if self.emoji_presentation && width == 1 && grapheme.contains(VS16) {
+            width = 2;
+        }
+        width + chars.map(|c| self.char_width(c)).sum::<usize>()
+    }
+
+    /// Columns taken by plain text.
+    ///
+    /// Escape sequences are not recognized here; use
+    /// [`WidthPolicy::visible_width`] for styled text.
+    #[must_use]
+    pub fn width(&self, text: &str) -> usize {
+        text.graphemes(true).map(|g| self.grapheme_width(g)).sum()
+    }
+
+    /// Columns taken by styled text, skipping escape sequences.
+    ///
+    /// Unlike [`visible_len`](crate::visible_len), which counts
+    /// characters, this counts terminal columns: wide characters take two
+    /// and control characters none.
+    ///
+    /// ```rust
+    /// use glyphs::{visible_len, WidthPolicy};
+    ///
+    /// let styled = "\x1b[1m\u{4f60}\u{597d}\x1b[0m!";
+    /// assert_eq!(visible_len(styled), 3);
+    /// assert_eq!(WidthPolicy::new().visible_width(styled), 5);
+    /// ```
+    #[must_use]
+    pub fn visible_width(&self, input: &str) -> usize {
+        let mut counter = Counter {
+            policy: *self,
+            width: 0,
+        };
+        parse_visit(input, &mut counter);
+        counter.width
+    }
+}
+
+impl Default for WidthPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Counter {
+    policy: WidthPolicy,
+    width: usize,
+}
+
+impl Visitor for Counter {
+    fn on_text(&mut self, text: &str) {
+        self.width += self.policy.width(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_and_grapheme_width() {
+        let policy = WidthPolicy::new();
+        assert_eq!(policy.width("abc"), 3);
+        assert_eq!(policy.width("\u{4e16}\u{754c}"), 4);
+        assert_eq!(policy.width("e\u{301}"), 1);
+        assert_eq!(policy.width("\t\n"), 0);
+        assert_eq!(policy.char_width('\u{2460}'), 1);
+        assert_eq!(policy.ambiguous_wide(true).char_width('\u{2460}'), 2);
+    }
+
+    #[test]
+    fn test_emoji_policies() {
+        let heart = "\u{2764}\u{fe0f}";
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let policy = WidthPolicy::new();
+        assert_eq!(policy.width(heart), 2);
+        assert_eq!(policy.emoji_presentation(false).width(heart), 1);
+        assert_eq!(policy.width(family), 2);
+        assert_eq!(policy.join_zwj(false).width(family), 6);
+    }
+
+    #[test]
+    fn test_visible_width() {
+        let policy = WidthPolicy::new();
+        assert_eq!(policy.visible_width("\x1b[31m\u{1f600}\x1b[0m ok"), 5);
+        assert_eq!(policy.visible_width("\x1b]8;;u\x07\u{3b1}\x1b]8;;\x07"), 1);
+    }
+}